@@ -12,7 +12,7 @@
 mod support;
 
 use image::Rgba;
-use qrc::QRCode;
+use qrc::{EyeShape, EyeStyle, Gradient, ModuleShape, QRCode, QrStyle};
 
 fn main() {
     support::header("qrc -- colorize");
@@ -67,7 +67,56 @@ fn main() {
                 format!("Tip:    Dark colours on white ensure scan reliability"),
             ]
         });
+
+        // ── Full style: every renderer ─────────────────────────────────
+        support::task_with_output("Navy on cream via QrStyle", || {
+            let style = QrStyle::new(
+                Rgba([0x00, 0x33, 0x66, 0xFF]),
+                Rgba([0xFF, 0xFD, 0xF0, 0xFF]),
+            );
+            let contrast = style.contrast_ratio();
+            let styled = qr.clone().with_style(style);
+            styled.to_png(512).save(dir.join("navy.png")).unwrap();
+            std::fs::write(dir.join("navy.svg"), styled.to_svg(512)).unwrap();
+            vec![
+                format!("Contrast: {contrast:.1}:1"),
+                format!("Use:      PNG, SVG, JPG and art output in one palette"),
+            ]
+        });
+
+        // ── Gradient with accented eyes ────────────────────────────────
+        support::task_with_output("Diagonal gradient, radial eyes", || {
+            let navy = Rgba([0x00, 0x33, 0x66, 0xFF]);
+            let plum = Rgba([0x66, 0x00, 0x33, 0xFF]);
+            let style = QrStyle::default()
+                .with_gradient(Gradient::linear(45, navy, plum))
+                .with_eye_gradient(Gradient::radial(plum, navy));
+            let styled = qr.clone().with_style(style);
+            styled.to_png(512).save(dir.join("gradient.png")).unwrap();
+            std::fs::write(dir.join("gradient.svg"), styled.to_svg(512)).unwrap();
+            vec![
+                format!("Fill:  linear 45°, #003366 → #660033"),
+                format!("Eyes:  radial, #660033 → #003366"),
+                format!("Check: darkest stop must reach 3:1 contrast"),
+            ]
+        });
+
+        // ── Accent-coloured, rounded eyes ──────────────────────────────
+        support::task_with_output("Rounded eyes in an accent colour", || {
+            let crimson = Rgba([0xDC, 0x14, 0x3C, 0xFF]);
+            let styled = qr
+                .clone()
+                .with_shape(ModuleShape::Circle)
+                .with_eye_style(EyeStyle::new(EyeShape::RoundedSquare, EyeShape::Circle))
+                .with_style(QrStyle::default().with_eye_colors(crimson, crimson));
+            styled.to_png(512).save(dir.join("eyes.png")).unwrap();
+            std::fs::write(dir.join("eyes.svg"), styled.to_svg(512)).unwrap();
+            vec![
+                format!("Modules: circles"),
+                format!("Eyes:    rounded frame, round centre, #DC143C"),
+            ]
+        });
     });
 
-    support::summary(4);
+    support::summary(7);
 }
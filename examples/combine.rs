@@ -1,54 +1,60 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright (c) 2022-2026 QRC. All rights reserved.
 
-//! Combine multiple QR codes into a single composite image.
+//! Split one payload across linked QR codes with Structured Append.
 //!
-//! Useful for encoding separate data segments side-by-side,
-//! such as a multi-part payload or a visual comparison strip.
+//! Useful when a message is too long for a single comfortable symbol:
+//! each part carries its position, the symbol count and a parity byte, so
+//! a reader joins them back in order.
 //!
 //! Run: `cargo run --example combine`
 
 #[path = "support.rs"]
 mod support;
 
-use qrc::{combine_qr_codes, QRCode};
+use qrc::{decode, QRCode, SymbolVersion};
 
 fn main() {
     support::header("qrc -- combine");
 
-    // ── Combine three QR codes ─────────────────────────────────────────
-    support::task_with_output("Combine 3 QR codes (contact, website, WiFi)", || {
-        let contact = QRCode::from_string("tel:+1-555-0100".to_string());
-        let website = QRCode::from_string("https://example.com".to_string());
-        let wifi = QRCode::from_string("WIFI:T:WPA;S:Guest;P:welcome;;".to_string());
-
-        match QRCode::combine_qr_codes(&[contact, website, wifi]) {
-            Ok(combined) => vec![
-                format!("Combined data: {} bytes", combined.data.len()),
-                format!("Use case:      Multi-info display card"),
+    // ── Split into the fewest symbols ──────────────────────────────────
+    support::task_with_output("Split a long note into version 5 symbols", || {
+        let note = "Structured Append links up to sixteen symbols. ".repeat(5);
+        let qr = QRCode::from_string(note).with_version(SymbolVersion::Fixed(5));
+        match qr.try_to_structured_append() {
+            Ok(parts) => vec![
+                format!("Symbols:       {}", parts.codes().len()),
+                format!("Parity:        {:#04x}", parts.parity()),
             ],
             Err(e) => vec![format!("Error: {e}")],
         }
     });
 
-    // ── Using the macro ────────────────────────────────────────────────
-    support::task_with_output("Combine via combine_qr_codes! macro", || {
-        let qr1 = QRCode::from_string("Part 1: Header".to_string());
-        let qr2 = QRCode::from_string("Part 2: Body".to_string());
-        let qr3 = QRCode::from_string("Part 3: Footer".to_string());
-
-        match combine_qr_codes!([qr1, qr2, qr3]) {
-            Ok(combined) => vec![
-                format!("Combined data: {} bytes", combined.data.len()),
-                format!("Macro:         combine_qr_codes!([...])"),
-            ],
-            Err(e) => vec![format!("Error: {e}")],
-        }
+    // ── Split evenly and lay out a sheet ───────────────────────────────
+    support::task_with_output("Split into 3 parts and render a sheet", || {
+        let qr = QRCode::from_string("tel:+1-555-0100 | https://example.com".to_string());
+        let parts = qr.split_into(3);
+        let sheet = parts.to_sheet(4, 3);
+        let decoded: Vec<String> = parts
+            .codes()
+            .iter()
+            .filter_map(|code| decode::decode(&code.to_image(256)).ok())
+            .filter_map(|d| {
+                d.structured_append
+                    .map(|s| format!("{}/{}", s.position + 1, s.total))
+            })
+            .collect();
+        vec![
+            format!("Sheet:         {}x{} px", sheet.width(), sheet.height()),
+            format!("Read back:     {}", decoded.join(", ")),
+        ]
     });
 
-    // ── Error handling: empty input ────────────────────────────────────
-    support::task_result("Reject empty input gracefully", || {
-        QRCode::combine_qr_codes(&[]).map(|_| "should not reach here")
+    // ── Error handling: too many symbols ───────────────────────────────
+    support::task_result("Reject more than 16 symbols", || {
+        QRCode::from_string("x".repeat(64))
+            .try_split_into(17)
+            .map(|_| "should not reach here")
     })
     .ok();
 
@@ -3,15 +3,16 @@
 
 //! Encoding format management for QR codes.
 //!
-//! Demonstrates inspecting and setting the encoding format,
-//! and how unsupported formats are rejected gracefully.
+//! Demonstrates inspecting and setting the encoding format, transcoding
+//! text into another character set behind an ECI designator, and how
+//! unsupported formats are rejected gracefully.
 //!
 //! Run: `cargo run --example encoding`
 
 #[path = "support.rs"]
 mod support;
 
-use qrc::{set_encoding_format, QRCode};
+use qrc::{decode, set_encoding_format, CharacterSet, QRCode};
 
 fn main() {
     support::header("qrc -- encoding");
@@ -49,6 +50,35 @@ fn main() {
         }
     });
 
+    // ── Transcode with an ECI designator ───────────────────────────────
+    support::task_with_output("Transcode Greek and Japanese text", || {
+        let greek = QRCode::from_string("Καλημέρα".to_string());
+        let japanese = QRCode::from_string("こんにちは".to_string());
+        let mut lines = Vec::new();
+        for (qr, charset) in [
+            (greek, CharacterSet::Iso8859_7),
+            (japanese, CharacterSet::ShiftJis),
+        ] {
+            let utf8 = qr.data.len();
+            match qr.set_character_set(charset) {
+                Ok(updated) => {
+                    let read = decode::decode(&updated.to_image(256))
+                        .ok()
+                        .and_then(|d| d.decoded_text());
+                    lines.push(format!(
+                        "{:<10} ECI {:>2}: {utf8} -> {} bytes, reads {:?}",
+                        charset.name(),
+                        charset.eci(),
+                        updated.data.len(),
+                        read.unwrap_or_default()
+                    ));
+                }
+                Err(e) => lines.push(format!("Error: {e}")),
+            }
+        }
+        lines
+    });
+
     // ── Reject unsupported encoding ────────────────────────────────────
     support::task_result("Reject unsupported encoding (latin-1)", || {
         let qr = QRCode::from_string("Test".to_string());
@@ -56,5 +86,5 @@ fn main() {
     })
     .ok();
 
-    support::summary(4);
+    support::summary(5);
 }
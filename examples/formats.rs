@@ -3,7 +3,7 @@
 
 //! Export QR codes in every supported image format.
 //!
-//! Generates PNG, JPG, GIF, and SVG files for a real-world URL,
+//! Generates PNG, JPG, GIF, SVG, PDF and EPS files for a real-world URL,
 //! showing file sizes and dimensions for each format.
 //!
 //! Run: `cargo run --example formats`
@@ -11,7 +11,7 @@
 #[path = "support.rs"]
 mod support;
 
-use qrc::QRCode;
+use qrc::{Length, QRCode};
 use std::fs;
 
 fn main() {
@@ -71,7 +71,31 @@ fn main() {
                 format!("Use case:   Logos, responsive web, high-DPI print"),
             ]
         });
+
+        // ── PDF output ─────────────────────────────────────────────────
+        support::task_with_output("Export as PDF (vector, 30 mm page)", || {
+            let pdf = qr.to_pdf(Length::Millimetres(30.0));
+            let path = dir.join("qrcode.pdf");
+            fs::write(&path, &pdf).unwrap();
+            vec![
+                format!("Page:       30 x 30 mm, quiet zone included"),
+                format!("File size:  {} bytes", pdf.len()),
+                format!("Use case:   Print pipelines, labels, packaging"),
+            ]
+        });
+
+        // ── EPS output ─────────────────────────────────────────────────
+        support::task_with_output("Export as EPS (vector, CMYK)", || {
+            let eps = qr.to_eps(Length::Millimetres(30.0));
+            let path = dir.join("qrcode.eps");
+            fs::write(&path, &eps).unwrap();
+            vec![
+                format!("Bounding:   {}", eps.lines().nth(2).unwrap_or_default()),
+                format!("File size:  {} bytes", eps.len()),
+                format!("Use case:   Legacy packaging and prepress workflows"),
+            ]
+        });
     });
 
-    support::summary(4);
+    support::summary(6);
 }
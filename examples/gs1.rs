@@ -0,0 +1,32 @@
+//! Element: GS1 product data — `ElementString` (AIs, check digits, FNC1
+//! mode) and `DigitalLink` URIs.
+//!
+//! Run: `cargo run --example gs1`
+
+use qrc::payload::gs1::{DigitalLink, ElementString};
+
+fn main() {
+    let product = ElementString::new()
+        .gtin("9506000134352")
+        .batch("ABC123")
+        .expiry("261231")
+        .serial("0042");
+
+    // Element string in FNC1 mode, for GS1 scanners at the till.
+    println!("hri:  {}", product.to_hri().unwrap());
+    let qr = product.to_qr_code().unwrap();
+    let symbol = qr.to_qrcode();
+    println!(
+        "gs1:  version {:?}, {} bits",
+        symbol.version(),
+        symbol.data_bits()
+    );
+
+    // The same data as a web address any phone camera opens.
+    let link = DigitalLink::new(product).domain("https://example.com");
+    println!("link: {}", link.to_uri().unwrap());
+
+    // A wrong check digit is caught before encoding.
+    let typo = ElementString::new().gtin("9506000134353");
+    println!("typo: {}", typo.to_gs1_string().unwrap_err());
+}
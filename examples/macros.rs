@@ -106,6 +106,7 @@ fn main() {
     });
 
     // ── combine_qr_codes! ──────────────────────────────────────────────
+    #[allow(deprecated)]
     support::task_with_output("combine_qr_codes! — Merge QR codes", || {
         let qr1 = QRCode::from_string("Left".to_string());
         let qr2 = QRCode::from_string("Right".to_string());
@@ -0,0 +1,35 @@
+//! Element: Swiss QR-bill — `QrBill` (SPC 0200 payload, QRR/SCOR/NON
+//! references, 46 mm symbol with the 7 mm Swiss cross, `EcLevel::M`).
+//!
+//! Run: `cargo run --example swiss_qr_bill`
+
+use qrc::payload::swiss_qr_bill::{Address, Currency, QrBill, Reference};
+
+fn main() {
+    let creditor = Address::new("Robert Schneider AG", "2501", "Biel", "CH")
+        .street("Rue du Lac")
+        .building_number("1268");
+
+    // A QR-IBAN bill with a QR reference and a fixed amount.
+    let bill = QrBill::new(
+        "CH44 3199 9123 0008 8901 2",
+        creditor.clone(),
+        Currency::Chf,
+    )
+    .amount_cents(194_975)
+    .reference(Reference::Qrr(
+        "21 00000 00003 13947 14300 09017".to_string(),
+    ))
+    .message("Auftrag vom 15.06.2020");
+    println!("payload:\n{}\n", bill.to_qr_string().unwrap());
+
+    let img = bill.to_image(300).unwrap();
+    println!("300 dpi: {}x{} px", img.width(), img.height());
+    let svg = bill.to_svg().unwrap();
+    println!("svg:     {} bytes", svg.len());
+
+    // A QR reference needs a QR-IBAN.
+    let wrong = QrBill::new("CH93 0076 2011 6238 5295 7", creditor, Currency::Eur)
+        .reference(Reference::Qrr("210000000003139471430009017".to_string()));
+    println!("wrong:   {}", wrong.to_qr_string().unwrap_err());
+}
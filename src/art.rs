@@ -17,14 +17,11 @@
 //! ([`with_ec_level`](crate::QRCode::with_ec_level) + `EcLevel::H`) so the
 //! blended regions stay recoverable.
 
+use crate::QrStyle;
+use crate::QrcError;
+use crate::Symbol;
 use image::{imageops, ImageBuffer, Rgba, RgbaImage};
-use qrcode::{Color, QrCode};
-
-/// Quiet-zone width, in modules (the QR specification mandates 4).
-const QUIET: u32 = 4;
-
-const BLACK: [u8; 4] = [0, 0, 0, 255];
-const WHITE: [u8; 4] = [255, 255, 255, 255];
+use qrcode::Color;
 
 /// Tuning for [`QRCode::blend_image`](crate::QRCode::blend_image).
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -51,72 +48,86 @@ impl Default for BlendOptions {
 }
 
 /// Renders `code` to a square, centred, high-contrast control image with a
-/// generous quiet zone. The modules are integer-scaled to fill `size` as
-/// closely as possible; if they cannot fit, the canvas grows to the next whole
-/// module rather than distorting them.
-pub(crate) fn control_image(code: &QrCode, size: u32) -> RgbaImage {
-    let n = code.width() as u32;
-    let total = n + 2 * QUIET;
+/// `quiet`-module border, painted in `style`'s colours. The modules are
+/// integer-scaled to fill `size` as closely as possible; if they cannot fit,
+/// the canvas grows to the next whole module rather than distorting them.
+pub(crate) fn control_image(
+    code: &Symbol,
+    size: u32,
+    quiet: u32,
+    style: &QrStyle,
+) -> Result<RgbaImage, QrcError> {
+    let (w, h) = (code.width() as u32, code.height() as u32);
+    let total = w + 2 * quiet;
     let module_px = (size / total).max(1);
     let qr_dim = module_px * total;
     let dim = size.max(qr_dim);
     let offset = (dim - qr_dim) / 2;
+    let offset_y = (dim - module_px * (h + 2 * quiet)) / 2;
 
-    let mut img: RgbaImage = ImageBuffer::from_pixel(dim, dim, Rgba(WHITE));
-    for y in 0..n {
-        for x in 0..n {
+    let mut img = crate::canvas(dim, dim, style.background())?;
+    for y in 0..h {
+        for x in 0..w {
             if code[(x as usize, y as usize)] != Color::Dark {
                 continue;
             }
-            let px0 = offset + (x + QUIET) * module_px;
-            let py0 = offset + (y + QUIET) * module_px;
+            let px0 = offset + (x + quiet) * module_px;
+            let py0 = offset_y + (y + quiet) * module_px;
             for dy in 0..module_px {
                 for dx in 0..module_px {
-                    img.put_pixel(px0 + dx, py0 + dy, Rgba(BLACK));
+                    img.put_pixel(px0 + dx, py0 + dy, style.dark);
                 }
             }
         }
     }
-    img
+    Ok(img)
 }
 
-/// Weaves `background` into `code`, returning a branded, scannable image. The
-/// background is resized to the output dimensions; an empty background is
-/// treated as a blank light canvas.
-pub(crate) fn blend(code: &QrCode, background: &RgbaImage, opts: &BlendOptions) -> RgbaImage {
-    let n = code.width() as u32;
-    let total = n + 2 * QUIET;
+/// Weaves `background` into `code` inside a `quiet`-module border, tinting
+/// toward `style`'s dark and light colours, and returns a branded, scannable
+/// image. The background is resized to the output dimensions; an empty
+/// background is treated as a blank light canvas.
+pub(crate) fn blend(
+    code: &Symbol,
+    background: &RgbaImage,
+    opts: &BlendOptions,
+    quiet: u32,
+    style: &QrStyle,
+) -> RgbaImage {
+    let (w, h) = (code.width() as u32, code.height() as u32);
+    let (total_x, total_y) = (w + 2 * quiet, h + 2 * quiet);
     let m = opts.module_size.max(1);
-    let dim = total * m;
+    let (dim_x, dim_y) = (total_x * m, total_y * m);
 
     let bg: RgbaImage = if background.width() == 0 || background.height() == 0 {
-        ImageBuffer::from_pixel(dim, dim, Rgba(WHITE))
+        ImageBuffer::from_pixel(dim_x, dim_y, style.light)
     } else {
-        imageops::resize(background, dim, dim, imageops::FilterType::Lanczos3)
+        imageops::resize(background, dim_x, dim_y, imageops::FilterType::Lanczos3)
     };
 
-    let mut out: RgbaImage = ImageBuffer::new(dim, dim);
+    let mut out: RgbaImage = ImageBuffer::new(dim_x, dim_y);
     let center = (m as f32 - 1.0) / 2.0;
     let dot_r = m as f32 * opts.dot_ratio.clamp(0.0, 1.0) / 2.0;
 
-    for my in 0..total {
-        for mx in 0..total {
-            let is_quiet = mx < QUIET || my < QUIET || mx >= QUIET + n || my >= QUIET + n;
-            let (dx_mod, dy_mod) = (mx.wrapping_sub(QUIET), my.wrapping_sub(QUIET));
+    for my in 0..total_y {
+        for mx in 0..total_x {
+            let is_quiet = mx < quiet || my < quiet || mx >= quiet + w || my >= quiet + h;
+            let (dx_mod, dy_mod) = (mx.wrapping_sub(quiet), my.wrapping_sub(quiet));
             let dark_module = !is_quiet && code[(dx_mod as usize, dy_mod as usize)] == Color::Dark;
-            let finder = !is_quiet && in_finder(dx_mod as usize, dy_mod as usize, n as usize);
-            let tint = if dark_module { BLACK } else { WHITE };
+            let finder =
+                !is_quiet && finder_origin(dx_mod as usize, dy_mod as usize, code).is_some();
+            let tint = if dark_module { style.dark } else { style.light };
 
             for dy in 0..m {
                 for dx in 0..m {
                     let (px, py) = (mx * m + dx, my * m + dy);
                     let pixel = if is_quiet || finder {
-                        Rgba(tint)
+                        tint
                     } else {
                         let ddx = dx as f32 - center;
                         let ddy = dy as f32 - center;
                         if (ddx * ddx + ddy * ddy).sqrt() <= dot_r {
-                            Rgba(tint) // solid centre dot
+                            tint // solid centre dot
                         } else {
                             mix(*bg.get_pixel(px, py), tint, opts.strength)
                         }
@@ -129,17 +140,20 @@ pub(crate) fn blend(code: &QrCode, background: &RgbaImage, opts: &BlendOptions)
     out
 }
 
-/// Whether data coordinate `(x, y)` lies in one of the three 7×7 finder
-/// patterns, which are always rendered solidly so detection stays robust.
-fn in_finder(x: usize, y: usize, size: usize) -> bool {
-    const F: usize = 7;
-    let (left, right) = (x < F, x >= size - F);
-    let (top, bottom) = (y < F, y >= size - F);
-    (top && (left || right)) || (bottom && left)
+/// Side length of a finder pattern, in modules.
+pub(crate) const FINDER_SIZE: usize = 7;
+
+/// Top-left data coordinate of the 7×7 finder pattern of `code` containing
+/// `(x, y)`, if any. Finder patterns are always rendered solidly so
+/// detection stays robust.
+pub(crate) fn finder_origin(x: usize, y: usize, code: &Symbol) -> Option<(usize, usize)> {
+    const F: usize = FINDER_SIZE;
+    code.finders()
+        .find(|&(left, top)| (left..left + F).contains(&x) && (top..top + F).contains(&y))
 }
 
 /// Linearly blends `bg` toward `tint` by `strength`.
-fn mix(bg: Rgba<u8>, tint: [u8; 4], strength: f32) -> Rgba<u8> {
+fn mix(bg: Rgba<u8>, tint: Rgba<u8>, strength: f32) -> Rgba<u8> {
     let s = strength.clamp(0.0, 1.0);
     let ch = |b: u8, t: u8| ((1.0 - s) * f32::from(b) + s * f32::from(t)) as u8;
     Rgba([
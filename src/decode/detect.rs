@@ -0,0 +1,608 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Locating a QR code in an image and sampling its module grid.
+//!
+//! Finder patterns are found from their 1:1:3:1:1 dark/light run lengths,
+//! first along each row and then confirmed along the column and row through
+//! the candidate centre. Every plausible trio of finders is turned into a
+//! perspective transform, refined with the bottom-right alignment pattern
+//! when the version has one.
+
+use image::RgbaImage;
+
+/// Side of the window whose mean thresholds each pixel, as a fraction of
+/// the image's longer side.
+const WINDOW_FRACTION: usize = 16;
+
+/// Below this local standard deviation a window is treated as flat and the
+/// global threshold is used instead of its mean.
+const MIN_CONTRAST: f64 = 20.0;
+
+/// Finder candidates considered when pairing them into trios.
+const MAX_CANDIDATES: usize = 10;
+
+/// A point in continuous image coordinates, where pixel `(x, y)` covers
+/// `x..x + 1` by `y..y + 1`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Point {
+    /// Horizontal position.
+    pub(crate) x: f64,
+    /// Vertical position.
+    pub(crate) y: f64,
+}
+
+impl Point {
+    /// Euclidean distance to `other`.
+    fn distance(self, other: Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// A thresholded image.
+#[derive(Debug)]
+pub(crate) struct Binary {
+    /// Width in pixels.
+    width: usize,
+    /// Height in pixels.
+    height: usize,
+    /// `true` for dark pixels, row by row.
+    dark: Vec<bool>,
+}
+
+impl Binary {
+    /// Thresholds `img` against the mean of a window around each pixel,
+    /// falling back to a global (Otsu) threshold where the window is flat.
+    /// Transparent pixels count as light. A non-zero `blur` first averages
+    /// each pixel with its neighbours that many pixels away, which closes
+    /// the gaps between separately drawn module shapes.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn new(img: &RgbaImage, blur: usize) -> Self {
+        let (width, height) = (img.width() as usize, img.height() as usize);
+        let mut luma = luma(img);
+        if blur > 0 {
+            let sums = Integral::new(&luma, width);
+            luma = (0..width * height)
+                .map(|i| sums.window(i % width, i / width, blur).0.round() as u8)
+                .collect();
+        }
+        let global = otsu(&luma);
+
+        let sums = Integral::new(&luma, width);
+        let radius = (width.max(height) / WINDOW_FRACTION).max(4);
+        let dark = luma
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let (mean, deviation) = sums.window(i % width, i / width, radius);
+                if deviation < MIN_CONTRAST {
+                    value <= global
+                } else {
+                    f64::from(value) < mean
+                }
+            })
+            .collect();
+        Binary {
+            width,
+            height,
+            dark,
+        }
+    }
+
+    /// Whether pixel (`x`, `y`) is dark; `None` outside the image.
+    fn pixel(&self, x: isize, y: isize) -> Option<bool> {
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        (x < self.width && y < self.height).then(|| self.dark[y * self.width + x])
+    }
+
+    /// Whether the pixel under `point` is dark. Points outside the image are
+    /// light, like a quiet zone.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn is_dark(&self, point: Point) -> bool {
+        if !point.x.is_finite() || !point.y.is_finite() {
+            return false;
+        }
+        self.pixel(point.x.floor() as isize, point.y.floor() as isize)
+            .unwrap_or(false)
+    }
+}
+
+/// The greyscale value of each pixel of `img`, row by row, composited over
+/// white.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn luma(img: &RgbaImage) -> Vec<u8> {
+    img.pixels()
+        .map(|p| {
+            let [r, g, b, a] = p.0.map(u32::from);
+            let grey = (r * 299 + g * 587 + b * 114) / 1000;
+            ((grey * a + 255 * (255 - a)) / 255) as u8
+        })
+        .collect()
+}
+
+/// Summed-area tables of a greyscale image and of its squares.
+#[derive(Debug)]
+struct Integral {
+    /// Image width in pixels.
+    width: usize,
+    /// Image height in pixels.
+    height: usize,
+    /// Sums of the values above and left of each corner, `width + 1` wide.
+    sum: Vec<u64>,
+    /// Sums of the squared values, laid out like `sum`.
+    squares: Vec<u64>,
+}
+
+impl Integral {
+    /// Tables for `values`, `width` pixels per row.
+    fn new(values: &[u8], width: usize) -> Self {
+        let height = values.len().checked_div(width).unwrap_or(0);
+        let stride = width + 1;
+        let mut sum = vec![0u64; stride * (height + 1)];
+        let mut squares = vec![0u64; stride * (height + 1)];
+        for y in 0..height {
+            let (mut row_sum, mut row_squares) = (0u64, 0u64);
+            for x in 0..width {
+                let value = u64::from(values[y * width + x]);
+                row_sum += value;
+                row_squares += value * value;
+                let at = (y + 1) * stride + x + 1;
+                sum[at] = sum[at - stride] + row_sum;
+                squares[at] = squares[at - stride] + row_squares;
+            }
+        }
+        Integral {
+            width,
+            height,
+            sum,
+            squares,
+        }
+    }
+
+    /// Mean and standard deviation of the square of pixels within `radius`
+    /// of (`x`, `y`), clipped to the image.
+    #[allow(clippy::cast_precision_loss)]
+    fn window(&self, x: usize, y: usize, radius: usize) -> (f64, f64) {
+        let stride = self.width + 1;
+        let (x0, x1) = (x.saturating_sub(radius), (x + radius + 1).min(self.width));
+        let (y0, y1) = (y.saturating_sub(radius), (y + radius + 1).min(self.height));
+        let total = |table: &[u64]| {
+            (table[y1 * stride + x1] + table[y0 * stride + x0]
+                - table[y0 * stride + x1]
+                - table[y1 * stride + x0]) as f64
+        };
+        let area = ((x1 - x0) * (y1 - y0)) as f64;
+        let mean = total(&self.sum) / area;
+        let variance = total(&self.squares) / area - mean * mean;
+        (mean, variance.max(0.0).sqrt())
+    }
+}
+
+/// Otsu's threshold for `luma`: the grey level that best separates the
+/// histogram into two classes. Values at or below it are dark.
+#[allow(clippy::cast_precision_loss)]
+fn otsu(luma: &[u8]) -> u8 {
+    let mut histogram = [0u64; 256];
+    for &value in luma {
+        histogram[usize::from(value)] += 1;
+    }
+    let total = luma.len() as f64;
+    let weighted: f64 = (0..256).map(|i| i as f64 * histogram[i] as f64).sum();
+    let (mut below, mut below_weighted) = (0.0, 0.0);
+    let (mut best, mut best_variance) = (127, 0.0);
+    for level in 0..=u8::MAX {
+        below += histogram[usize::from(level)] as f64;
+        below_weighted += f64::from(level) * histogram[usize::from(level)] as f64;
+        let above = total - below;
+        if below == 0.0 || above == 0.0 {
+            continue;
+        }
+        let difference = below_weighted / below - (weighted - below_weighted) / above;
+        let variance = below * above * difference * difference;
+        if variance > best_variance {
+            best_variance = variance;
+            best = level;
+        }
+    }
+    best
+}
+
+/// Whether five run lengths are in the 1:1:3:1:1 finder proportion.
+#[allow(clippy::cast_precision_loss)]
+fn finder_ratio(runs: &[usize; 5]) -> bool {
+    let total: usize = runs.iter().sum();
+    if runs.contains(&0) || total < 7 {
+        return false;
+    }
+    let module = total as f64 / 7.0;
+    let tolerance = module / 2.0;
+    runs.iter()
+        .zip([1.0, 1.0, 3.0, 1.0, 1.0])
+        .all(|(&run, weight)| (run as f64 - weight * module).abs() < weight * tolerance)
+}
+
+/// A confirmed finder-pattern centre.
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    /// Centre of the pattern.
+    centre: Point,
+    /// Estimated module size, in pixels.
+    module: f64,
+    /// Rows on which the pattern was found.
+    count: usize,
+}
+
+/// Finds the finder patterns in `binary`, most often confirmed first.
+#[allow(clippy::cast_possible_wrap)]
+fn finder_candidates(binary: &Binary) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = Vec::new();
+    for y in 0..binary.height {
+        let mut runs = [0usize; 5];
+        let mut state = 0;
+        for x in 0..=binary.width {
+            let dark = x < binary.width && binary.dark[y * binary.width + x];
+            if dark == (state % 2 == 0) {
+                runs[state] += 1;
+                continue;
+            }
+            if state < 4 {
+                state += 1;
+                runs[state] += 1;
+                continue;
+            }
+            // A light pixel ends the fifth run.
+            if finder_ratio(&runs) {
+                if let Some(found) = confirm(binary, &runs, x, y) {
+                    merge(&mut candidates, found);
+                }
+            }
+            runs = [runs[2], runs[3], runs[4], 1, 0];
+            state = 3;
+        }
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.count));
+    candidates.truncate(MAX_CANDIDATES);
+    candidates
+}
+
+/// Cross-checks a row match whose last run ends before pixel `end` on row
+/// `y`: vertically through its centre, then horizontally again through the
+/// vertical centre.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+fn confirm(binary: &Binary, runs: &[usize; 5], end: usize, y: usize) -> Option<Candidate> {
+    let total: usize = runs.iter().sum();
+    let x = end as f64 - runs[4] as f64 - runs[3] as f64 - runs[2] as f64 / 2.0;
+    let column = x.floor() as isize;
+    let (dy, vertical) = cross_check(binary, column, y as isize, (0, 1), total)?;
+    if 5 * vertical.abs_diff(total) >= 2 * total {
+        return None;
+    }
+    let centre_y = y as f64 + dy;
+    let (dx, horizontal) = cross_check(binary, column, centre_y.floor() as isize, (1, 0), total)?;
+    if 5 * horizontal.abs_diff(total) >= 2 * total {
+        return None;
+    }
+    Some(Candidate {
+        centre: Point {
+            x: column as f64 + dx,
+            y: centre_y,
+        },
+        module: (horizontal + vertical) as f64 / 14.0,
+        count: 1,
+    })
+}
+
+/// Measures the five runs through pixel (`x`, `y`) along `step`, each at
+/// most `max` long, and returns the centre of the middle run relative to the
+/// pixel together with the total length, if they form a finder pattern.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_wrap)]
+fn cross_check(
+    binary: &Binary,
+    x: isize,
+    y: isize,
+    step: (isize, isize),
+    max: usize,
+) -> Option<(f64, usize)> {
+    let at = |k: isize| binary.pixel(x + step.0 * k, y + step.1 * k);
+    if at(0) != Some(true) {
+        return None;
+    }
+    let mut runs = [0usize; 5];
+    let mut k = 0;
+    for (run, dark) in [(2, true), (1, false), (0, true)] {
+        while at(k) == Some(dark) && runs[run] <= max {
+            runs[run] += 1;
+            k -= 1;
+        }
+        if runs[run] > max || (run > 0 && at(k).is_none()) {
+            return None;
+        }
+    }
+    k = 1;
+    for (run, dark) in [(2, true), (3, false), (4, true)] {
+        while at(k) == Some(dark) && runs[run] <= max {
+            runs[run] += 1;
+            k += 1;
+        }
+        if runs[run] > max || (run < 4 && at(k).is_none()) {
+            return None;
+        }
+    }
+    if !finder_ratio(&runs) {
+        return None;
+    }
+    let centre = k as f64 - runs[4] as f64 - runs[3] as f64 - runs[2] as f64 / 2.0;
+    Some((centre, runs.iter().sum()))
+}
+
+/// Folds `found` into a nearby candidate of similar size, or adds it.
+#[allow(clippy::cast_precision_loss)]
+fn merge(candidates: &mut Vec<Candidate>, found: Candidate) {
+    let near = candidates.iter_mut().find(|c| {
+        c.centre.distance(found.centre) <= c.module.max(found.module)
+            && (c.module - found.module).abs() <= c.module.max(found.module) / 2.0
+    });
+    match near {
+        Some(c) => {
+            let weight = c.count as f64;
+            let blend = |a: f64, b: f64| (a * weight + b) / (weight + 1.0);
+            c.centre = Point {
+                x: blend(c.centre.x, found.centre.x),
+                y: blend(c.centre.y, found.centre.y),
+            };
+            c.module = blend(c.module, found.module);
+            c.count += 1;
+        }
+        None => candidates.push(found),
+    }
+}
+
+/// Three finder patterns taken to be the corners of one symbol.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Finders {
+    /// Centre of the top-left finder.
+    top_left: Point,
+    /// Centre of the top-right finder.
+    top_right: Point,
+    /// Centre of the bottom-left finder.
+    bottom_left: Point,
+    /// Average module size, in pixels.
+    module: f64,
+}
+
+/// Every trio of finder candidates that could form a symbol, the most
+/// square first.
+pub(crate) fn locate(binary: &Binary) -> Vec<Finders> {
+    let candidates = finder_candidates(binary);
+    let mut trios: Vec<(f64, Finders)> = Vec::new();
+    for i in 0..candidates.len() {
+        for j in i + 1..candidates.len() {
+            for k in j + 1..candidates.len() {
+                let trio = [candidates[i], candidates[j], candidates[k]];
+                if let Some(found) = orient(trio) {
+                    trios.push(found);
+                }
+            }
+        }
+    }
+    trios.sort_by(|a, b| a.0.total_cmp(&b.0));
+    trios.into_iter().map(|(_, finders)| finders).collect()
+}
+
+/// Orders `trio` as top-left, top-right and bottom-left, returning it with
+/// how far it is from an isosceles right triangle of similar modules.
+fn orient(trio: [Candidate; 3]) -> Option<(f64, Finders)> {
+    let modules = trio.map(|c| c.module);
+    let smallest = modules.iter().copied().fold(f64::INFINITY, f64::min);
+    let largest = modules.iter().copied().fold(0.0, f64::max);
+    if largest > smallest * 1.5 {
+        return None;
+    }
+    let [a, b, c] = trio.map(|c| c.centre);
+    // The top-left finder is opposite the longest side.
+    let (ab, bc, ac) = (a.distance(b), b.distance(c), a.distance(c));
+    let (top_left, mut first, mut second, hypotenuse) = if bc >= ab && bc >= ac {
+        (a, b, c, bc)
+    } else if ac >= ab {
+        (b, a, c, ac)
+    } else {
+        (c, a, b, ab)
+    };
+    let (legs_a, legs_b) = (top_left.distance(first), top_left.distance(second));
+    let module = (modules[0] + modules[1] + modules[2]) / 3.0;
+    if legs_a.min(legs_b) < 12.0 * module {
+        return None;
+    }
+    let squareness = (legs_a - legs_b).abs() / legs_a.max(legs_b);
+    let right_angle = (hypotenuse * hypotenuse - legs_a * legs_a - legs_b * legs_b).abs()
+        / (hypotenuse * hypotenuse);
+    if squareness > 0.4 || right_angle > 0.4 {
+        return None;
+    }
+    // Clockwise in image coordinates: top-left, top-right, bottom-left.
+    let cross = (first.x - top_left.x) * (second.y - top_left.y)
+        - (first.y - top_left.y) * (second.x - top_left.x);
+    if cross < 0.0 {
+        std::mem::swap(&mut first, &mut second);
+    }
+    Some((
+        squareness + right_angle,
+        Finders {
+            top_left,
+            top_right: first,
+            bottom_left: second,
+            module,
+        },
+    ))
+}
+
+impl Finders {
+    /// Candidate symbol sizes, in modules, the most likely first.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn sizes(&self) -> Vec<usize> {
+        let across = (self.top_left.distance(self.top_right)
+            + self.top_left.distance(self.bottom_left))
+            / (2.0 * self.module)
+            + 7.0;
+        let mut sizes: Vec<usize> = (21..=177).step_by(4).collect();
+        sizes.sort_by(|&a, &b| {
+            (a as f64 - across)
+                .abs()
+                .total_cmp(&(b as f64 - across).abs())
+        });
+        sizes.truncate(3);
+        sizes
+    }
+
+    /// The transform from module to image coordinates for a `size`-module
+    /// symbol, refined with the alignment pattern when one is found.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn transform(&self, binary: &Binary, size: usize) -> Option<Transform> {
+        let far = size as f64 - 3.5;
+        let corner = Point {
+            x: self.top_right.x + self.bottom_left.x - self.top_left.x,
+            y: self.top_right.y + self.bottom_left.y - self.top_left.y,
+        };
+        let modules = [(3.5, 3.5), (far, 3.5), (3.5, far), (far, far)];
+        let transform = Transform::between(
+            modules,
+            [self.top_left, self.top_right, self.bottom_left, corner],
+        )?;
+        if size > 21 {
+            let centre = size as f64 - 6.5;
+            if let Some(alignment) = self.alignment(binary, &transform, centre) {
+                return Transform::between(
+                    [modules[0], modules[1], modules[2], (centre, centre)],
+                    [self.top_left, self.top_right, self.bottom_left, alignment],
+                )
+                .or(Some(transform));
+            }
+        }
+        Some(transform)
+    }
+
+    /// Samples a `size`-module grid from `binary`, row by row, with `true`
+    /// for dark modules.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn sample(&self, binary: &Binary, size: usize) -> Option<Vec<bool>> {
+        let transform = self.transform(binary, size)?;
+        let mut grid = Vec::with_capacity(size * size);
+        for y in 0..size {
+            for x in 0..size {
+                let point = transform.apply(x as f64 + 0.5, y as f64 + 0.5);
+                grid.push(binary.is_dark(point));
+            }
+        }
+        Some(grid)
+    }
+
+    /// Searches around the expected position of the alignment pattern
+    /// centred on module (`centre`, `centre`) for a dark module ringed by
+    /// light and then dark modules, returning the middle of the best match.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap
+    )]
+    fn alignment(&self, binary: &Binary, estimate: &Transform, centre: f64) -> Option<Point> {
+        let expected = estimate.apply(centre, centre);
+        let origin = estimate.apply(centre - 1.0, centre - 1.0);
+        let across = estimate.apply(centre, centre - 1.0);
+        let down = estimate.apply(centre - 1.0, centre);
+        let step_x = (across.x - origin.x, across.y - origin.y);
+        let step_y = (down.x - origin.x, down.y - origin.y);
+        let ring = |radius: f64| {
+            (-1i8..=1)
+                .flat_map(|i| (-1i8..=1).map(move |j| (i, j)))
+                .filter(|&(i, j)| i != 0 || j != 0)
+                .map(move |(i, j)| (f64::from(i) * radius, f64::from(j) * radius))
+        };
+        let score = |at: Point| {
+            let dark = |(i, j): (f64, f64)| {
+                binary.is_dark(Point {
+                    x: at.x + i * step_x.0 + j * step_y.0,
+                    y: at.y + i * step_x.1 + j * step_y.1,
+                })
+            };
+            usize::from(dark((0.0, 0.0)))
+                + ring(1.0).filter(|&offset| !dark(offset)).count()
+                + ring(2.0).filter(|&offset| dark(offset)).count()
+        };
+
+        let reach = (4.0 * self.module).ceil() as isize;
+        let stride = ((self.module / 4.0).floor() as usize).max(1);
+        let mut best = (0, 0.0, 0.0, 0.0);
+        for dy in (-reach..=reach).step_by(stride) {
+            for dx in (-reach..=reach).step_by(stride) {
+                let at = Point {
+                    x: expected.x + dx as f64,
+                    y: expected.y + dy as f64,
+                };
+                let found = score(at);
+                if found > best.0 {
+                    best = (found, at.x, at.y, 1.0);
+                } else if found == best.0 {
+                    best = (found, best.1 + at.x, best.2 + at.y, best.3 + 1.0);
+                }
+            }
+        }
+        // Two of the 17 samples may be misread.
+        (best.0 >= 15).then(|| Point {
+            x: best.1 / best.3,
+            y: best.2 / best.3,
+        })
+    }
+}
+
+/// A perspective transform from module to image coordinates.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Transform([f64; 8]);
+
+impl Transform {
+    /// The transform mapping each of the four `from` points onto the matching
+    /// `to` point, or `None` when the points are degenerate.
+    pub(crate) fn between(from: [(f64, f64); 4], to: [Point; 4]) -> Option<Self> {
+        let mut system = [[0.0; 9]; 8];
+        for (i, (&(u, v), p)) in from.iter().zip(to).enumerate() {
+            system[2 * i] = [u, v, 1.0, 0.0, 0.0, 0.0, -u * p.x, -v * p.x, p.x];
+            system[2 * i + 1] = [0.0, 0.0, 0.0, u, v, 1.0, -u * p.y, -v * p.y, p.y];
+        }
+        for column in 0..8 {
+            let pivot = (column..8)
+                .max_by(|&a, &b| system[a][column].abs().total_cmp(&system[b][column].abs()))?;
+            if system[pivot][column].abs() < 1e-12 {
+                return None;
+            }
+            system.swap(column, pivot);
+            let pivot_row = system[column];
+            for (row, equation) in system.iter_mut().enumerate() {
+                if row != column {
+                    let factor = equation[column] / pivot_row[column];
+                    for (value, &p) in equation.iter_mut().zip(&pivot_row).skip(column) {
+                        *value -= factor * p;
+                    }
+                }
+            }
+        }
+        let mut h = [0.0; 8];
+        for (i, value) in h.iter_mut().enumerate() {
+            *value = system[i][8] / system[i][i];
+        }
+        Some(Transform(h))
+    }
+
+    /// The image point for module coordinates (`u`, `v`).
+    pub(crate) fn apply(&self, u: f64, v: f64) -> Point {
+        let h = &self.0;
+        let w = h[6] * u + h[7] * v + 1.0;
+        Point {
+            x: (h[0] * u + h[1] * v + h[2]) / w,
+            y: (h[3] * u + h[4] * v + h[5]) / w,
+        }
+    }
+}
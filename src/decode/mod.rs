@@ -0,0 +1,702 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Reading QR codes back from images.
+//!
+//! [`decode`] finds a QR code in an [`RgbaImage`] and returns its payload, so
+//! a styled, blended or watermarked rendering can be checked before it
+//! ships. The image is thresholded against its local mean, the finder
+//! patterns are located from their run lengths, the module grid is sampled
+//! through a perspective transform anchored on the finders and the
+//! bottom-right alignment pattern, and each block is repaired with
+//! Reed-Solomon error correction before the segments are parsed.
+//!
+//! # Examples
+//!
+//! ```
+//! use qrc::{decode, EcLevel, QRCode};
+//!
+//! let qr = QRCode::from_string("https://example.com".to_string());
+//! let decoded = decode::decode(&qr.to_image(256)).unwrap();
+//! assert_eq!(decoded.text(), Some("https://example.com"));
+//! assert_eq!((decoded.version, decoded.ec_level), (2, EcLevel::M));
+//! ```
+
+pub(crate) mod detect;
+mod reed_solomon;
+
+use crate::{CharacterSet, QrcError, Sequence};
+use detect::{Binary, Finders};
+use image::RgbaImage;
+use qrcode::EcLevel;
+use std::fmt;
+
+/// A decoded QR code.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    /// The payload bytes, concatenated across segments. Kanji segments are
+    /// returned as Shift JIS.
+    pub data: Vec<u8>,
+    /// Symbol version, `1..=40`.
+    pub version: u8,
+    /// Error-correction level read from the format information.
+    pub ec_level: EcLevel,
+    /// Data mask pattern, `0..=7`.
+    pub mask: u8,
+    /// Codewords repaired by error correction, across all blocks.
+    pub corrected: usize,
+    /// The Structured Append header, if the symbol is one of a sequence.
+    pub structured_append: Option<Sequence>,
+    /// The ECI designator naming the payload's character set, if any.
+    pub eci: Option<u32>,
+    /// Whether the symbol carries the FNC1 first-position indicator of GS1
+    /// data; group separators in the payload are then `0x1D`.
+    pub gs1: bool,
+}
+
+impl Decoded {
+    /// The payload as text, if it is valid UTF-8.
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.data).ok()
+    }
+
+    /// The payload as text in the character set its ECI designator names,
+    /// or in UTF-8 without one; `None` if the bytes are not valid in that
+    /// set or the designator is not one [`CharacterSet`] knows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{decode, QRCode};
+    ///
+    /// let qr = QRCode::from_string("Ωmega".to_string())
+    ///     .set_encoding_format("iso-8859-7")
+    ///     .unwrap();
+    /// let decoded = decode::decode(&qr.to_image(256)).unwrap();
+    /// assert_eq!(decoded.eci, Some(9));
+    /// assert_eq!(decoded.text(), None);
+    /// assert_eq!(decoded.decoded_text().as_deref(), Some("Ωmega"));
+    /// ```
+    #[must_use]
+    pub fn decoded_text(&self) -> Option<String> {
+        let charset = match self.eci {
+            Some(eci) => CharacterSet::from_eci(eci)?,
+            None => CharacterSet::Utf8,
+        };
+        charset.decode(&self.data)
+    }
+}
+
+/// Finds and decodes a QR code in `img`.
+///
+/// Any rotation is handled, as is moderate perspective. Dark-on-light codes
+/// are expected, with transparent pixels read as light.
+///
+/// # Errors
+///
+/// Returns [`QrcError::Decode`] if no code is found, its format information
+/// is unreadable, or it has more errors than its error-correction level can
+/// repair.
+pub fn decode(img: &RgbaImage) -> Result<Decoded, QrcError> {
+    let mut failure = Failure::NotFound;
+    // Exact thresholds first; blurring only helps shapes drawn with gaps.
+    for blur in BLUR_RADII {
+        let binary = Binary::new(img, blur);
+        for finders in detect::locate(&binary) {
+            match read_finders(&binary, &finders) {
+                Ok(decoded) => return Ok(decoded),
+                Err(other) => failure = failure.max(other),
+            }
+        }
+    }
+    Err(QrcError::Decode(failure.to_string()))
+}
+
+/// Blur radii tried in turn, in pixels.
+const BLUR_RADII: [usize; 3] = [0, 1, 3];
+
+/// Samples and reads the symbol anchored on `finders`, trying the likely
+/// sizes and the mirror image of each.
+fn read_finders(binary: &Binary, finders: &Finders) -> Result<Decoded, Failure> {
+    let mut failure = Failure::NotFound;
+    let mut tried = Vec::new();
+    let mut sizes = finders.sizes();
+    sizes.reverse();
+    while let Some(size) = sizes.pop().filter(|_| tried.len() < 6) {
+        if tried.contains(&size) {
+            continue;
+        }
+        tried.push(size);
+        let Some(modules) = finders.sample(binary, size) else {
+            continue;
+        };
+        let grid = Grid { size, modules };
+        let mirrored = grid.transposed();
+        for grid in [grid, mirrored] {
+            match read(&grid) {
+                Ok(decoded) => return Ok(decoded),
+                // The version information knows better than the finder
+                // spacing.
+                Err(Failure::Size(size)) => sizes.push(size),
+                Err(other) => failure = failure.max(other),
+            }
+        }
+    }
+    Err(failure)
+}
+
+/// How far decoding got before it failed, in pipeline order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Failure {
+    /// No trio of finder patterns was found.
+    NotFound,
+    /// The sampled grid's version information names another size.
+    Size(usize),
+    /// Neither copy of the format information was readable.
+    Format,
+    /// A block had more errors than it can correct.
+    Correction,
+    /// The corrected data did not parse as segments.
+    Segments,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Failure::NotFound | Failure::Size(_) => "no QR code found",
+            Failure::Format => "format information is unreadable",
+            Failure::Correction => "too many errors to correct",
+            Failure::Segments => "data segments are malformed",
+        })
+    }
+}
+
+/// A sampled square of modules, `true` for dark.
+#[derive(Clone, Debug)]
+struct Grid {
+    /// Side, in modules.
+    size: usize,
+    /// Modules, row by row.
+    modules: Vec<bool>,
+}
+
+impl Grid {
+    /// The module at column `x`, row `y`.
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    /// The grid mirrored across its main diagonal.
+    fn transposed(&self) -> Self {
+        let modules = (0..self.size * self.size)
+            .map(|i| self.get(i / self.size, i % self.size))
+            .collect();
+        Grid {
+            size: self.size,
+            modules,
+        }
+    }
+
+    /// The bits at `coordinates`, first one most significant.
+    fn bits(&self, coordinates: impl Iterator<Item = (usize, usize)>) -> u32 {
+        coordinates.fold(0, |acc, (x, y)| (acc << 1) | u32::from(self.get(x, y)))
+    }
+}
+
+/// Decodes a sampled grid.
+fn read(grid: &Grid) -> Result<Decoded, Failure> {
+    let size = grid.size;
+    let version = (size - 17) / 4;
+    if version >= 7 {
+        match read_version(grid) {
+            Some(found) if found == version => {}
+            Some(found) => return Err(Failure::Size(17 + 4 * found)),
+            None => return Err(Failure::Format),
+        }
+    }
+    let (ec_level, mask) = read_format(grid).ok_or(Failure::Format)?;
+
+    let codewords: Vec<u8> = data_modules(version)
+        .chunks_exact(8)
+        .map(|byte| {
+            byte.iter().fold(0, |acc, &(x, y)| {
+                (acc << 1) | u8::from(grid.get(x, y) != masked(mask, x, y))
+            })
+        })
+        .collect();
+    let layout = Blocks::new(version, ec_level);
+    let mut blocks = vec![Vec::new(); layout.count];
+    for (codeword, b) in codewords.into_iter().zip(layout.interleaving()) {
+        blocks[b].push(codeword);
+    }
+
+    let mut data = Vec::new();
+    let mut corrected = 0;
+    for (b, mut block) in blocks.into_iter().enumerate() {
+        corrected += reed_solomon::correct(&mut block, layout.ec).ok_or(Failure::Correction)?;
+        data.extend_from_slice(&block[..layout.data_len(b)]);
+    }
+    let payload = segments(&data, version).ok_or(Failure::Segments)?;
+    Ok(Decoded {
+        data: payload.data,
+        version: u8::try_from(version).unwrap_or(u8::MAX),
+        ec_level,
+        mask,
+        corrected,
+        structured_append: payload.structured_append,
+        eci: payload.eci,
+        gs1: payload.gs1,
+    })
+}
+
+/// The data modules of a `version` symbol in placement order: two-column
+/// strips from the right edge, alternately upward and downward, skipping the
+/// vertical timing pattern.
+pub(crate) fn data_modules(version: usize) -> Vec<(usize, usize)> {
+    let size = 17 + 4 * version;
+    let function = function_modules(version);
+    let mut order = Vec::new();
+    let mut upward = true;
+    let mut right = size - 1;
+    while right > 0 {
+        if right == 6 {
+            right -= 1;
+        }
+        for i in 0..size {
+            let y = if upward { size - 1 - i } else { i };
+            for x in [right, right - 1] {
+                if !function[y * size + x] {
+                    order.push((x, y));
+                }
+            }
+        }
+        upward = !upward;
+        right = right.saturating_sub(2);
+    }
+    order
+}
+
+/// How a symbol's codewords divide into Reed-Solomon blocks.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Blocks {
+    /// Error-correction codewords per block.
+    pub(crate) ec: usize,
+    /// Number of blocks.
+    pub(crate) count: usize,
+    /// Codewords in each of the shorter blocks.
+    short: usize,
+    /// Trailing blocks holding one more data codeword.
+    long: usize,
+    /// Error-correction codewords set aside to detect misdecodes rather
+    /// than correct errors (ISO/IEC 18004 table 9, "p").
+    protected: usize,
+}
+
+impl Blocks {
+    /// The blocks of a `version` symbol at `level`.
+    pub(crate) fn new(version: usize, level: EcLevel) -> Self {
+        let (ec, count) = BLOCKS[version - 1];
+        let level = ec_index(level);
+        let (ec, count) = (ec[level], count[level]);
+        let total = data_modules(version).len() / 8;
+        let protected = match (version, level) {
+            (1, 0) => 3,
+            (1, 1) | (2, 0) => 2,
+            (1, _) | (3, 0) => 1,
+            _ => 0,
+        };
+        Blocks {
+            ec,
+            count,
+            short: total / count,
+            long: total % count,
+            protected,
+        }
+    }
+
+    /// Codewords each block is guaranteed to correct.
+    pub(crate) fn correctable(&self) -> usize {
+        (self.ec - self.protected) / 2
+    }
+
+    /// Data codewords in block `b`.
+    pub(crate) fn data_len(&self, b: usize) -> usize {
+        self.short - self.ec + usize::from(b >= self.count - self.long)
+    }
+
+    /// The block of each codeword in placement order: data codewords taken
+    /// from each block in turn, then error-correction codewords likewise.
+    pub(crate) fn interleaving(&self) -> Vec<usize> {
+        let mut order = Vec::new();
+        for i in 0..=self.short - self.ec {
+            order.extend((0..self.count).filter(|&b| i < self.data_len(b)));
+        }
+        for _ in 0..self.ec {
+            order.extend(0..self.count);
+        }
+        order
+    }
+}
+
+/// Error-correction codewords per block and number of blocks for versions
+/// 1–40, each indexed L, M, Q, H (ISO/IEC 18004 table 9).
+const BLOCKS: [([usize; 4], [usize; 4]); 40] = [
+    ([7, 10, 13, 17], [1, 1, 1, 1]),
+    ([10, 16, 22, 28], [1, 1, 1, 1]),
+    ([15, 26, 18, 22], [1, 1, 2, 2]),
+    ([20, 18, 26, 16], [1, 2, 2, 4]),
+    ([26, 24, 18, 22], [1, 2, 4, 4]),
+    ([18, 16, 24, 28], [2, 4, 4, 4]),
+    ([20, 18, 18, 26], [2, 4, 6, 5]),
+    ([24, 22, 22, 26], [2, 4, 6, 6]),
+    ([30, 22, 20, 24], [2, 5, 8, 8]),
+    ([18, 26, 24, 28], [4, 5, 8, 8]),
+    ([20, 30, 28, 24], [4, 5, 8, 11]),
+    ([24, 22, 26, 28], [4, 8, 10, 11]),
+    ([26, 22, 24, 22], [4, 9, 12, 16]),
+    ([30, 24, 20, 24], [4, 9, 16, 16]),
+    ([22, 24, 30, 24], [6, 10, 12, 18]),
+    ([24, 28, 24, 30], [6, 10, 17, 16]),
+    ([28, 28, 28, 28], [6, 11, 16, 19]),
+    ([30, 26, 28, 28], [6, 13, 18, 21]),
+    ([28, 26, 26, 26], [7, 14, 21, 25]),
+    ([28, 26, 30, 28], [8, 16, 20, 25]),
+    ([28, 26, 28, 30], [8, 17, 23, 25]),
+    ([28, 28, 30, 24], [9, 17, 23, 34]),
+    ([30, 28, 30, 30], [9, 18, 25, 30]),
+    ([30, 28, 30, 30], [10, 20, 27, 32]),
+    ([26, 28, 30, 30], [12, 21, 29, 35]),
+    ([28, 28, 28, 30], [12, 23, 34, 37]),
+    ([30, 28, 30, 30], [12, 25, 34, 40]),
+    ([30, 28, 30, 30], [13, 26, 35, 42]),
+    ([30, 28, 30, 30], [14, 28, 38, 45]),
+    ([30, 28, 30, 30], [15, 29, 40, 48]),
+    ([30, 28, 30, 30], [16, 31, 43, 51]),
+    ([30, 28, 30, 30], [17, 33, 45, 54]),
+    ([30, 28, 30, 30], [18, 35, 48, 57]),
+    ([30, 28, 30, 30], [19, 37, 51, 60]),
+    ([30, 28, 30, 30], [19, 38, 53, 63]),
+    ([30, 28, 30, 30], [20, 40, 56, 66]),
+    ([30, 28, 30, 30], [21, 43, 59, 70]),
+    ([30, 28, 30, 30], [22, 45, 62, 74]),
+    ([30, 28, 30, 30], [24, 47, 65, 77]),
+    ([30, 28, 30, 30], [25, 49, 68, 81]),
+];
+
+/// Index of `level` in the [`BLOCKS`] columns.
+fn ec_index(level: EcLevel) -> usize {
+    match level {
+        EcLevel::L => 0,
+        EcLevel::M => 1,
+        EcLevel::Q => 2,
+        EcLevel::H => 3,
+    }
+}
+
+/// Remainder of `value`, shifted past the generator's degree, divided by the
+/// BCH generator `poly`.
+fn bch(value: u32, poly: u32) -> u32 {
+    let degree = 31 - poly.leading_zeros();
+    let mut remainder = value << degree;
+    while remainder.leading_zeros() <= poly.leading_zeros() {
+        remainder ^= poly << (poly.leading_zeros() - remainder.leading_zeros());
+    }
+    remainder
+}
+
+/// The two copies of the format information in a `size`-module symbol,
+/// most significant bit first.
+pub(crate) fn format_modules(size: usize) -> [Vec<(usize, usize)>; 2] {
+    [
+        (0..6)
+            .map(|x| (x, 8))
+            .chain([(7, 8), (8, 8), (8, 7)])
+            .chain((0..6).rev().map(|y| (8, y)))
+            .collect(),
+        (size - 7..size)
+            .rev()
+            .map(|y| (8, y))
+            .chain((size - 8..size).map(|x| (x, 8)))
+            .collect(),
+    ]
+}
+
+/// The top-right and bottom-left copies of the version information in a
+/// `size`-module symbol, most significant bit first.
+pub(crate) fn version_modules(size: usize) -> [Vec<(usize, usize)>; 2] {
+    let far = size - 11;
+    [
+        (0..6)
+            .rev()
+            .flat_map(|y| (far..far + 3).rev().map(move |x| (x, y)))
+            .collect(),
+        (0..6)
+            .rev()
+            .flat_map(|x| (far..far + 3).rev().map(move |y| (x, y)))
+            .collect(),
+    ]
+}
+
+/// The error-correction level and mask from the format information, taking
+/// whichever copy is closest to a valid code word (at most 3 bits off).
+fn read_format(grid: &Grid) -> Option<(EcLevel, u8)> {
+    let [first, second] = format_modules(grid.size).map(|copy| grid.bits(copy.into_iter()));
+    let (info, distance) = (0..32u32)
+        .flat_map(|info| {
+            let code = ((info << 10) | bch(info, 0x537)) ^ 0x5412;
+            [first, second].map(|read| (info, (read ^ code).count_ones()))
+        })
+        .min_by_key(|&(_, distance)| distance)?;
+    if distance > 3 {
+        return None;
+    }
+    let level = match info >> 3 {
+        0 => EcLevel::M,
+        1 => EcLevel::L,
+        2 => EcLevel::H,
+        _ => EcLevel::Q,
+    };
+    Some((level, u8::try_from(info & 7).unwrap_or(0)))
+}
+
+/// The version from either copy of the version information, allowing 3
+/// wrong bits.
+fn read_version(grid: &Grid) -> Option<usize> {
+    let [top_right, bottom_left] =
+        version_modules(grid.size).map(|copy| grid.bits(copy.into_iter()));
+    let (version, distance) = (7..=40u32)
+        .flat_map(|version| {
+            let code = (version << 12) | bch(version, 0x1F25);
+            [top_right, bottom_left].map(|read| (version, (read ^ code).count_ones()))
+        })
+        .min_by_key(|&(_, distance)| distance)?;
+    (distance <= 3).then_some(version as usize)
+}
+
+/// Whether data mask `mask` inverts the module at column `x`, row `y`.
+fn masked(mask: u8, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y) % 2 == 0,
+        1 => y % 2 == 0,
+        2 => x % 3 == 0,
+        3 => (x + y) % 3 == 0,
+        4 => (y / 2 + x / 3) % 2 == 0,
+        5 => (x * y) % 2 + (x * y) % 3 == 0,
+        6 => ((x * y) % 2 + (x * y) % 3) % 2 == 0,
+        _ => ((x + y) % 2 + (x * y) % 3) % 2 == 0,
+    }
+}
+
+/// Centre coordinates of the alignment patterns for `version` along either
+/// axis.
+pub(crate) fn alignment_positions(version: usize) -> Vec<usize> {
+    if version < 2 {
+        return Vec::new();
+    }
+    let count = version / 7 + 2;
+    let size = 17 + 4 * version;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+/// The modules of a `version` symbol that carry finder, separator, timing,
+/// alignment, format or version information rather than data, row by row.
+pub(crate) fn function_modules(version: usize) -> Vec<bool> {
+    let size = 17 + 4 * version;
+    let mut function = vec![false; size * size];
+    let mut mark = |x0: usize, y0: usize, width: usize, height: usize| {
+        for y in y0..y0 + height {
+            for x in x0..x0 + width {
+                function[y * size + x] = true;
+            }
+        }
+    };
+    mark(0, 0, 9, 9);
+    mark(size - 8, 0, 8, 9);
+    mark(0, size - 8, 9, 8);
+    mark(6, 0, 1, size);
+    mark(0, 6, size, 1);
+    let positions = alignment_positions(version);
+    let last = positions.len().saturating_sub(1);
+    for (i, &x) in positions.iter().enumerate() {
+        for (j, &y) in positions.iter().enumerate() {
+            // The three corners under the finder patterns are skipped.
+            if (i == 0 && (j == 0 || j == last)) || (i == last && j == 0) {
+                continue;
+            }
+            mark(x - 2, y - 2, 5, 5);
+        }
+    }
+    if version >= 7 {
+        mark(size - 11, 0, 3, 6);
+        mark(0, size - 11, 6, 3);
+    }
+    function
+}
+
+/// Reads bits most significant first.
+#[derive(Debug)]
+struct BitReader<'a> {
+    /// The bytes being read.
+    bytes: &'a [u8],
+    /// Bits consumed so far.
+    position: usize,
+}
+
+impl BitReader<'_> {
+    /// Bits left to read.
+    fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+
+    /// The next `count` bits, or `None` past the end.
+    fn read(&mut self, count: usize) -> Option<u32> {
+        if count > self.remaining() {
+            return None;
+        }
+        let mut value = 0;
+        for _ in 0..count {
+            let bit = (self.bytes[self.position / 8] >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.position += 1;
+        }
+        Some(value)
+    }
+}
+
+/// Characters of the alphanumeric mode, by value.
+const ALPHANUMERIC: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// What the segments of a symbol carry besides its layout.
+struct Payload {
+    /// The bytes, concatenated across segments.
+    data: Vec<u8>,
+    /// The Structured Append header, if any.
+    structured_append: Option<Sequence>,
+    /// The last ECI designator, if any.
+    eci: Option<u32>,
+    /// Whether an FNC1 first-position indicator was read.
+    gs1: bool,
+}
+
+/// Parses the segments in the corrected `data` of a `version` symbol.
+fn segments(data: &[u8], version: usize) -> Option<Payload> {
+    let band = match version {
+        1..=9 => 0,
+        10..=26 => 1,
+        _ => 2,
+    };
+    let mut reader = BitReader {
+        bytes: data,
+        position: 0,
+    };
+    let mut out = Vec::new();
+    let mut sequence = None;
+    let mut eci = None;
+    let mut gs1 = false;
+    while reader.remaining() >= 4 {
+        match reader.read(4)? {
+            0 => break,
+            // Numeric: three digits per 10 bits.
+            1 => {
+                let mut count = reader.read([10, 12, 14][band])? as usize;
+                while count > 0 {
+                    let digits = count.min(3);
+                    let value = reader.read([4, 7, 10][digits - 1])?;
+                    if value >= 10u32.pow(digits as u32) {
+                        return None;
+                    }
+                    out.extend(format!("{value:0digits$}").bytes());
+                    count -= digits;
+                }
+            }
+            // Alphanumeric: two characters per 11 bits.
+            2 => {
+                let mut count = reader.read([9, 11, 13][band])? as usize;
+                let mut text = Vec::with_capacity(count);
+                while count > 0 {
+                    let pair = count >= 2;
+                    let value = reader.read(if pair { 11 } else { 6 })? as usize;
+                    let chars = if pair {
+                        vec![value / 45, value % 45]
+                    } else {
+                        vec![value]
+                    };
+                    for c in chars {
+                        text.push(*ALPHANUMERIC.get(c)?);
+                    }
+                    count -= if pair { 2 } else { 1 };
+                }
+                if gs1 {
+                    // Under FNC1, `%` is a group separator and `%%` a `%`.
+                    let mut chars = text.into_iter();
+                    while let Some(c) = chars.next() {
+                        match c {
+                            b'%' if chars.as_slice().first() == Some(&b'%') => {
+                                chars.next();
+                                out.push(b'%');
+                            }
+                            b'%' => out.push(0x1D),
+                            _ => out.push(c),
+                        }
+                    }
+                } else {
+                    out.extend(text);
+                }
+            }
+            4 => {
+                let count = reader.read([8, 16, 16][band])?;
+                for _ in 0..count {
+                    out.push(u8::try_from(reader.read(8)?).ok()?);
+                }
+            }
+            // Kanji: 13 bits per double-byte Shift JIS character.
+            8 => {
+                let count = reader.read([8, 10, 12][band])?;
+                for _ in 0..count {
+                    let value = reader.read(13)?;
+                    let packed = ((value / 0xC0) << 8) | (value % 0xC0);
+                    let code = packed + if packed < 0x1F00 { 0x8140 } else { 0xC140 };
+                    out.extend_from_slice(&u16::try_from(code).ok()?.to_be_bytes());
+                }
+            }
+            // ECI designator: 1, 2 or 3 bytes, flagged by the leading bits.
+            7 => {
+                let first = reader.read(8)?;
+                let (extra, high) = match first {
+                    0x00..=0x7F => (0, first),
+                    0x80..=0xBF => (8, first & 0x3F),
+                    0xC0..=0xDF => (16, first & 0x1F),
+                    _ => return None,
+                };
+                eci = Some(high << extra | reader.read(extra)?);
+            }
+            // Structured append: position, total and parity.
+            3 => {
+                sequence = Some(Sequence::from_bits(reader.read(16)?));
+            }
+            // FNC1 in first position marks GS1 data; in second position it
+            // carries an application indicator.
+            5 => gs1 = true,
+            9 => {
+                reader.read(8)?;
+            }
+            _ => return None,
+        }
+    }
+    Some(Payload {
+        data: out,
+        structured_append: sequence,
+        eci,
+        gs1,
+    })
+}
@@ -0,0 +1,158 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Reed-Solomon error correction over GF(256).
+//!
+//! QR codes use the field generated by x⁸ + x⁴ + x³ + x² + 1 and generator
+//! polynomials with roots α⁰ … α^(n−1). Errors are located with
+//! Berlekamp-Massey and a Chien search, and their values found with Forney's
+//! algorithm.
+
+/// The field's primitive polynomial, without the x⁸ term's carry.
+const PRIMITIVE: u16 = 0x11D;
+
+/// Powers of α, doubled so products of two logarithms need no reduction.
+const EXP: [u8; 512] = {
+    let mut table = [0u8; 512];
+    let mut value: u16 = 1;
+    let mut i = 0;
+    while i < 512 {
+        table[i] = value as u8;
+        value <<= 1;
+        if value & 0x100 != 0 {
+            value ^= PRIMITIVE;
+        }
+        i += 1;
+    }
+    table
+};
+
+/// Discrete logarithms base α; `LOG[0]` is unused.
+const LOG: [u8; 256] = {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 255 {
+        table[EXP[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Product of `a` and `b` in GF(256).
+fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        EXP[usize::from(LOG[usize::from(a)]) + usize::from(LOG[usize::from(b)])]
+    }
+}
+
+/// Quotient of `a` by the non-zero `b` in GF(256).
+fn div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        0
+    } else {
+        EXP[usize::from(LOG[usize::from(a)]) + 255 - usize::from(LOG[usize::from(b)])]
+    }
+}
+
+/// α raised to `power`.
+fn alpha(power: usize) -> u8 {
+    EXP[power % 255]
+}
+
+/// Value at `x` of the polynomial with `coefficients` in ascending order.
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients.iter().rev().fold(0, |acc, &c| mul(acc, x) ^ c)
+}
+
+/// Corrects `block` in place, whose last `ec` codewords are error
+/// correction, and returns how many codewords were wrong, or `None` when the
+/// errors exceed what the block can repair.
+pub(crate) fn correct(block: &mut [u8], ec: usize) -> Option<usize> {
+    let n = block.len();
+    if ec == 0 || ec >= n {
+        return None;
+    }
+    // Syndromes S_j = r(α^j), with the first codeword the highest power.
+    let syndromes: Vec<u8> = (0..ec)
+        .map(|j| block.iter().fold(0, |acc, &c| mul(acc, alpha(j)) ^ c))
+        .collect();
+    if syndromes.iter().all(|&s| s == 0) {
+        return Some(0);
+    }
+
+    // Berlekamp-Massey: the shortest error locator Λ(x) generating the
+    // syndromes.
+    let mut locator = vec![1u8];
+    let mut previous = vec![1u8];
+    let mut errors = 0;
+    let mut shift = 1;
+    let mut last_discrepancy = 1u8;
+    for step in 0..ec {
+        let mut discrepancy = syndromes[step];
+        for i in 1..=errors.min(locator.len() - 1) {
+            discrepancy ^= mul(locator[i], syndromes[step - i]);
+        }
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let scale = div(discrepancy, last_discrepancy);
+        let before = locator.clone();
+        if locator.len() < previous.len() + shift {
+            locator.resize(previous.len() + shift, 0);
+        }
+        for (i, &c) in previous.iter().enumerate() {
+            locator[i + shift] ^= mul(scale, c);
+        }
+        if 2 * errors <= step {
+            errors = step + 1 - errors;
+            previous = before;
+            last_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    locator.truncate(errors + 1);
+    if 2 * errors > ec {
+        return None;
+    }
+
+    // Chien search: an error at power p (counting from the last codeword)
+    // makes α^−p a root of Λ.
+    let positions: Vec<usize> = (0..n)
+        .filter(|&p| evaluate(&locator, alpha(255 - p % 255)) == 0)
+        .collect();
+    if positions.len() != errors {
+        return None;
+    }
+
+    // Forney: Ω(x) = S(x)·Λ(x) mod x^ec, e = X·Ω(X⁻¹) / Λ'(X⁻¹).
+    let mut evaluator = vec![0u8; ec];
+    for (i, &s) in syndromes.iter().enumerate() {
+        for (j, &l) in locator.iter().enumerate() {
+            if i + j < ec {
+                evaluator[i + j] ^= mul(s, l);
+            }
+        }
+    }
+    let derivative: Vec<u8> = locator
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| if i % 2 == 1 { c } else { 0 })
+        .collect();
+    for &p in &positions {
+        let x = alpha(p);
+        let x_inverse = alpha(255 - p % 255);
+        let denominator = evaluate(&derivative, x_inverse);
+        if denominator == 0 {
+            return None;
+        }
+        let value = mul(x, div(evaluate(&evaluator, x_inverse), denominator));
+        block[n - 1 - p] ^= value;
+    }
+    Some(errors)
+}
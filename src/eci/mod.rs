@@ -0,0 +1,317 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Extended Channel Interpretation: character sets other than the default.
+//!
+//! A QR code's byte segments carry no character set of their own; readers
+//! assume ISO-8859-1 or guess. An ECI designator ahead of the data names
+//! the set, and the text is transcoded into it, so strict scanners show
+//! accented, Cyrillic, Greek or Japanese text as written.
+
+mod tables;
+
+use crate::QrcError;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tables::{ISO_8859, JIS_ROWS, JIS_X_0208};
+
+/// Cells in a row of JIS X 0208.
+const JIS_CELLS: usize = 94;
+
+/// A character set that can be announced with an ECI designator.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::CharacterSet;
+///
+/// let greek = CharacterSet::from_label("greek").unwrap();
+/// assert_eq!(greek, CharacterSet::Iso8859_7);
+/// assert_eq!(greek.eci(), 9);
+/// assert_eq!(greek.encode("Γειά").unwrap(), [0xC3, 0xE5, 0xE9, 0xDC]);
+/// assert!(greek.encode("Straße").is_err());
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CharacterSet {
+    /// ISO-8859-1, Latin-1 Western European (ECI 3).
+    Iso8859_1,
+    /// ISO-8859-2, Latin-2 Central European (ECI 4).
+    Iso8859_2,
+    /// ISO-8859-3, Latin-3 South European (ECI 5).
+    Iso8859_3,
+    /// ISO-8859-4, Latin-4 North European (ECI 6).
+    Iso8859_4,
+    /// ISO-8859-5, Latin/Cyrillic (ECI 7).
+    Iso8859_5,
+    /// ISO-8859-6, Latin/Arabic (ECI 8).
+    Iso8859_6,
+    /// ISO-8859-7, Latin/Greek (ECI 9).
+    Iso8859_7,
+    /// ISO-8859-8, Latin/Hebrew (ECI 10).
+    Iso8859_8,
+    /// ISO-8859-9, Latin-5 Turkish (ECI 11).
+    Iso8859_9,
+    /// ISO-8859-10, Latin-6 Nordic (ECI 12).
+    Iso8859_10,
+    /// ISO-8859-11, Latin/Thai (ECI 13).
+    Iso8859_11,
+    /// ISO-8859-13, Latin-7 Baltic Rim (ECI 15).
+    Iso8859_13,
+    /// ISO-8859-14, Latin-8 Celtic (ECI 16).
+    Iso8859_14,
+    /// ISO-8859-15, Latin-9 (ECI 17).
+    Iso8859_15,
+    /// ISO-8859-16, Latin-10 South-Eastern European (ECI 18).
+    Iso8859_16,
+    /// Shift JIS: JIS X 0208 with ASCII and half-width katakana (ECI 20).
+    ShiftJis,
+    /// UTF-16, big-endian, without a byte order mark (ECI 25).
+    Utf16Be,
+    /// UTF-8 (ECI 26).
+    Utf8,
+}
+
+/// Every character set with its part of ISO/IEC 8859, if any, its ECI
+/// designator and its IANA name.
+const SETS: [(CharacterSet, u8, u32, &str); 18] = [
+    (CharacterSet::Iso8859_1, 1, 3, "ISO-8859-1"),
+    (CharacterSet::Iso8859_2, 2, 4, "ISO-8859-2"),
+    (CharacterSet::Iso8859_3, 3, 5, "ISO-8859-3"),
+    (CharacterSet::Iso8859_4, 4, 6, "ISO-8859-4"),
+    (CharacterSet::Iso8859_5, 5, 7, "ISO-8859-5"),
+    (CharacterSet::Iso8859_6, 6, 8, "ISO-8859-6"),
+    (CharacterSet::Iso8859_7, 7, 9, "ISO-8859-7"),
+    (CharacterSet::Iso8859_8, 8, 10, "ISO-8859-8"),
+    (CharacterSet::Iso8859_9, 9, 11, "ISO-8859-9"),
+    (CharacterSet::Iso8859_10, 10, 12, "ISO-8859-10"),
+    (CharacterSet::Iso8859_11, 11, 13, "ISO-8859-11"),
+    (CharacterSet::Iso8859_13, 13, 15, "ISO-8859-13"),
+    (CharacterSet::Iso8859_14, 14, 16, "ISO-8859-14"),
+    (CharacterSet::Iso8859_15, 15, 17, "ISO-8859-15"),
+    (CharacterSet::Iso8859_16, 16, 18, "ISO-8859-16"),
+    (CharacterSet::ShiftJis, 0, 20, "Shift_JIS"),
+    (CharacterSet::Utf16Be, 0, 25, "UTF-16BE"),
+    (CharacterSet::Utf8, 0, 26, "UTF-8"),
+];
+
+/// Labels accepted besides the `iso-8859-N` family, after IANA's aliases.
+const ALIASES: [(&str, CharacterSet); 22] = [
+    ("latin1", CharacterSet::Iso8859_1),
+    ("l1", CharacterSet::Iso8859_1),
+    ("latin2", CharacterSet::Iso8859_2),
+    ("l2", CharacterSet::Iso8859_2),
+    ("latin3", CharacterSet::Iso8859_3),
+    ("l3", CharacterSet::Iso8859_3),
+    ("latin4", CharacterSet::Iso8859_4),
+    ("l4", CharacterSet::Iso8859_4),
+    ("cyrillic", CharacterSet::Iso8859_5),
+    ("arabic", CharacterSet::Iso8859_6),
+    ("greek", CharacterSet::Iso8859_7),
+    ("hebrew", CharacterSet::Iso8859_8),
+    ("latin5", CharacterSet::Iso8859_9),
+    ("latin6", CharacterSet::Iso8859_10),
+    ("latin7", CharacterSet::Iso8859_13),
+    ("latin8", CharacterSet::Iso8859_14),
+    ("latin10", CharacterSet::Iso8859_16),
+    ("shift_jis", CharacterSet::ShiftJis),
+    ("sjis", CharacterSet::ShiftJis),
+    ("utf-16be", CharacterSet::Utf16Be),
+    ("utf-16", CharacterSet::Utf16Be),
+    ("utf-8", CharacterSet::Utf8),
+];
+
+impl CharacterSet {
+    /// This set's row of [`SETS`], which lists them in declaration order.
+    fn row(self) -> (CharacterSet, u8, u32, &'static str) {
+        SETS[self as usize]
+    }
+
+    /// The ECI designator announcing this set.
+    #[must_use]
+    pub fn eci(self) -> u32 {
+        self.row().2
+    }
+
+    /// The set an ECI designator announces, if it is one of these.
+    #[must_use]
+    pub fn from_eci(eci: u32) -> Option<Self> {
+        SETS.into_iter().find(|row| row.2 == eci).map(|row| row.0)
+    }
+
+    /// The IANA name of the set, such as `ISO-8859-1` or `Shift_JIS`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        self.row().3
+    }
+
+    /// The set named by `label`, ignoring case: an IANA name such as
+    /// `ISO-8859-5`, `iso_8859-5` or `iso8859-5`, or an alias such as
+    /// `latin2`, `cyrillic`, `sjis` or `utf-8`.
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim().to_ascii_lowercase();
+        let part = ["iso-8859-", "iso_8859-", "iso8859-"]
+            .iter()
+            .find_map(|prefix| label.strip_prefix(prefix))
+            .and_then(|part| part.parse::<u8>().ok());
+        if let Some(part) = part {
+            return SETS
+                .into_iter()
+                .find(|row| row.1 == part && part != 0)
+                .map(|row| row.0);
+        }
+        ALIASES
+            .into_iter()
+            .find(|alias| alias.0 == label)
+            .map(|alias| alias.1)
+    }
+
+    /// Transcodes `text` into this set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidOption`] naming the first character the
+    /// set cannot represent.
+    pub fn encode(self, text: &str) -> Result<Vec<u8>, QrcError> {
+        let unmappable = |c: char| {
+            QrcError::InvalidOption(format!("'{c}' cannot be encoded in {}", self.name()))
+        };
+        match self {
+            CharacterSet::Utf8 => Ok(text.as_bytes().to_vec()),
+            CharacterSet::Utf16Be => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            CharacterSet::ShiftJis => {
+                let mut out = Vec::with_capacity(text.len());
+                for c in text.chars() {
+                    out.extend_from_slice(&shift_jis(c).ok_or_else(|| unmappable(c))?);
+                }
+                Ok(out)
+            }
+            _ => {
+                let upper = upper_half(self.row().1);
+                text.chars()
+                    .map(|c| match u8::try_from(u32::from(c)) {
+                        Ok(byte) if byte < 0xA0 => Ok(byte),
+                        _ => match upper {
+                            None => u8::try_from(u32::from(c)).map_err(|_| unmappable(c)),
+                            Some(upper) => upper
+                                .chars()
+                                .position(|u| u == c && u != '\0')
+                                .and_then(|i| u8::try_from(0xA0 + i).ok())
+                                .ok_or_else(|| unmappable(c)),
+                        },
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Reads `bytes` as text in this set, or `None` if they are not valid.
+    #[must_use]
+    pub fn decode(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            CharacterSet::Utf8 => String::from_utf8(bytes.to_vec()).ok(),
+            CharacterSet::Utf16Be => {
+                if bytes.len() % 2 != 0 {
+                    return None;
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16(&units).ok()
+            }
+            CharacterSet::ShiftJis => {
+                let mut out = String::with_capacity(bytes.len());
+                let mut iter = bytes.iter().copied();
+                while let Some(byte) = iter.next() {
+                    let c = match byte {
+                        0x00..=0x7F => char::from(byte),
+                        0xA1..=0xDF => char::from_u32(0xFF61 + u32::from(byte - 0xA1))?,
+                        _ => jis_char(byte, iter.next()?)?,
+                    };
+                    out.push(c);
+                }
+                Some(out)
+            }
+            _ => {
+                let upper = upper_half(self.row().1);
+                bytes
+                    .iter()
+                    .map(|&byte| match upper {
+                        Some(upper) if byte >= 0xA0 => upper
+                            .chars()
+                            .nth(usize::from(byte - 0xA0))
+                            .filter(|&c| c != '\0'),
+                        _ => Some(char::from(byte)),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The table for bytes `0xA0..=0xFF` of ISO/IEC 8859 part `part`; `None`
+/// for part 1, whose bytes are their own code points.
+fn upper_half(part: u8) -> Option<&'static str> {
+    ISO_8859
+        .iter()
+        .find(|table| table.0 == part)
+        .map(|table| table.1)
+}
+
+/// JIS X 0208 as characters, by row and cell from 0.
+fn jis_table() -> &'static [char] {
+    static TABLE: OnceLock<Vec<char>> = OnceLock::new();
+    TABLE.get_or_init(|| JIS_X_0208.chars().collect())
+}
+
+/// The Shift JIS bytes of `c`, or `None` if it has none.
+#[allow(clippy::cast_possible_truncation)]
+fn shift_jis(c: char) -> Option<Vec<u8>> {
+    static CODES: OnceLock<HashMap<char, usize>> = OnceLock::new();
+    match u32::from(c) {
+        code @ 0x00..=0x7F => return Some(vec![code as u8]),
+        code @ 0xFF61..=0xFF9F => return Some(vec![(code - 0xFF61 + 0xA1) as u8]),
+        _ => {}
+    }
+    let codes = CODES.get_or_init(|| {
+        (0..)
+            .zip(jis_table())
+            .filter(|&(_, &c)| c != '\0')
+            .map(|(i, &c)| (c, i))
+            .collect()
+    });
+    let index = *codes.get(&c)?;
+    let (row, cell) = (index / JIS_CELLS + 1, index % JIS_CELLS + 1);
+    let lead = row.div_ceil(2) + if row <= 62 { 0x80 } else { 0xC0 };
+    let trail = if row % 2 == 0 {
+        cell + 0x9E
+    } else {
+        cell + 0x3F + usize::from(cell >= 64)
+    };
+    Some(vec![lead as u8, trail as u8])
+}
+
+/// The character of the double-byte Shift JIS code `lead`, `trail`.
+fn jis_char(lead: u8, trail: u8) -> Option<char> {
+    let base = match lead {
+        0x81..=0x9F => lead - 0x80,
+        0xE0..=0xEF => lead - 0xC0,
+        _ => return None,
+    };
+    let (row, cell) = match trail {
+        0x40..=0x7E => (base * 2 - 1, trail - 0x3F),
+        0x80..=0x9E => (base * 2 - 1, trail - 0x40),
+        0x9F..=0xFC => (base * 2, trail - 0x9E),
+        _ => return None,
+    };
+    let (row, cell) = (usize::from(row), usize::from(cell));
+    if row > JIS_ROWS {
+        return None;
+    }
+    jis_table()
+        .get((row - 1) * JIS_CELLS + cell - 1)
+        .copied()
+        .filter(|&c| c != '\0')
+}
@@ -0,0 +1,89 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Encapsulated PostScript output for print workflows.
+//!
+//! Like the PDF writer, layers are drawn in module units under a transform
+//! that flips the y axis, so every [`ModuleShape`](crate::ModuleShape) and
+//! eye style comes out as the same vector outline. Colours are written in
+//! CMYK; gradients use LanguageLevel 3 `shfill`.
+
+use crate::pdf::{shading, ColorModel};
+use crate::vector::{number, Fill, Layer};
+use image::Rgba;
+use std::fmt::Write as _;
+
+/// Decimals written for coordinates and colour components.
+const PRECISION: u8 = 4;
+
+/// An EPS document `side` points wide showing `layers`, drawn in a grid of
+/// `modules` columns and rows, over an optional `background`. The bounding
+/// box is as high as the grid's proportions ask.
+pub(crate) fn document(
+    layers: &[Layer],
+    background: Option<Rgba<u8>>,
+    modules: (f64, f64),
+    side: f64,
+) -> String {
+    let num = |value: f64| number(value, PRECISION);
+    let shaded = layers
+        .iter()
+        .any(|layer| matches!(layer.fill, Fill::Gradient { .. }));
+    let mut eps = String::from("%!PS-Adobe-3.0 EPSF-3.0\n");
+    let _ = writeln!(eps, "%%Creator: qrc {}", env!("CARGO_PKG_VERSION"));
+    let scale = side / modules.0;
+    let height = scale * modules.1;
+    let _ = writeln!(eps, "%%BoundingBox: 0 0 {} {}", side.ceil(), height.ceil());
+    let _ = writeln!(eps, "%%HiResBoundingBox: 0 0 {} {}", num(side), num(height));
+    let _ = writeln!(eps, "%%LanguageLevel: {}", if shaded { 3 } else { 2 });
+    eps.push_str("%%DocumentProcessColors: Cyan Magenta Yellow Black\n");
+    eps.push_str("%%Pages: 1\n%%EndComments\n");
+    eps.push_str("10 dict begin\n");
+    eps.push_str("/m { moveto } bind def /l { lineto } bind def\n");
+    eps.push_str("/c { curveto } bind def /h { closepath } bind def\n");
+    eps.push_str("%%EndProlog\n%%Page: 1 1\ngsave\n");
+    let _ = writeln!(
+        eps,
+        "[{} 0 0 {} 0 {}] concat",
+        num(scale),
+        num(-scale),
+        num(height)
+    );
+    if let Some(color) = background {
+        let (w, h) = (num(modules.0), num(modules.1));
+        let _ = writeln!(eps, "{}0 0 {w} {h} rectfill", cmyk(color));
+    }
+    for layer in layers {
+        let path = layer.path.to_postfix(["m", "l", "c", "h"], PRECISION);
+        let eo = if layer.even_odd { "eo" } else { "" };
+        match &layer.fill {
+            Fill::Solid(color) => {
+                let _ = writeln!(eps, "{}newpath\n{path}{eo}fill", cmyk(*color));
+            }
+            Fill::Gradient {
+                gradient,
+                x,
+                y,
+                size,
+                ..
+            } => {
+                let dict = shading(gradient, *x, *y, *size, ColorModel::Cmyk);
+                let concat = match gradient.geometry(*x, *y, *size).transform() {
+                    Some(m) => format!("[{}] concat\n", m.map(num).join(" ")),
+                    None => String::new(),
+                };
+                let _ = writeln!(
+                    eps,
+                    "gsave\nnewpath\n{path}{eo}clip\n{concat}{dict} shfill\ngrestore"
+                );
+            }
+        }
+    }
+    eps.push_str("grestore\nend\nshowpage\n%%Trailer\n%%EOF\n");
+    eps
+}
+
+/// `c m y k setcmykcolor` operator for `color`.
+fn cmyk(color: Rgba<u8>) -> String {
+    format!("{} setcmykcolor\n", ColorModel::Cmyk.components(color))
+}
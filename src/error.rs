@@ -0,0 +1,84 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The crate-wide error type.
+//!
+//! Every fallible entry point (`try_*` renderers, the byte encoders,
+//! [`QRCode::combine_qr_codes`](crate::QRCode::combine_qr_codes), …) reports
+//! failures as a [`QrcError`], so a caller can surface bad user input instead
+//! of crashing on it.
+
+use qrcode::bits::Bits;
+use qrcode::optimize::{total_encoded_len, Parser, Segment};
+use qrcode::types::{EcLevel, QrError, Version};
+use std::fmt;
+
+/// Errors produced while encoding, rendering or validating a QR code.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum QrcError {
+    /// The data does not fit in the largest symbol at the requested
+    /// error-correction level.
+    CapacityExceeded {
+        /// Bits required to encode the data.
+        needed: usize,
+        /// Bits available in the largest permitted symbol.
+        available: usize,
+    },
+    /// An option or argument is out of range or inconsistent with the others.
+    InvalidOption(String),
+    /// The rendered image could not be encoded to the requested format.
+    ImageEncode(image::ImageError),
+    /// A structured payload failed validation.
+    InvalidPayload(String),
+    /// An image could not be read back as a QR code.
+    Decode(String),
+}
+
+impl QrcError {
+    /// Converts a `qrcode` encoder error for `data` at `ec_level`, filling in
+    /// the needed/available bit counts when the data is simply too long.
+    pub(crate) fn from_qr(err: QrError, data: &[u8], ec_level: EcLevel) -> Self {
+        match err {
+            QrError::DataTooLong => {
+                let version = Version::Normal(40);
+                let segments: Vec<Segment> = Parser::new(data).optimize(version).collect();
+                QrcError::CapacityExceeded {
+                    needed: total_encoded_len(&segments, version),
+                    available: Bits::new(version).max_len(ec_level).unwrap_or_default(),
+                }
+            }
+            other => QrcError::InvalidOption(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for QrcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrcError::CapacityExceeded { needed, available } => write!(
+                f,
+                "data too long: needs {needed} bits but only {available} are available"
+            ),
+            QrcError::InvalidOption(msg) => write!(f, "invalid option: {msg}"),
+            QrcError::ImageEncode(err) => write!(f, "image encoding failed: {err}"),
+            QrcError::InvalidPayload(msg) => write!(f, "invalid payload: {msg}"),
+            QrcError::Decode(msg) => write!(f, "decoding failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for QrcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            QrcError::ImageEncode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<image::ImageError> for QrcError {
+    fn from(err: image::ImageError) -> Self {
+        QrcError::ImageEncode(err)
+    }
+}
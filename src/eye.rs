@@ -0,0 +1,150 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Finder-pattern ("eye") styling.
+//!
+//! Each finder pattern is a 7×7 dark frame around a light ring and a 3×3 dark
+//! centre (the "ball"). An [`EyeStyle`] draws the frame and the ball as single
+//! shapes instead of seven-by-seven grids of modules, independently of the
+//! [`ModuleShape`](crate::ModuleShape) used for data. Their colours come from
+//! [`QrStyle::eye_frame`](crate::QrStyle::eye_frame) and
+//! [`QrStyle::eye_ball`](crate::QrStyle::eye_ball).
+
+use crate::shape::rounded_rect_contains;
+use crate::vector::Path;
+
+/// Side length of a finder pattern, in modules.
+const FRAME: f64 = 7.0;
+/// Side length of the light ring's inner edge, in modules.
+const HOLE: f64 = 5.0;
+/// Side length of the centre ball, in modules.
+const BALL: f64 = 3.0;
+
+/// Outline of a finder frame or ball.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EyeShape {
+    /// Sharp corners, as in the specification (default).
+    #[default]
+    Square,
+    /// Corners rounded by a quarter of the side.
+    RoundedSquare,
+    /// A circle (a ring for frames).
+    Circle,
+    /// Rounded on the outer corner and on the corner facing the symbol's
+    /// centre, sharp on the other two.
+    Leaf,
+}
+
+/// Shapes for the finder frames and balls.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{EyeShape, EyeStyle, QRCode};
+///
+/// let qr = QRCode::from_string("Hello".to_string())
+///     .with_eye_style(EyeStyle::new(EyeShape::RoundedSquare, EyeShape::Circle));
+/// assert!(qr.to_svg(256).contains("fill-rule=\"evenodd\""));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EyeStyle {
+    /// Shape of the 7×7 outer frame.
+    pub frame: EyeShape,
+    /// Shape of the 3×3 centre.
+    pub ball: EyeShape,
+}
+
+impl EyeStyle {
+    /// Creates an eye style with the given frame and ball shapes.
+    #[must_use]
+    pub fn new(frame: EyeShape, ball: EyeShape) -> Self {
+        EyeStyle { frame, ball }
+    }
+}
+
+/// Which part of a finder pattern a point falls in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EyePart {
+    /// The outer frame.
+    Frame,
+    /// The centre ball.
+    Ball,
+}
+
+/// Which part of a finder pattern the 7×7 grid cell (`x`, `y`) belongs to, if
+/// any: the outer ring of cells is the frame, the central 3×3 the ball.
+pub(crate) fn cell_part(x: usize, y: usize) -> Option<EyePart> {
+    if x == 0 || y == 0 || x == 6 || y == 6 {
+        Some(EyePart::Frame)
+    } else if (2..5).contains(&x) && (2..5).contains(&y) {
+        Some(EyePart::Ball)
+    } else {
+        None
+    }
+}
+
+/// Which shaped part covers point (`x`, `y`), in modules from the top-left of
+/// a finder pattern whose corner sits at `corner` (`(left, top)`).
+pub(crate) fn shape_part(style: EyeStyle, corner: (bool, bool), x: f64, y: f64) -> Option<EyePart> {
+    let frame = radii(style.frame, FRAME, corner);
+    let hole = radii(style.frame, HOLE, corner);
+    let ball = radii(style.ball, BALL, corner);
+    let inset = (FRAME - HOLE) / 2.0;
+    let ball_inset = (FRAME - BALL) / 2.0;
+    if rounded_rect_contains(x - ball_inset, y - ball_inset, BALL, BALL, ball) {
+        Some(EyePart::Ball)
+    } else if rounded_rect_contains(x, y, FRAME, FRAME, frame)
+        && !rounded_rect_contains(x - inset, y - inset, HOLE, HOLE, hole)
+    {
+        Some(EyePart::Frame)
+    } else {
+        None
+    }
+}
+
+/// Outline of `part` of a finder pattern whose top-left corner is at
+/// (`x`, `y`), `module` units per module. Frames are two subpaths meant for an
+/// even-odd fill.
+pub(crate) fn outline(
+    style: EyeStyle,
+    part: EyePart,
+    corner: (bool, bool),
+    x: f64,
+    y: f64,
+    module: f64,
+) -> Path {
+    let mut path = Path::default();
+    let mut square = |side: f64, shape: EyeShape| {
+        let inset = (FRAME - side) / 2.0 * module;
+        let r = radii(shape, side, corner).map(|radius| radius * module);
+        let px = side * module;
+        path.rounded_rect(x + inset, y + inset, px, px, r);
+    };
+    match part {
+        EyePart::Frame => {
+            square(FRAME, style.frame);
+            square(HOLE, style.frame);
+        }
+        EyePart::Ball => square(BALL, style.ball),
+    }
+    path
+}
+
+/// Corner radii (top-left, top-right, bottom-right, bottom-left), in modules,
+/// for `shape` drawn in a square of side `side`.
+fn radii(shape: EyeShape, side: f64, (left, top): (bool, bool)) -> [f64; 4] {
+    match shape {
+        EyeShape::Square => [0.0; 4],
+        EyeShape::RoundedSquare => [side / 4.0; 4],
+        EyeShape::Circle => [side / 2.0; 4],
+        EyeShape::Leaf => {
+            let r = side * 0.45;
+            // Round the outer corner and the one diagonally opposite it.
+            if left == top {
+                [r, 0.0, r, 0.0]
+            } else {
+                [0.0, r, 0.0, r]
+            }
+        }
+    }
+}
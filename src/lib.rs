@@ -50,29 +50,29 @@
 //! | Feature | Description |
 //! | ------- | ----------- |
 //! | Library license | Apache-2.0 OR MIT |
-//! | Library version | 0.0.6 |
-//! | Mode Numeric | not specified |
-//! | Mode Alphanumeric | not specified |
-//! | Mode Byte | not specified |
-//! | Mode Kanji | not specified |
-//! | Mode ECI | not specified |
-//! | Mode FNC1 | not specified |
-//! | Mode Structured Append | not specified |
+//! | Library version | 0.0.7 |
+//! | Mode Numeric | supported |
+//! | Mode Alphanumeric | supported |
+//! | Mode Byte | supported |
+//! | Mode Kanji | supported |
+//! | Mode ECI | supported |
+//! | Mode FNC1 | supported (first position, GS1) |
+//! | Mode Structured Append | supported |
 //! | Mode Hanzi | not specified |
-//! | Mixing modes | not specified |
-//! | QR Codes version 1 - 40 | not specified |
-//! | Micro QR Codes version M1 - M4 | not specified |
+//! | Mixing modes | supported |
+//! | QR Codes version 1 - 40 | supported |
+//! | Micro QR Codes version M1 - M4 | supported |
 //! | Find maximal error correction level | not specified |
-//! | Optimize QR Codes | not specified |
+//! | Optimize QR Codes | supported |
 //! | PNG output | supported |
 //! | JPG output | supported |
 //! | GIF output | supported |
 //! | SVG output | supported |
-//! | EPS output | not specified |
-//! | PDF output | not specified |
-//! | BMP output | not specified |
-//! | TIFF output | not specified |
-//! | WebP output | not specified |
+//! | EPS output | supported |
+//! | PDF output | supported |
+//! | BMP output | supported |
+//! | TIFF output | supported (`tiff` feature) |
+//! | WebP output | supported (`webp` feature) |
 //! | Black and white QR Codes | Yes |
 //! | Colorized QR code | Yes |
 //! | Animated QR Codes (GIF, APNG, WebP) | not specified |
@@ -95,27 +95,113 @@
 )]
 #![crate_name = "qrc"]
 
-use image::{DynamicImage, ImageBuffer, ImageFormat, Rgba, RgbaImage};
+use image::{imageops, DynamicImage, ImageBuffer, ImageFormat, Rgba, RgbaImage};
 use miniz_oxide::deflate::compress_to_vec_zlib;
-use qrcode::{render::svg, Color, QrCode};
 use std::collections::HashMap;
 use std::fmt::Write as _;
 use std::io::Cursor;
 
 pub use qrcode::types::EcLevel;
 pub use qrcode::types::QrError;
+pub use qrcode::types::{Color, Version};
+
+/// The crate-wide error type returned by every fallible operation.
+mod error;
+pub use error::QrcError;
+
+/// Panic message shared by the infallible wrappers around the `try_*` API.
+const ENCODE_FAILED: &str = "Failed to encode QR code";
+
+/// Default quiet-zone width, in modules (the QR specification mandates 4).
+pub const DEFAULT_QUIET_ZONE: u32 = 4;
+
+/// Widest quiet zone, in modules, that the renderers accept.
+pub const MAX_QUIET_ZONE: u32 = 64;
+
+/// Largest RGBA image, in bytes, that the raster renderers allocate: 1 GiB,
+/// or 16 384 × 16 384 pixels. Larger requests fail with
+/// [`QrcError::InvalidOption`] instead of exhausting memory.
+pub const MAX_RASTER_BYTES: u64 = 1 << 30;
 
 /// The `macros` module contains functions for generating macros.
 pub mod macros;
 
-/// Structured payload builders (vCard, Wi-Fi, MeCard, EMVCo) that turn typed
-/// data into the text conventions QR scanners recognise.
+/// Structured payload builders (vCard, Wi-Fi, MeCard, EMVCo, EPC, GS1, Swiss
+/// QR-bill) that turn typed data into the text conventions QR scanners
+/// recognise.
 pub mod payload;
 
+/// Reading QR codes back from rendered images, to check they still scan.
+pub mod decode;
+
+/// Encoded symbols with explicit type, version, mode and mask choices.
+mod symbol;
+pub use symbol::{SegmentMode, Symbol, SymbolType, SymbolVersion};
+
+/// Splitting data into the mix of segment modes that encodes it shortest.
+mod segment;
+pub use segment::SegmentSpan;
+
+/// Rectangular Micro QR (rMQR) encoding.
+mod rmqr;
+
+/// Messages split across linked symbols with Structured Append.
+mod structured;
+pub use structured::{Sequence, StructuredAppend, MAX_SYMBOLS};
+
+/// Character sets announced with ECI designators, and their transcoders.
+mod eci;
+pub use eci::CharacterSet;
+
+/// Grading rendered codes module by module against their correction budget.
+mod scannability;
+pub use scannability::{ModuleStatus, ScanReport};
+
+/// Logos sized and placed within the error-correction budget.
+mod logo;
+pub use logo::LogoOptions;
+
 /// Offline "art QR" primitives: ControlNet control images and image blending.
 mod art;
 pub use art::BlendOptions;
 
+/// Neighbour-aware module geometry shared by the raster and SVG renderers.
+mod shape;
+use shape::Neighbours;
+
+/// Finder-pattern ("eye") shapes drawn independently of the data modules.
+mod eye;
+use eye::EyePart;
+pub use eye::{EyeShape, EyeStyle};
+
+/// Foreground/background colours and the contrast check shared by every
+/// renderer.
+mod style;
+pub use style::{Gradient, GradientKind, GradientStop, QrStyle, MIN_CONTRAST_RATIO};
+
+/// Resolution-independent outlines traced once and written by the vector
+/// renderers.
+mod vector;
+use vector::{Fill, Layer, Path};
+
+/// Single-page PDF output built from the vector outlines.
+mod pdf;
+
+/// Encapsulated PostScript output built from the vector outlines.
+mod eps;
+
+/// Compact PNG encoding with resolution and text metadata.
+mod png;
+pub use png::PngOptions;
+
+/// Text renderings for terminals.
+mod terminal;
+pub use terminal::{TerminalCharset, TerminalOptions};
+
+/// Physical lengths for print output.
+mod units;
+pub use units::{Length, PrintLayout, PrintSize, PrintTarget};
+
 #[cfg(feature = "wasm")]
 /// WASM bindings for the QRC library.
 pub mod wasm;
@@ -132,8 +218,192 @@ pub enum ModuleShape {
     Circle,
     /// Diamond-shaped modules.
     Diamond,
+    /// Modules that flow into their neighbours: corners are rounded only where
+    /// the outline turns outward, and inside corners get a matching fillet.
+    Liquid,
+    /// Horizontal runs of modules merged into rounded bars.
+    HorizontalBars,
+    /// Vertical runs of modules merged into rounded bars.
+    VerticalBars,
+    /// Dots joined to their dark orthogonal neighbours by short bridges.
+    ConnectedDots,
+}
+
+/// How raster renderers map the requested width onto whole modules.
+///
+/// Fractional scaling fills the requested width exactly but, when the width is
+/// not a multiple of the module count, some modules come out one pixel wider
+/// than others. The integer modes pick a whole number of pixels per module so
+/// every module has the same size, which scans better at small sizes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RasterScaling {
+    /// Stretch the modules fractionally to fill the requested width (default).
+    #[default]
+    Stretch,
+    /// Use the largest whole pixels-per-module that fits and centre the symbol
+    /// on a light canvas of the requested width.
+    Pad,
+    /// Use the largest whole pixels-per-module that fits and shrink the output
+    /// to exactly that many modules; see
+    /// [`QRCode::try_image_size`](QRCode::try_image_size).
+    Exact,
+}
+
+/// Output options for [`QRCode::to_svg_with_options`].
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{QRCode, SvgOptions};
+///
+/// let qr = QRCode::from_string("Hello".to_string());
+/// let svg = qr.to_svg_with_options(0, &SvgOptions::default().with_view_box_only(true));
+/// assert!(svg.contains("version=\"1.1\" viewBox=\"0 0 29 29\">"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct SvgOptions {
+    /// Emit only a `viewBox` in module units and no `width`/`height`, so the
+    /// document scales to whatever box it is placed in. The requested width
+    /// is then ignored.
+    pub view_box_only: bool,
+    /// Maximum number of decimals written for coordinates (default 3).
+    /// Trailing zeros are always dropped.
+    pub precision: u8,
+    /// Physical `width`/`height` (in millimetres) instead of pixels, e.g.
+    /// [`PrintLayout::size`]. Coordinates are then in module units, as with
+    /// [`view_box_only`](Self::view_box_only).
+    pub size: Option<Length>,
 }
 
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            view_box_only: false,
+            precision: 3,
+            size: None,
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Sets [`view_box_only`](Self::view_box_only) (builder pattern).
+    #[must_use]
+    pub fn with_view_box_only(mut self, view_box_only: bool) -> Self {
+        self.view_box_only = view_box_only;
+        self
+    }
+
+    /// Sets [`precision`](Self::precision) (builder pattern).
+    #[must_use]
+    pub fn with_precision(mut self, precision: u8) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Sets a physical [`size`](Self::size) (builder pattern).
+    #[must_use]
+    pub fn with_size(mut self, size: Length) -> Self {
+        self.size = Some(size);
+        self
+    }
+}
+
+/// Raster encodings accepted by [`QRCode::encode`].
+///
+/// WebP and TIFF need the `webp` and `tiff` cargo features; the others are
+/// always available.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OutputFormat {
+    /// Lossless PNG.
+    Png,
+    /// JPEG at `quality` (1-100); the alpha channel is dropped.
+    Jpeg {
+        /// Encoder quality, 1-100.
+        quality: u8,
+    },
+    /// GIF.
+    Gif,
+    /// Uncompressed BMP with an alpha channel.
+    Bmp,
+    /// Windows icon holding the code at the requested width plus one image
+    /// per entry of `extra_sizes`, each PNG-compressed. Every size must be
+    /// between 1 and 256 pixels.
+    Ico {
+        /// Additional square sizes, in pixels; duplicates are dropped.
+        extra_sizes: Vec<u32>,
+    },
+    /// Lossless WebP.
+    #[cfg(feature = "webp")]
+    WebP,
+    /// TIFF.
+    #[cfg(feature = "tiff")]
+    Tiff,
+}
+
+impl OutputFormat {
+    /// Conventional file extension, without the dot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::OutputFormat;
+    ///
+    /// assert_eq!(OutputFormat::Jpeg { quality: 85 }.extension(), "jpg");
+    /// ```
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Png => "png",
+            OutputFormat::Jpeg { .. } => "jpg",
+            OutputFormat::Gif => "gif",
+            OutputFormat::Bmp => "bmp",
+            OutputFormat::Ico { .. } => "ico",
+            #[cfg(feature = "webp")]
+            OutputFormat::WebP => "webp",
+            #[cfg(feature = "tiff")]
+            OutputFormat::Tiff => "tiff",
+        }
+    }
+}
+
+/// Encodes a rendered `img` to `format`, recording `dpi` where the format has
+/// a resolution field (PNG `pHYs`, JPEG JFIF density). Icons hold `img` only.
+fn encode_image(
+    img: RgbaImage,
+    format: &OutputFormat,
+    dpi: Option<u32>,
+) -> Result<Vec<u8>, QrcError> {
+    let image_format = match format {
+        OutputFormat::Png => return png::encode(&img, dpi, &[]),
+        OutputFormat::Jpeg { quality } => {
+            let rgb = DynamicImage::ImageRgba8(img).to_rgb8();
+            let mut buf = Cursor::new(Vec::new());
+            let mut encoder =
+                image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, *quality);
+            if let Some(dpi) = dpi {
+                let density = u16::try_from(dpi).unwrap_or(u16::MAX);
+                encoder.set_pixel_density(image::codecs::jpeg::PixelDensity::dpi(density));
+            }
+            encoder.encode_image(&rgb)?;
+            return Ok(buf.into_inner());
+        }
+        OutputFormat::Gif => ImageFormat::Gif,
+        OutputFormat::Bmp => ImageFormat::Bmp,
+        OutputFormat::Ico { .. } => ImageFormat::Ico,
+        #[cfg(feature = "webp")]
+        OutputFormat::WebP => ImageFormat::WebP,
+        #[cfg(feature = "tiff")]
+        OutputFormat::Tiff => ImageFormat::Tiff,
+    };
+    let mut buf = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(img).write_to(&mut buf, image_format)?;
+    Ok(buf.into_inner())
+}
+
+/// Largest side of a Windows icon image, in pixels.
+const MAX_ICON: u32 = 256;
+
 /// Represents a QR code containing data.
 ///
 /// This struct can be used to generate QR code images in various formats.
@@ -158,6 +428,38 @@ pub struct QRCode {
     pub ec_level: EcLevel,
     /// Shape used for rendering individual QR modules.
     pub shape: ModuleShape,
+    /// Width of the light border around the symbol, in modules.
+    pub quiet_zone: u32,
+    /// How `to_png`, `to_image` and the byte encoders map pixels to modules.
+    pub scaling: RasterScaling,
+    /// Colours used by every renderer.
+    pub style: QrStyle,
+    /// Shapes for the finder patterns; `None` draws them with
+    /// [`shape`](Self::shape) like any other module.
+    pub eye_style: Option<EyeStyle>,
+    /// Which symbol versions the encoder may use.
+    pub version: SymbolVersion,
+    /// How the data is split into segment modes.
+    pub mode: SegmentMode,
+    /// Mask pattern number, 0–7; `None` picks the pattern with the lowest
+    /// penalty.
+    pub mask: Option<u8>,
+    /// Standard QR code, Micro QR or rectangular Micro QR.
+    pub symbol_type: SymbolType,
+    /// Structured Append header placing this symbol in a sequence; set by
+    /// [`to_structured_append`](Self::to_structured_append) and
+    /// [`split_into`](Self::split_into).
+    pub structured_append: Option<Sequence>,
+    /// Character set [`data`](Self::data) is written in, announced to
+    /// scanners with an ECI designator; `None` emits no designator. Set it
+    /// with [`set_character_set`](Self::set_character_set), which
+    /// transcodes the data.
+    pub character_set: Option<CharacterSet>,
+    /// Whether [`data`](Self::data) is a GS1 element string, announced to
+    /// scanners with the FNC1 first-position indicator. Group separators
+    /// (`0x1D`) end variable-length fields. Build the data with
+    /// [`payload::gs1`].
+    pub gs1: bool,
 }
 
 impl Default for QRCode {
@@ -167,6 +469,17 @@ impl Default for QRCode {
             encoding_format: "utf-8".to_string(),
             ec_level: EcLevel::M,
             shape: ModuleShape::Square,
+            quiet_zone: DEFAULT_QUIET_ZONE,
+            scaling: RasterScaling::Stretch,
+            style: QrStyle::default(),
+            eye_style: None,
+            version: SymbolVersion::Auto,
+            mode: SegmentMode::Auto,
+            mask: None,
+            symbol_type: SymbolType::Qr,
+            structured_append: None,
+            character_set: None,
+            gs1: false,
         }
     }
 }
@@ -185,9 +498,7 @@ impl QRCode {
     pub fn new(data: Vec<u8>) -> Self {
         Self {
             data,
-            encoding_format: "utf-8".to_string(),
-            ec_level: EcLevel::M,
-            shape: ModuleShape::Square,
+            ..Self::default()
         }
     }
 
@@ -197,9 +508,7 @@ impl QRCode {
     pub fn from_string(data: String) -> Self {
         Self {
             data: data.into_bytes(),
-            encoding_format: "utf-8".to_string(),
-            ec_level: EcLevel::M,
-            shape: ModuleShape::Square,
+            ..Self::default()
         }
     }
 
@@ -208,9 +517,7 @@ impl QRCode {
     pub fn from_bytes(data: Vec<u8>) -> Self {
         Self {
             data,
-            encoding_format: "utf-8".to_string(),
-            ec_level: EcLevel::M,
-            shape: ModuleShape::Square,
+            ..Self::default()
         }
     }
 
@@ -248,24 +555,251 @@ impl QRCode {
         self
     }
 
-    /// Tries to convert the `QRCode` data to a `QrCode` structure.
+    /// Sets the quiet-zone width in modules (builder pattern).
+    ///
+    /// Every raster and vector renderer surrounds the symbol with this many
+    /// light modules. The specification asks for at least
+    /// [`DEFAULT_QUIET_ZONE`] (4); narrower borders may not scan on busy
+    /// backgrounds. Renderers reject widths above [`MAX_QUIET_ZONE`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::QRCode;
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string()).with_quiet_zone(2);
+    /// assert_eq!(qr.quiet_zone, 2);
+    /// ```
+    #[must_use]
+    pub fn with_quiet_zone(mut self, modules: u32) -> Self {
+        self.quiet_zone = modules;
+        self
+    }
+
+    /// Sets how raster renderers map pixels to modules (builder pattern).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{QRCode, RasterScaling};
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string())
+    ///     .with_scaling(RasterScaling::Exact);
+    /// let img = qr.to_png(100);
+    /// // 21 modules + 8 quiet-zone modules at 3 px each.
+    /// assert_eq!(img.width(), 87);
+    /// ```
+    #[must_use]
+    pub fn with_scaling(mut self, scaling: RasterScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Sets the colours used by every renderer (builder pattern).
+    ///
+    /// The style is validated when rendering: the `try_*` methods return
+    /// [`QrcError::InvalidOption`] for illegible combinations and the
+    /// panicking wrappers panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use image::Rgba;
+    /// use qrc::{QRCode, QrStyle};
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string())
+    ///     .with_style(QrStyle::new(Rgba([200, 200, 200, 255]), Rgba([255, 255, 255, 255])));
+    /// assert!(qr.try_to_png(128).is_err());
+    /// ```
+    #[must_use]
+    pub fn with_style(mut self, style: QrStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Draws the finder patterns with their own frame and centre shapes
+    /// (builder pattern).
+    ///
+    /// Eye colours are set on the [`style`](Self::style) with
+    /// [`QrStyle::with_eye_colors`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{EyeShape, EyeStyle, ModuleShape, QRCode};
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string())
+    ///     .with_shape(ModuleShape::Circle)
+    ///     .with_eye_style(EyeStyle::new(EyeShape::Leaf, EyeShape::Square));
+    /// assert_eq!(qr.eye_style.unwrap().frame, EyeShape::Leaf);
+    /// ```
+    #[must_use]
+    pub fn with_eye_style(mut self, eye_style: EyeStyle) -> Self {
+        self.eye_style = Some(eye_style);
+        self
+    }
+
+    /// Sets which symbol versions the encoder may use (builder pattern).
+    ///
+    /// [`SymbolVersion::Fixed`] gives every code the same size for a
+    /// consistent print layout; encoding fails when the data does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{QRCode, SymbolVersion};
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string()).with_version(SymbolVersion::Fixed(3));
+    /// assert_eq!(qr.to_qrcode().width(), 29);
+    ///
+    /// let long = QRCode::from_string("x".repeat(100)).with_version(SymbolVersion::Fixed(3));
+    /// assert!(long.try_to_qrcode().is_err());
+    /// ```
+    #[must_use]
+    pub fn with_version(mut self, version: SymbolVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Forces how the data is split into segments (builder pattern).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{QRCode, SegmentMode};
+    ///
+    /// let qr = QRCode::from_string("HELLO WORLD".to_string()).with_mode(SegmentMode::Alphanumeric);
+    /// assert!(qr.try_to_qrcode().is_ok());
+    /// let lower = QRCode::from_string("hello".to_string()).with_mode(SegmentMode::Alphanumeric);
+    /// assert!(lower.try_to_qrcode().is_err());
+    /// ```
+    #[must_use]
+    pub fn with_mode(mut self, mode: SegmentMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Forces mask pattern `mask`, 0–7, instead of the one with the lowest
+    /// penalty (builder pattern).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::QRCode;
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string()).with_mask(5);
+    /// assert_eq!(qr.to_qrcode().mask(), 5);
+    /// ```
+    #[must_use]
+    pub fn with_mask(mut self, mask: u8) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Chooses the symbol type (builder pattern). A
+    /// [`quiet_zone`](Self::quiet_zone) still at the previous type's default
+    /// moves to the width the new type calls for: 4 modules for QR codes
+    /// and 2 for Micro QR and rMQR. A width set with
+    /// [`with_quiet_zone`](Self::with_quiet_zone) is kept, whichever call
+    /// comes first.
+    ///
+    /// Every renderer draws the wide rMQR symbols in proportion: the width
+    /// passed to [`to_png`](Self::to_png) or [`to_svg`](Self::to_svg) sets
+    /// the image width, and the height follows from the module grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{QRCode, SymbolType, SymbolVersion};
+    ///
+    /// let qr = QRCode::from_string("SN 4711".to_string())
+    ///     .with_symbol_type(SymbolType::Rmqr)
+    ///     .with_version(SymbolVersion::rmqr(7, 43).unwrap());
+    /// let img = qr.to_png(470);
+    /// // 43 × 7 modules plus a 2-module quiet zone: 47 × 11 at 10 px each.
+    /// assert_eq!((img.width(), img.height()), (470, 110));
+    /// ```
+    #[must_use]
+    pub fn with_symbol_type(mut self, symbol_type: SymbolType) -> Self {
+        if self.quiet_zone == self.symbol_type.quiet_zone() {
+            self.quiet_zone = symbol_type.quiet_zone();
+        }
+        self.symbol_type = symbol_type;
+        self
+    }
+
+    /// Sets the Structured Append header (builder pattern). The header
+    /// takes 20 bits of the symbol and is only accepted on standard QR
+    /// codes; [`split_into`](Self::split_into) sets it on every part.
+    #[must_use]
+    pub fn with_structured_append(mut self, sequence: Sequence) -> Self {
+        self.structured_append = Some(sequence);
+        self
+    }
+
+    /// Marks the data as a GS1 element string (builder pattern), so the
+    /// symbol opens with the FNC1 first-position indicator that GS1 scanners
+    /// look for. Only standard QR codes carry it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::QRCode;
+    ///
+    /// let qr = QRCode::from_string("0109506000134352\u{1d}10ABC123".to_string()).with_gs1(true);
+    /// let decoded = qrc::decode::decode(&qr.to_image(300)).unwrap();
+    /// assert!(decoded.gs1);
+    /// assert_eq!(decoded.data, qr.data);
+    /// ```
+    #[must_use]
+    pub fn with_gs1(mut self, gs1: bool) -> Self {
+        self.gs1 = gs1;
+        self
+    }
+
+    /// Tries to encode the `QRCode` data as a [`Symbol`], honouring the
+    /// [`symbol_type`](Self::symbol_type), [`version`](Self::version),
+    /// [`mode`](Self::mode) and [`mask`](Self::mask) choices.
     ///
     /// # Errors
     ///
-    /// Returns `QrError` if the data is too long or otherwise invalid.
-    pub fn try_to_qrcode(&self) -> Result<QrCode, QrError> {
-        QrCode::with_error_correction_level(&self.data, self.ec_level)
+    /// Returns [`QrcError::CapacityExceeded`] if the data is too long for the
+    /// largest permitted symbol at the current error-correction level, or
+    /// [`QrcError::InvalidOption`] if the version or mask is out of range,
+    /// the symbol type lacks the error-correction level, or the data cannot
+    /// be encoded in the forced mode.
+    pub fn try_to_qrcode(&self) -> Result<Symbol, QrcError> {
+        symbol::encode(self)
+    }
+
+    /// Validates the style, then encodes the data; the common first step of
+    /// every renderer.
+    fn encode_for_render(&self) -> Result<Symbol, QrcError> {
+        self.style.validate()?;
+        self.encode_with_border()
     }
 
-    /// Converts the `QRCode` data to a `QrCode` structure.
+    /// Checks the quiet zone against [`MAX_QUIET_ZONE`], then encodes the
+    /// data; the first step wherever the rendered layout matters.
+    fn encode_with_border(&self) -> Result<Symbol, QrcError> {
+        if self.quiet_zone > MAX_QUIET_ZONE {
+            return Err(QrcError::InvalidOption(format!(
+                "a quiet zone of {} modules exceeds the maximum of {MAX_QUIET_ZONE}",
+                self.quiet_zone
+            )));
+        }
+        self.try_to_qrcode()
+    }
+
+    /// Encodes the `QRCode` data as a [`Symbol`].
     ///
     /// # Panics
     ///
     /// Panics if the data cannot be encoded as a valid QR code.
     /// Use [`try_to_qrcode`](Self::try_to_qrcode) for a fallible alternative.
     #[must_use]
-    pub fn to_qrcode(&self) -> QrCode {
-        self.try_to_qrcode().expect("Failed to encode QR code")
+    pub fn to_qrcode(&self) -> Symbol {
+        self.try_to_qrcode().expect(ENCODE_FAILED)
     }
 
     /// Renders the QR code into an RGBA image buffer at the given width.
@@ -276,25 +810,244 @@ impl QRCode {
         clippy::cast_possible_truncation,
         clippy::cast_sign_loss
     )]
-    fn render_image(&self, width: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
-        let qrcode = self.to_qrcode();
-        let height = width;
-        let qr_width = qrcode.width() as f64;
+    fn render_image(&self, width: u32) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, QrcError> {
+        let qrcode = self.encode_for_render()?;
+        if self.scaling != RasterScaling::Stretch {
+            let (module_px, size) = self.aligned_layout(&qrcode, width);
+            return self.render_aligned(&qrcode, module_px, size);
+        }
+        let (cols, rows) = self.total_modules(&qrcode);
+        let height = proportional_height(width, cols, rows);
+        let (qr_width, qr_height) = (cols as f64, rows as f64);
         let module_size = f64::from(width) / qr_width;
-        let mut img: RgbaImage = ImageBuffer::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+        let mut img = canvas(width, height, self.style.background())?;
 
         for (x, y, pixel) in img.enumerate_pixels_mut() {
             let x_index = (f64::from(x) / f64::from(width) * qr_width) as usize;
-            let y_index = (f64::from(y) / f64::from(height) * qr_width) as usize;
-            if qrcode[(x_index, y_index)] == Color::Dark {
-                let mod_x = f64::from(x) - (x_index as f64) * module_size;
-                let mod_y = f64::from(y) - (y_index as f64) * module_size;
-                if self.is_inside_shape(mod_x, mod_y, module_size) {
-                    *pixel = Rgba([0, 0, 0, 255]);
+            let y_index = (f64::from(y) / f64::from(height) * qr_height) as usize;
+            let mod_x = f64::from(x) - (x_index as f64) * module_size;
+            let mod_y = f64::from(y) - (y_index as f64) * module_size;
+            let cell = (x_index, y_index);
+            let module = (module_size, module_size);
+            if let Some(color) = self.paint(&qrcode, cell, (mod_x, mod_y), module) {
+                *pixel = color;
+            }
+        }
+        Ok(img)
+    }
+
+    /// Pixels per module and output width and height for an integer-aligned
+    /// rendering about `width` pixels wide. At least one pixel per module is
+    /// always used, so tiny widths grow to fit the symbol. `Pad` adds the
+    /// same margin above and below as at the sides.
+    #[allow(clippy::cast_possible_truncation)]
+    fn aligned_layout(&self, qrcode: &Symbol, width: u32) -> (u32, (u32, u32)) {
+        let (cols, rows) = self.total_modules(qrcode);
+        let (cols, rows) = (cols as u32, rows as u32);
+        let module_px = (width / cols).max(1);
+        let margin = match self.scaling {
+            RasterScaling::Pad => width.saturating_sub(module_px * cols),
+            _ => 0,
+        };
+        (
+            module_px,
+            (module_px * cols + margin, module_px * rows + margin),
+        )
+    }
+
+    /// Renders every module as a `module_px`-pixel square cell, centred on a
+    /// light canvas `size` pixels wide and high. Shapes are sampled at pixel
+    /// centres so each module is drawn identically. Fails if the canvas
+    /// would exceed [`MAX_RASTER_BYTES`].
+    #[allow(clippy::cast_possible_truncation)]
+    fn render_aligned(
+        &self,
+        qrcode: &Symbol,
+        module_px: u32,
+        size: (u32, u32),
+    ) -> Result<RgbaImage, QrcError> {
+        let (cols, rows) = self.total_modules(qrcode);
+        let (cols, rows) = (cols as u32, rows as u32);
+        let offset_x = (size.0 - module_px * cols) / 2;
+        let offset_y = (size.1 - module_px * rows) / 2;
+        let module_size = f64::from(module_px);
+        let mut img = canvas(size.0, size.1, self.style.background())?;
+
+        for my in 0..rows {
+            for mx in 0..cols {
+                let cell = (mx as usize, my as usize);
+                // Light cells only matter for eye shapes and liquid fillets.
+                if !self.is_dark(qrcode, cell.0, cell.1)
+                    && self.eye_cell(qrcode, cell).is_none()
+                    && self.shape != ModuleShape::Liquid
+                {
+                    continue;
                 }
+                for dy in 0..module_px {
+                    for dx in 0..module_px {
+                        let at = (f64::from(dx) + 0.5, f64::from(dy) + 0.5);
+                        let module = (module_size, module_size);
+                        if let Some(color) = self.paint(qrcode, cell, at, module) {
+                            let px = offset_x + mx * module_px + dx;
+                            let py = offset_y + my * module_px + dy;
+                            img.put_pixel(px, py, color);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(img)
+    }
+
+    /// Width and height of the rendered grid in modules: the symbol plus the
+    /// quiet zone on both sides.
+    fn total_modules(&self, qrcode: &Symbol) -> (usize, usize) {
+        let quiet = 2 * self.quiet_zone as usize;
+        (qrcode.width() + quiet, qrcode.height() + quiet)
+    }
+
+    /// Width and height, in pixels, of the rendered grid at `module_px`
+    /// pixels per module, or [`QrcError::InvalidOption`] if either does not
+    /// fit in a `u32`.
+    fn grid_pixels(&self, qrcode: &Symbol, module_px: u32) -> Result<(u32, u32), QrcError> {
+        let (cols, rows) = self.total_modules(qrcode);
+        let px = |modules: usize| u32::try_from(modules).ok()?.checked_mul(module_px);
+        match (px(cols), px(rows)) {
+            (Some(width), Some(height)) => Ok((width, height)),
+            _ => Err(QrcError::InvalidOption(format!(
+                "{cols} modules at {module_px} px each is too large an image"
+            ))),
+        }
+    }
+
+    /// Whether grid cell (`x`, `y`) — counted from the outer edge of the quiet
+    /// zone — is a dark module of `qrcode`.
+    fn is_dark(&self, qrcode: &Symbol, x: usize, y: usize) -> bool {
+        let quiet = self.quiet_zone as usize;
+        let (x, y) = (x.wrapping_sub(quiet), y.wrapping_sub(quiet));
+        x < qrcode.width() && y < qrcode.height() && qrcode[(x, y)] == Color::Dark
+    }
+
+    /// Colour of the pixel at offset `at` inside grid `cell`, for modules
+    /// `module` pixels wide and high, or `None` where the light background
+    /// shows through. Finder patterns are drawn from the
+    /// [`eye_style`](Self::eye_style) shapes when one is set; everything else
+    /// from the module [`shape`](Self::shape).
+    #[allow(clippy::cast_precision_loss)]
+    fn paint(
+        &self,
+        qrcode: &Symbol,
+        cell: (usize, usize),
+        at: (f64, f64),
+        module: (f64, f64),
+    ) -> Option<Rgba<u8>> {
+        let fx = cell.0 as f64 + at.0 / module.0;
+        let fy = cell.1 as f64 + at.1 / module.1;
+        if let Some((eye_style, origin)) = self.eye_cell(qrcode, cell) {
+            let quiet = f64::from(self.quiet_zone);
+            let lx = fx - quiet - origin.0 as f64;
+            let ly = fy - quiet - origin.1 as f64;
+            let corner = (origin.0 == 0, origin.1 == 0);
+            let part = eye::shape_part(eye_style, corner, lx, ly)?;
+            return Some(self.eye_color(qrcode, part, origin, fx, fy));
+        }
+        if self.shape.is_connected() {
+            let dark = self.is_dark(qrcode, cell.0, cell.1);
+            let (u, v) = (at.0 / module.0, at.1 / module.1);
+            return shape::pieces(self.shape, dark, self.neighbours(qrcode, cell))
+                .into_iter()
+                .any(|piece| piece.contains(u, v))
+                .then(|| self.dark_color(qrcode, fx, fy));
+        }
+        if !self.is_dark(qrcode, cell.0, cell.1) {
+            return None;
+        }
+        // Use the average module size for the shape check.
+        let avg_mod = (module.0 + module.1) / 2.0;
+        self.is_inside_shape(at.0, at.1, avg_mod)
+            .then(|| self.dark_color(qrcode, fx, fy))
+    }
+
+    /// Which of the eight grid cells around `cell` are dark.
+    fn neighbours(&self, qrcode: &Symbol, cell: (usize, usize)) -> Neighbours {
+        let dark = |dx: isize, dy: isize| {
+            let x = cell.0.wrapping_add_signed(dx);
+            let y = cell.1.wrapping_add_signed(dy);
+            self.is_dark(qrcode, x, y)
+        };
+        Neighbours {
+            sides: [dark(0, -1), dark(1, 0), dark(0, 1), dark(-1, 0)],
+            diagonals: [dark(-1, -1), dark(1, -1), dark(1, 1), dark(-1, 1)],
+        }
+    }
+
+    /// The eye style and finder origin for `cell`, if it lies in a finder
+    /// pattern that is drawn with an [`EyeStyle`].
+    fn eye_cell(
+        &self,
+        qrcode: &Symbol,
+        cell: (usize, usize),
+    ) -> Option<(EyeStyle, (usize, usize))> {
+        let eye_style = self.eye_style?;
+        Some((eye_style, self.finder_at(qrcode, cell.0, cell.1)?))
+    }
+
+    /// Paint for a dark pixel at fractional grid position (`fx`, `fy`), in
+    /// modules from the outer edge of the quiet zone: the eye colours inside
+    /// finder patterns, else the symbol-wide gradient, else the flat dark
+    /// colour.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn dark_color(&self, qrcode: &Symbol, fx: f64, fy: f64) -> Rgba<u8> {
+        let (x, y) = (fx as usize, fy as usize);
+        if let Some(origin) = self.finder_at(qrcode, x, y) {
+            let quiet = self.quiet_zone as usize;
+            let local = (x - quiet - origin.0, y - quiet - origin.1);
+            if let Some(part) = eye::cell_part(local.0, local.1) {
+                return self.eye_color(qrcode, part, origin, fx, fy);
             }
         }
-        img
+        self.body_color(qrcode, fx, fy)
+    }
+
+    /// Colour of `part` of the finder pattern at `origin`, at fractional grid
+    /// position (`fx`, `fy`): its flat eye colour, else the eye gradient over
+    /// that pattern, else the body paint.
+    #[allow(clippy::cast_precision_loss)]
+    fn eye_color(
+        &self,
+        qrcode: &Symbol,
+        part: EyePart,
+        origin: (usize, usize),
+        fx: f64,
+        fy: f64,
+    ) -> Rgba<u8> {
+        let flat = match part {
+            EyePart::Frame => self.style.eye_frame,
+            EyePart::Ball => self.style.eye_ball,
+        };
+        if let Some(color) = flat {
+            return color;
+        }
+        if let Some(gradient) = &self.style.eye_gradient {
+            let quiet = f64::from(self.quiet_zone);
+            let side = art::FINDER_SIZE as f64;
+            let u = (fx - quiet - origin.0 as f64) / side;
+            let v = (fy - quiet - origin.1 as f64) / side;
+            return gradient.color_at(u, v);
+        }
+        self.body_color(qrcode, fx, fy)
+    }
+
+    /// The symbol-wide gradient at fractional grid position (`fx`, `fy`), or
+    /// the flat dark colour.
+    #[allow(clippy::cast_precision_loss)]
+    fn body_color(&self, qrcode: &Symbol, fx: f64, fy: f64) -> Rgba<u8> {
+        let (w, h) = (qrcode.width() as f64, qrcode.height() as f64);
+        let quiet = f64::from(self.quiet_zone);
+        match &self.style.gradient {
+            Some(gradient) => gradient.color_at((fx - quiet) / w, (fy - quiet) / h),
+            None => self.style.dark,
+        }
     }
 
     /// Checks whether a pixel at (`mod_x`, `mod_y`) within a module of the given
@@ -317,163 +1070,807 @@ impl QRCode {
                 let half = module_size / 2.0;
                 (mod_x - half).abs() + (mod_y - half).abs() <= half
             }
+            // Connected shapes are resolved against their neighbours in `paint`.
+            ModuleShape::Liquid
+            | ModuleShape::HorizontalBars
+            | ModuleShape::VerticalBars
+            | ModuleShape::ConnectedDots => true,
+        }
+    }
+
+    /// Converts the `QRCode` to a PNG image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_png`](Self::try_to_png) for a fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::QRCode;
+    ///
+    /// let qr = QRCode::from_string("Hello, world!".to_string());
+    /// let png_image = qr.to_png(256);
+    /// ```
+    #[must_use]
+    pub fn to_png(&self, width: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+        self.try_to_png(width).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_png`](Self::to_png).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`] or the image would exceed
+    /// [`MAX_RASTER_BYTES`].
+    pub fn try_to_png(&self, width: u32) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, QrcError> {
+        self.render_image(width)
+    }
+
+    /// Returns the PNG-encoded bytes of the QR code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit,
+    /// [`QrcError::InvalidOption`] if the style is illegible, or
+    /// [`QrcError::ImageEncode`] if encoding fails — for example, a `width` of
+    /// `0`, which is not a valid PNG size.
+    pub fn to_png_bytes(&self, width: u32) -> Result<Vec<u8>, QrcError> {
+        self.encode(width, OutputFormat::Png)
+    }
+
+    /// Returns PNG-encoded bytes with the metadata chosen by `options`.
+    ///
+    /// Like [`to_png_bytes`](Self::to_png_bytes), the image is stored in the
+    /// smallest lossless colour type its colours allow: 1-bit greyscale for
+    /// black on white, a 1- to 8-bit palette for up to 256 colours, and
+    /// 8-bit RGB(A) otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit,
+    /// [`QrcError::InvalidOption`] if the style is illegible, the image
+    /// would exceed [`MAX_RASTER_BYTES`], a text keyword
+    /// is not 1-79 printable Latin-1 characters, or the payload is embedded
+    /// but is not UTF-8, or [`QrcError::ImageEncode`] for a `width` of `0`.
+    pub fn to_png_bytes_with_options(
+        &self,
+        width: u32,
+        options: &PngOptions,
+    ) -> Result<Vec<u8>, QrcError> {
+        let mut text = Vec::new();
+        if options.software {
+            text.push((
+                "Software".to_string(),
+                format!("qrc {}", env!("CARGO_PKG_VERSION")),
+            ));
+        }
+        if options.embed_payload {
+            let payload = String::from_utf8(self.data.clone()).map_err(|_| {
+                QrcError::InvalidOption("only UTF-8 payloads can be embedded as text".to_string())
+            })?;
+            text.push(("Payload".to_string(), payload));
+        }
+        text.extend(options.text.iter().cloned());
+        png::encode(&self.render_image(width)?, options.dpi, &text)
+    }
+
+    /// Returns GIF-encoded bytes of the QR code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit,
+    /// [`QrcError::InvalidOption`] if the style is illegible, or
+    /// [`QrcError::ImageEncode`] if GIF encoding fails.
+    pub fn to_gif(&self, width: u32) -> Result<Vec<u8>, QrcError> {
+        self.encode(width, OutputFormat::Gif)
+    }
+
+    /// Returns JPEG-encoded bytes (quality 85) of the QR code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit,
+    /// [`QrcError::InvalidOption`] if the style is illegible, or
+    /// [`QrcError::ImageEncode`] if encoding fails — for example, a `width` of
+    /// `0`, which is not a valid JPEG size.
+    pub fn to_jpg(&self, width: u32) -> Result<Vec<u8>, QrcError> {
+        self.to_jpg_with_quality(width, 85)
+    }
+
+    /// Returns JPEG-encoded bytes at a custom quality (1-100).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit,
+    /// [`QrcError::InvalidOption`] if the style is illegible, or
+    /// [`QrcError::ImageEncode`] if encoding fails — for example, a `width` of
+    /// `0`, which is not a valid JPEG size.
+    pub fn to_jpg_with_quality(&self, width: u32, quality: u8) -> Result<Vec<u8>, QrcError> {
+        self.encode(width, OutputFormat::Jpeg { quality })
+    }
+
+    /// Renders the QR code at `width` pixels and encodes it to `format`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{OutputFormat, QRCode};
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string());
+    /// let bmp = qr.encode(128, OutputFormat::Bmp).unwrap();
+    /// assert!(bmp.starts_with(b"BM"));
+    ///
+    /// let icon = qr.encode(64, OutputFormat::Ico { extra_sizes: vec![16, 32] }).unwrap();
+    /// assert_eq!(&icon[4..6], &[3, 0]); // three images
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit,
+    /// [`QrcError::InvalidOption`] if the style is illegible, the image would
+    /// exceed [`MAX_RASTER_BYTES`], or an icon size is outside 1-256, or [`QrcError::ImageEncode`] if encoding fails — for
+    /// example, a `width` of `0`.
+    pub fn encode(&self, width: u32, format: OutputFormat) -> Result<Vec<u8>, QrcError> {
+        match format {
+            OutputFormat::Png => self.to_png_bytes_with_options(width, &PngOptions::default()),
+            OutputFormat::Ico { extra_sizes } => self.encode_ico(width, &extra_sizes),
+            other => encode_image(self.render_image(width)?, &other, None),
+        }
+    }
+
+    /// A multi-image Windows icon at `width` and each of `extra_sizes`.
+    fn encode_ico(&self, width: u32, extra_sizes: &[u32]) -> Result<Vec<u8>, QrcError> {
+        use image::codecs::ico::{IcoEncoder, IcoFrame};
+
+        let mut sizes = vec![width];
+        sizes.extend_from_slice(extra_sizes);
+        sizes.sort_unstable();
+        sizes.dedup();
+        if let Some(bad) = sizes.iter().find(|&&size| !(1..=MAX_ICON).contains(&size)) {
+            return Err(QrcError::InvalidOption(format!(
+                "icon sizes must be between 1 and {MAX_ICON} pixels, got {bad}"
+            )));
+        }
+        let mut frames = Vec::with_capacity(sizes.len());
+        for size in sizes {
+            let img = self.render_image(size)?;
+            frames.push(IcoFrame::as_png(
+                img.as_raw(),
+                img.width(),
+                img.height(),
+                image::ExtendedColorType::Rgba8,
+            )?);
+        }
+        let mut buf = Vec::new();
+        IcoEncoder::new(&mut buf).encode_images(&frames)?;
+        Ok(buf)
+    }
+
+    /// Returns the raw RGBA image buffer for the QR code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_image`](Self::try_to_image) for a fallible alternative.
+    #[must_use]
+    pub fn to_image(&self, width: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+        self.try_to_image(width).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_image`](Self::to_image).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`] or the image would exceed
+    /// [`MAX_RASTER_BYTES`].
+    pub fn try_to_image(&self, width: u32) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, QrcError> {
+        self.render_image(width)
+    }
+
+    /// Renders the QR code at exactly `module_px` pixels per module.
+    ///
+    /// The output is `(symbol width + 2 × quiet zone) × module_px` pixels
+    /// wide, and as high in proportion, independent of
+    /// [`scaling`](Self::scaling).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_image_scaled`](Self::try_to_image_scaled) for a fallible
+    /// alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::QRCode;
+    ///
+    /// let img = QRCode::from_string("Hello".to_string()).to_image_scaled(4);
+    /// assert_eq!(img.width(), (21 + 8) * 4);
+    /// ```
+    #[must_use]
+    pub fn to_image_scaled(&self, module_px: u32) -> RgbaImage {
+        self.try_to_image_scaled(module_px).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_image_scaled`](Self::to_image_scaled).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if `module_px` is `0`, the
+    /// style is illegible, or the image would be too large.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn try_to_image_scaled(&self, module_px: u32) -> Result<RgbaImage, QrcError> {
+        if module_px == 0 {
+            return Err(QrcError::InvalidOption(
+                "pixels per module must be at least 1".to_string(),
+            ));
+        }
+        let qrcode = self.encode_for_render()?;
+        let size = self.grid_pixels(&qrcode, module_px)?;
+        self.render_aligned(&qrcode, module_px, size)
+    }
+
+    /// Returns the width, in pixels, of the image that `to_png`, `to_image`
+    /// and the byte encoders produce for the requested `width` under the
+    /// current [`scaling`](Self::scaling). Only rMQR images differ in height.
+    ///
+    /// `Stretch` always yields `width`; `Pad` yields `width` unless the symbol
+    /// needs more pixels; `Exact` yields the largest whole-module size that
+    /// fits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code.
+    pub fn try_image_size(&self, width: u32) -> Result<u32, QrcError> {
+        let qrcode = self.encode_with_border()?;
+        Ok(match self.scaling {
+            RasterScaling::Stretch => width,
+            _ => self.aligned_layout(&qrcode, width).1 .0,
+        })
+    }
+
+    /// Converts the `QRCode` to an SVG image.
+    ///
+    /// Every module shape is written as a single merged `<path>` per layer,
+    /// with coordinates rounded to three decimals. Use
+    /// [`to_svg_with_options`](Self::to_svg_with_options) for a scalable
+    /// `viewBox`-only document or a different precision.
+    ///
+    /// # Parameters
+    ///
+    /// * `width`: The width of the desired image in pixels.
+    ///
+    /// # Returns
+    ///
+    /// A `String` representing the QR code in SVG format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_svg`](Self::try_to_svg) for a fallible alternative.
+    #[must_use]
+    pub fn to_svg(&self, width: u32) -> String {
+        self.try_to_svg(width).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_svg`](Self::to_svg).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`].
+    pub fn try_to_svg(&self, width: u32) -> Result<String, QrcError> {
+        self.try_to_svg_with_options(width, &SvgOptions::default())
+    }
+
+    /// Converts the `QRCode` to an SVG image with explicit [`SvgOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{QRCode, SvgOptions};
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string());
+    /// let svg = qr.to_svg_with_options(100, &SvgOptions::default().with_precision(1));
+    /// assert!(svg.contains("M13.8 13.8h"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_svg_with_options`](Self::try_to_svg_with_options) for a
+    /// fallible alternative.
+    #[must_use]
+    pub fn to_svg_with_options(&self, width: u32, options: &SvgOptions) -> String {
+        self.try_to_svg_with_options(width, options)
+            .expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_svg_with_options`](Self::to_svg_with_options).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`].
+    #[allow(clippy::cast_precision_loss)]
+    pub fn try_to_svg_with_options(
+        &self,
+        width: u32,
+        options: &SvgOptions,
+    ) -> Result<String, QrcError> {
+        let qrcode = self.encode_for_render()?;
+        let (cols, rows) = self.total_modules(&qrcode);
+        let module_size = if options.view_box_only || options.size.is_some() {
+            1.0
+        } else {
+            f64::from(width) / cols as f64
+        };
+        let precision = options.precision;
+        let mut defs = String::new();
+        let mut defined = Vec::new();
+        let mut elements = String::new();
+        for layer in self.vector_layers(&qrcode, module_size) {
+            let fill = match &layer.fill {
+                Fill::Solid(color) => style::svg_fill(*color),
+                Fill::Gradient {
+                    name,
+                    gradient,
+                    x,
+                    y,
+                    size,
+                } => {
+                    if !defined.contains(name) {
+                        defs.push_str(&gradient.svg_def(name, *x, *y, *size, precision));
+                        defined.push(name.clone());
+                    }
+                    format!("fill=\"url(#{name})\"")
+                }
+            };
+            let rule = if layer.even_odd {
+                "fill-rule=\"evenodd\" "
+            } else {
+                ""
+            };
+            let _ = write!(
+                elements,
+                "<path {rule}{fill} d=\"{}\"/>",
+                layer.path.to_svg(precision)
+            );
+        }
+        if !defs.is_empty() {
+            elements = format!("<defs>{defs}</defs>{elements}");
+        }
+
+        let background = if self.style.transparent_background {
+            String::new()
+        } else {
+            format!(
+                "<rect width=\"100%\" height=\"100%\" {}/>",
+                style::svg_fill(self.style.light)
+            )
+        };
+        let size = if let Some(size) = options.size {
+            let mm = size.to_millimetres();
+            let (w, h) = (mm, mm * rows as f64 / cols as f64);
+            let (w, h) = (vector::number(w, 3), vector::number(h, 3));
+            format!("width=\"{w}mm\" height=\"{h}mm\" viewBox=\"0 0 {cols} {rows}\"")
+        } else if options.view_box_only {
+            format!("viewBox=\"0 0 {cols} {rows}\"")
+        } else {
+            let height = vector::number(f64::from(width) * rows as f64 / cols as f64, precision);
+            format!("width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\"")
+        };
+        Ok(format!(
+            "<?xml version=\"1.0\" standalone=\"yes\"?><svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" {size}>{background}{elements}</svg>"
+        ))
+    }
+
+    /// Works out the whole-dot module grid for printing at `size`, quiet zone
+    /// included, and reports the physical dimensions that result.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the DPI is `0`, a length is
+    /// not positive, the symbol cannot get one dot per module in the space
+    /// given, or the image would exceed [`MAX_RASTER_BYTES`].
+    pub fn print_layout(&self, size: &PrintSize) -> Result<PrintLayout, QrcError> {
+        let qrcode = self.encode_with_border()?;
+        let modules = u32::try_from(self.total_modules(&qrcode).0).unwrap_or(u32::MAX);
+        let layout = size.layout(modules)?;
+        let (_, height) = self.grid_pixels(&qrcode, layout.module_px)?;
+        check_raster(layout.width_px, height)?;
+        Ok(layout)
+    }
+
+    /// Renders the code on the grid chosen by [`print_layout`](Self::print_layout)
+    /// and encodes it to `format`, recording the DPI in PNG (`pHYs`) and JPEG
+    /// (JFIF density) output.
+    ///
+    /// Vector output needs no pixel grid: pass the layout's
+    /// [`size`](PrintLayout::size) to [`to_pdf`](Self::to_pdf),
+    /// [`to_eps`](Self::to_eps) or [`SvgOptions::with_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{Length, OutputFormat, PrintSize, QRCode};
+    ///
+    /// let qr = QRCode::from_string("Hello".to_string());
+    /// let size = PrintSize::fit(Length::Millimetres(20.0), 600);
+    /// let png = qr.encode_for_print(&size, OutputFormat::Png).unwrap();
+    /// let img = image::load_from_memory(&png).unwrap();
+    /// // 20 mm at 600 dpi is 472 dots: 16 per module for 29 modules.
+    /// assert_eq!(img.width(), 29 * 16);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`print_layout`](Self::print_layout) and
+    /// [`encode`](Self::encode), and [`QrcError::InvalidOption`] for
+    /// [`OutputFormat::Ico`], whose fixed icon sizes carry no print
+    /// resolution.
+    pub fn encode_for_print(
+        &self,
+        size: &PrintSize,
+        format: OutputFormat,
+    ) -> Result<Vec<u8>, QrcError> {
+        if let OutputFormat::Ico { .. } = format {
+            return Err(QrcError::InvalidOption(
+                "ICO icons cannot be sized for print".to_string(),
+            ));
         }
+        let layout = self.print_layout(size)?;
+        let img = self.try_to_image_scaled(layout.module_px)?;
+        encode_image(img, &format, Some(layout.dpi))
     }
 
-    /// Converts the `QRCode` to a PNG image.
+    /// Converts the `QRCode` to a single-page PDF document.
+    ///
+    /// The page is `size` wide, quiet zone included, and as high as the
+    /// symbol's proportions ask (square but for rMQR). Every module is a
+    /// vector path in the current [`shape`](Self::shape) and
+    /// [`style`](Self::style); gradients become PDF shadings. Colours are
+    /// written as opaque RGB.
     ///
     /// # Examples
     ///
     /// ```
-    /// use qrc::QRCode;
+    /// use qrc::{Length, QRCode};
     ///
-    /// let qr = QRCode::from_string("Hello, world!".to_string());
-    /// let png_image = qr.to_png(256);
+    /// let qr = QRCode::from_string("Hello".to_string());
+    /// let pdf = qr.to_pdf(Length::Millimetres(30.0));
+    /// assert!(pdf.starts_with(b"%PDF-1.4"));
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code or `size` is
+    /// out of range. Use [`try_to_pdf`](Self::try_to_pdf) for a fallible
+    /// alternative.
     #[must_use]
-    pub fn to_png(&self, width: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
-        self.render_image(width)
-    }
-
-    /// Renders the QR code and encodes it to `format`, returning the bytes.
-    fn encode_bytes(&self, width: u32, format: ImageFormat) -> Result<Vec<u8>, image::ImageError> {
-        let img = self.render_image(width);
-        let mut buf = Cursor::new(Vec::new());
-        DynamicImage::ImageRgba8(img).write_to(&mut buf, format)?;
-        Ok(buf.into_inner())
+    pub fn to_pdf(&self, size: Length) -> Vec<u8> {
+        self.try_to_pdf(size).expect(ENCODE_FAILED)
     }
 
-    /// Returns the PNG-encoded bytes of the QR code.
+    /// Fallible version of [`to_pdf`](Self::to_pdf).
     ///
     /// # Errors
     ///
-    /// Returns an [`image::ImageError`] if encoding fails — for example, a
-    /// `width` of `0`, which is not a valid PNG size.
-    pub fn to_png_bytes(&self, width: u32) -> Result<Vec<u8>, image::ImageError> {
-        self.encode_bytes(width, ImageFormat::Png)
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if `size` is not positive,
+    /// exceeds 200 inches, or the [`style`](Self::style) fails
+    /// [`QrStyle::validate`].
+    #[allow(clippy::cast_precision_loss)]
+    pub fn try_to_pdf(&self, size: Length) -> Result<Vec<u8>, QrcError> {
+        let side = size.checked_points("PDF page size", pdf::MAX_PAGE)?;
+        let qrcode = self.encode_for_render()?;
+        let (cols, rows) = self.total_modules(&qrcode);
+        let background = (!self.style.transparent_background).then_some(self.style.light);
+        Ok(pdf::document(
+            &self.vector_layers(&qrcode, 1.0),
+            background,
+            (cols as f64, rows as f64),
+            side,
+        ))
     }
 
-    /// Returns GIF-encoded bytes of the QR code.
+    /// Converts the `QRCode` to Encapsulated PostScript.
     ///
-    /// # Errors
+    /// The `%%BoundingBox` is `size` wide, quiet zone included, and as high
+    /// as the symbol's proportions ask (square but for rMQR). Every module is
+    /// a vector path in the current [`shape`](Self::shape). Colours
+    /// from the [`style`](Self::style) are converted to CMYK with
+    /// `setcmykcolor`; gradients need a LanguageLevel 3 interpreter.
     ///
-    /// Returns an [`image::ImageError`] if GIF encoding fails.
-    pub fn to_gif(&self, width: u32) -> Result<Vec<u8>, image::ImageError> {
-        self.encode_bytes(width, ImageFormat::Gif)
-    }
-
-    /// Returns JPEG-encoded bytes (quality 85) of the QR code.
+    /// # Examples
     ///
-    /// # Errors
+    /// ```
+    /// use qrc::{Length, QRCode};
     ///
-    /// Returns an [`image::ImageError`] if encoding fails — for example, a
-    /// `width` of `0`, which is not a valid JPEG size.
-    pub fn to_jpg(&self, width: u32) -> Result<Vec<u8>, image::ImageError> {
-        self.to_jpg_with_quality(width, 85)
+    /// let qr = QRCode::from_string("Hello".to_string());
+    /// let eps = qr.to_eps(Length::Inches(1.0));
+    /// assert!(eps.starts_with("%!PS-Adobe-3.0 EPSF-3.0"));
+    /// assert!(eps.contains("%%BoundingBox: 0 0 72 72"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code or `size` is
+    /// out of range. Use [`try_to_eps`](Self::try_to_eps) for a fallible
+    /// alternative.
+    #[must_use]
+    pub fn to_eps(&self, size: Length) -> String {
+        self.try_to_eps(size).expect(ENCODE_FAILED)
     }
 
-    /// Returns JPEG-encoded bytes at a custom quality (1-100).
+    /// Fallible version of [`to_eps`](Self::to_eps).
     ///
     /// # Errors
     ///
-    /// Returns an [`image::ImageError`] if encoding fails — for example, a
-    /// `width` of `0`, which is not a valid JPEG size.
-    pub fn to_jpg_with_quality(
-        &self,
-        width: u32,
-        quality: u8,
-    ) -> Result<Vec<u8>, image::ImageError> {
-        let img = self.render_image(width);
-        let rgb = DynamicImage::ImageRgba8(img).to_rgb8();
-        let mut buf = Cursor::new(Vec::new());
-        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, quality).encode_image(&rgb)?;
-        Ok(buf.into_inner())
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if `size` is not positive,
+    /// exceeds 200 inches, or the [`style`](Self::style) fails
+    /// [`QrStyle::validate`].
+    #[allow(clippy::cast_precision_loss)]
+    pub fn try_to_eps(&self, size: Length) -> Result<String, QrcError> {
+        let side = size.checked_points("EPS size", pdf::MAX_PAGE)?;
+        let qrcode = self.encode_for_render()?;
+        let (cols, rows) = self.total_modules(&qrcode);
+        let background = (!self.style.transparent_background).then_some(self.style.light);
+        Ok(eps::document(
+            &self.vector_layers(&qrcode, 1.0),
+            background,
+            (cols as f64, rows as f64),
+            side,
+        ))
     }
 
-    /// Returns the raw RGBA image buffer for the QR code.
+    /// Renders the `QRCode` as Unicode half blocks, two module rows per
+    /// line, for printing on a terminal with a light background.
+    ///
+    /// Shorthand for [`to_terminal`](Self::to_terminal) with the default
+    /// [`TerminalOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::QRCode;
+    ///
+    /// let text = QRCode::from_string("Hello".to_string()).to_unicode();
+    /// // 21 modules plus a 4-module quiet zone on each side: 29 rows.
+    /// assert_eq!(text.lines().count(), 15);
+    /// assert!(text.lines().nth(2).unwrap().contains('█'));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_unicode`](Self::try_to_unicode) for a fallible alternative.
     #[must_use]
-    pub fn to_image(&self, width: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
-        self.render_image(width)
+    pub fn to_unicode(&self) -> String {
+        self.try_to_unicode().expect(ENCODE_FAILED)
     }
 
-    /// Converts the `QRCode` to an SVG image.
+    /// Fallible version of [`to_unicode`](Self::to_unicode).
     ///
-    /// For non-square shapes, a custom SVG renderer is used.
-    ///
-    /// # Parameters
+    /// # Errors
     ///
-    /// * `width`: The width of the desired image in pixels.
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`].
+    pub fn try_to_unicode(&self) -> Result<String, QrcError> {
+        self.try_to_terminal(&TerminalOptions::default())
+    }
+
+    /// Renders the `QRCode` as text for a terminal, with the charset, ANSI
+    /// colours and inversion chosen by `options`. The quiet zone is included,
+    /// and each line ends with `\n`.
     ///
-    /// # Returns
+    /// # Panics
     ///
-    /// A `String` representing the QR code in SVG format.
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_terminal`](Self::try_to_terminal) for a fallible
+    /// alternative.
     #[must_use]
-    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
-    pub fn to_svg(&self, width: u32) -> String {
-        let qrcode = self.to_qrcode();
+    pub fn to_terminal(&self, options: &TerminalOptions) -> String {
+        self.try_to_terminal(options).expect(ENCODE_FAILED)
+    }
 
-        if self.shape == ModuleShape::Square {
-            return qrcode
-                .render::<svg::Color>()
-                .min_dimensions(width, width)
-                .dark_color(svg::Color("#000000"))
-                .light_color(svg::Color("#FFFFFF"))
-                .build();
-        }
+    /// Fallible version of [`to_terminal`](Self::to_terminal).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`].
+    pub fn try_to_terminal(&self, options: &TerminalOptions) -> Result<String, QrcError> {
+        let qrcode = self.encode_for_render()?;
+        let light = (!self.style.transparent_background).then_some(self.style.light);
+        Ok(terminal::render(
+            self.total_modules(&qrcode),
+            |x, y| self.is_dark(&qrcode, x, y),
+            options,
+            self.style.dark,
+            light,
+        ))
+    }
 
-        // Custom SVG for non-square shapes
-        let qr_dim = qrcode.width();
-        let module_size = f64::from(width) / qr_dim as f64;
-        let mut elements = String::new();
+    /// The painted outlines shared by the vector renderers, at `module_size`
+    /// units per module with the origin at the outer edge of the quiet zone.
+    /// Finder patterns get their own layers when they carry eye shapes,
+    /// colours or gradients; empty layers are dropped.
+    #[allow(clippy::cast_precision_loss)]
+    fn vector_layers(&self, qrcode: &Symbol, module_size: f64) -> Vec<Layer> {
+        let quiet = self.quiet_zone as usize;
+        let mut layers = Vec::new();
+        let eyes_apart = self.eye_style.is_some()
+            || self.style.eye_gradient.is_some()
+            || self.style.eye_frame.is_some()
+            || self.style.eye_ball.is_some();
+        let fill = match &self.style.gradient {
+            Some(gradient) => Fill::Gradient {
+                name: "qrc-fill".to_string(),
+                gradient: gradient.clone(),
+                x: quiet as f64 * module_size,
+                y: quiet as f64 * module_size,
+                size: (
+                    qrcode.width() as f64 * module_size,
+                    qrcode.height() as f64 * module_size,
+                ),
+            },
+            None => Fill::Solid(self.style.dark),
+        };
+        layers.push(Layer {
+            path: self.module_outline(qrcode, module_size, |x, y| {
+                !(eyes_apart && self.finder_at(qrcode, x, y).is_some())
+            }),
+            fill: fill.clone(),
+            even_odd: false,
+        });
 
-        for y in 0..qr_dim {
-            for x in 0..qr_dim {
-                if qrcode[(x, y)] == Color::Dark {
-                    let px = x as f64 * module_size;
-                    let py = y as f64 * module_size;
-                    match self.shape {
-                        ModuleShape::Circle => {
-                            let cx = px + module_size / 2.0;
-                            let cy = py + module_size / 2.0;
-                            let r = module_size / 2.0;
-                            let _ = write!(
-                                elements,
-                                "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{r}\" fill=\"#000000\"/>"
-                            );
+        if eyes_apart {
+            for (i, eye) in qrcode.finders().enumerate() {
+                let (ex, ey) = ((eye.0 + quiet) as f64, (eye.1 + quiet) as f64);
+                let (px, py) = (ex * module_size, ey * module_size);
+                let gradient_fill =
+                    self.style
+                        .eye_gradient
+                        .as_ref()
+                        .map(|gradient| Fill::Gradient {
+                            name: format!("qrc-eye-{i}"),
+                            gradient: gradient.clone(),
+                            x: px,
+                            y: py,
+                            size: (
+                                art::FINDER_SIZE as f64 * module_size,
+                                art::FINDER_SIZE as f64 * module_size,
+                            ),
+                        });
+                for part in [EyePart::Frame, EyePart::Ball] {
+                    let flat = match part {
+                        EyePart::Frame => self.style.eye_frame,
+                        EyePart::Ball => self.style.eye_ball,
+                    };
+                    let part_fill = flat
+                        .map(Fill::Solid)
+                        .or_else(|| gradient_fill.clone())
+                        .unwrap_or_else(|| fill.clone());
+                    let layer = if let Some(eye_style) = self.eye_style {
+                        let corner = (eye.0 == 0, eye.1 == 0);
+                        Layer {
+                            path: eye::outline(eye_style, part, corner, px, py, module_size),
+                            fill: part_fill,
+                            even_odd: part == EyePart::Frame,
                         }
-                        ModuleShape::Diamond => {
-                            let half = module_size / 2.0;
-                            let top_x = px + half;
-                            let top_y = py;
-                            let right_x = px + module_size;
-                            let right_y = py + half;
-                            let bot_x = px + half;
-                            let bot_y = py + module_size;
-                            let left_x = px;
-                            let left_y = py + half;
-                            let _ = write!(elements,
-                                "<polygon points=\"{top_x},{top_y} {right_x},{right_y} {bot_x},{bot_y} {left_x},{left_y}\" fill=\"#000000\"/>"
-                            );
+                    } else {
+                        Layer {
+                            path: self.module_outline(qrcode, module_size, |x, y| {
+                                self.finder_at(qrcode, x, y) == Some(eye)
+                                    && eye::cell_part(x - quiet - eye.0, y - quiet - eye.1)
+                                        == Some(part)
+                            }),
+                            fill: part_fill,
+                            even_odd: false,
+                        }
+                    };
+                    layers.push(layer);
+                }
+            }
+        }
+        layers.retain(|layer| !layer.path.is_empty());
+        layers
+    }
+
+    /// Outline of the dark modules among the grid cells selected by `layer`,
+    /// in the current shape, at `module_size` units per module.
+    #[allow(clippy::cast_precision_loss)]
+    fn module_outline(
+        &self,
+        qrcode: &Symbol,
+        module_size: f64,
+        layer: impl Fn(usize, usize) -> bool,
+    ) -> Path {
+        let (cols, rows) = self.total_modules(qrcode);
+        let dark = |x: usize, y: usize| layer(x, y) && self.is_dark(qrcode, x, y);
+        let mut path = Path::default();
+        for y in 0..rows {
+            let py = y as f64 * module_size;
+            let mut x = 0;
+            while x < cols {
+                let px = x as f64 * module_size;
+                if self.shape.is_connected() {
+                    // Overlapping pieces merge under the nonzero fill rule.
+                    if layer(x, y) {
+                        let n = self.neighbours(qrcode, (x, y));
+                        for piece in shape::pieces(self.shape, self.is_dark(qrcode, x, y), n) {
+                            piece.trace(&mut path, px, py, module_size);
                         }
-                        // RoundedSquare; `Square` is handled by the early return above.
-                        _ => {
-                            let r = module_size * 0.3;
-                            let _ = write!(elements,
-                                "<rect x=\"{px}\" y=\"{py}\" width=\"{module_size}\" height=\"{module_size}\" rx=\"{r}\" ry=\"{r}\" fill=\"#000000\"/>"
-                            );
+                    }
+                    x += 1;
+                    continue;
+                }
+                if !dark(x, y) {
+                    x += 1;
+                    continue;
+                }
+                match self.shape {
+                    ModuleShape::Circle => {
+                        let half = module_size / 2.0;
+                        path.circle(px + half, py + half, half);
+                    }
+                    ModuleShape::Diamond => {
+                        let half = module_size / 2.0;
+                        path.move_to(px + half, py);
+                        path.line_to(px + module_size, py + half);
+                        path.line_to(px + half, py + module_size);
+                        path.line_to(px, py + half);
+                        path.close();
+                    }
+                    ModuleShape::RoundedSquare => {
+                        let r = module_size * 0.3;
+                        path.rounded_rect(px, py, module_size, module_size, [r; 4]);
+                    }
+                    _ => {
+                        // Horizontal runs of squares become one rectangle.
+                        let start = x;
+                        while x + 1 < cols && dark(x + 1, y) {
+                            x += 1;
                         }
+                        let run = (x + 1 - start) as f64 * module_size;
+                        path.rounded_rect(px, py, run, module_size, [0.0; 4]);
                     }
                 }
+                x += 1;
             }
         }
+        path
+    }
 
-        format!(
-            "<?xml version=\"1.0\" standalone=\"yes\"?><svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"{width}\" height=\"{width}\"><rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>{elements}</svg>"
-        )
+    /// Symbol-space origin of the finder pattern covering grid cell
+    /// (`x`, `y`), if any.
+    fn finder_at(&self, qrcode: &Symbol, x: usize, y: usize) -> Option<(usize, usize)> {
+        let quiet = self.quiet_zone as usize;
+        let (x, y) = (x.checked_sub(quiet)?, y.checked_sub(quiet)?);
+        art::finder_origin(x, y, qrcode)
     }
 
     /// Colorizes the QR code with the specified color.
     ///
+    /// The image is rendered at one pixel per module, including the quiet
+    /// zone. `color` replaces the style's dark colour and gradients; the light
+    /// colour and transparency still come from [`style`](Self::style).
+    ///
     /// # Parameters
     ///
     /// * `color`: The `Rgba<u8>` color value to use for the QR code.
@@ -481,24 +1878,34 @@ impl QRCode {
     /// # Returns
     ///
     /// A colorized `RgbaImage` of the QR code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_colorize`](Self::try_colorize) for a fallible alternative.
     #[must_use]
-    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
     pub fn colorize(&self, color: Rgba<u8>) -> RgbaImage {
-        let qrcode = self.to_qrcode();
-        let qr_dim = qrcode.width() as u32;
-        let module_size = 1.0; // 1:1 mapping at native resolution
-        let mut img: RgbaImage =
-            ImageBuffer::from_pixel(qr_dim, qr_dim, Rgba([255, 255, 255, 255]));
-        for (x, y, pixel) in img.enumerate_pixels_mut() {
-            if qrcode[(x as usize, y as usize)] == Color::Dark {
-                let mod_x = f64::from(x) - f64::from(x) * module_size / module_size;
-                let mod_y = f64::from(y) - f64::from(y) * module_size / module_size;
-                if self.is_inside_shape(mod_x, mod_y, module_size) {
-                    *pixel = color;
-                }
-            }
-        }
-        img
+        self.try_colorize(color).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`colorize`](Self::colorize).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if `color` does not contrast
+    /// enough with the light colour.
+    pub fn try_colorize(&self, color: Rgba<u8>) -> Result<RgbaImage, QrcError> {
+        let colored = Self {
+            style: QrStyle {
+                dark: color,
+                gradient: None,
+                eye_gradient: None,
+                ..self.style.clone()
+            },
+            ..self.clone()
+        };
+        colored.try_to_image_scaled(1)
     }
 
     /// Resizes the QR code image to the specified width and height.
@@ -511,34 +1918,50 @@ impl QRCode {
     /// # Returns
     ///
     /// A resized `RgbaImage` of the QR code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_resize`](Self::try_resize) for a fallible alternative.
     #[must_use]
+    pub fn resize(&self, width: u32, height: u32) -> RgbaImage {
+        self.try_resize(width, height).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`resize`](Self::resize).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`] or the image would exceed
+    /// [`MAX_RASTER_BYTES`].
     #[allow(
         clippy::cast_precision_loss,
         clippy::cast_possible_truncation,
         clippy::cast_sign_loss
     )]
-    pub fn resize(&self, width: u32, height: u32) -> RgbaImage {
-        let qrcode = self.to_qrcode();
-        let qr_width = qrcode.width() as f64;
+    pub fn try_resize(&self, width: u32, height: u32) -> Result<RgbaImage, QrcError> {
+        let qrcode = self.encode_for_render()?;
+        let (cols, rows) = self.total_modules(&qrcode);
+        let (qr_width, qr_height) = (cols as f64, rows as f64);
         let module_size_x = f64::from(width) / qr_width;
-        let module_size_y = f64::from(height) / qr_width;
-        let mut img: RgbaImage = ImageBuffer::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+        let module_size_y = f64::from(height) / qr_height;
+        let mut img = canvas(width, height, self.style.background())?;
         for y in 0..height {
             for x in 0..width {
                 let x_index = (f64::from(x) / f64::from(width) * qr_width) as usize;
-                let y_index = (f64::from(y) / f64::from(height) * qr_width) as usize;
-                if qrcode[(x_index, y_index)] == Color::Dark {
-                    let mod_x = f64::from(x) - (x_index as f64) * module_size_x;
-                    let mod_y = f64::from(y) - (y_index as f64) * module_size_y;
-                    // Use average module size for shape check
-                    let avg_mod = (module_size_x + module_size_y) / 2.0;
-                    if self.is_inside_shape(mod_x, mod_y, avg_mod) {
-                        img.put_pixel(x, y, Rgba([0, 0, 0, 255]));
-                    }
+                let y_index = (f64::from(y) / f64::from(height) * qr_height) as usize;
+                let mod_x = f64::from(x) - (x_index as f64) * module_size_x;
+                let mod_y = f64::from(y) - (y_index as f64) * module_size_y;
+                let cell = (x_index, y_index);
+                let module = (module_size_x, module_size_y);
+                if let Some(color) = self.paint(&qrcode, cell, (mod_x, mod_y), module) {
+                    img.put_pixel(x, y, color);
                 }
             }
         }
-        img
+        Ok(img)
     }
 
     /// Adds a watermark image to the QR code.
@@ -622,41 +2045,127 @@ impl QRCode {
         Self::from_string(dynamic_url)
     }
 
+    /// Splits the data across the fewest linked symbols that hold it, up to
+    /// [`MAX_SYMBOLS`], with Structured Append headers.
+    ///
+    /// Each symbol keeps every other option of this code; the
+    /// [`version`](Self::version) bounds each symbol's size, so
+    /// `SymbolVersion::Fixed(10)` splits the data into version 10 symbols.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data does not fit in 16 symbols.
+    /// Use [`try_to_structured_append`](Self::try_to_structured_append) for a
+    /// fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{QRCode, SymbolVersion};
+    ///
+    /// let qr = QRCode::from_string("x".repeat(100)).with_version(SymbolVersion::Fixed(2));
+    /// let parts = qr.to_structured_append();
+    /// // Version 2 at level M holds 28 data codewords: 24 bytes after the
+    /// // headers, so 100 bytes need five symbols.
+    /// assert_eq!(parts.codes().len(), 5);
+    /// ```
+    #[must_use]
+    pub fn to_structured_append(&self) -> StructuredAppend {
+        self.try_to_structured_append().expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of
+    /// [`to_structured_append`](Self::to_structured_append).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in
+    /// 16 symbols, with the shortfall of the fullest, or
+    /// [`QrcError::InvalidOption`] if the code is not a standard QR code or
+    /// another option is invalid.
+    pub fn try_to_structured_append(&self) -> Result<StructuredAppend, QrcError> {
+        structured::split(self, None)
+    }
+
+    /// Splits the data evenly across `count` linked symbols, 1 to
+    /// [`MAX_SYMBOLS`], with Structured Append headers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the split is invalid or a part does not fit in a symbol.
+    /// Use [`try_split_into`](Self::try_split_into) for a fallible
+    /// alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::QRCode;
+    ///
+    /// let parts = QRCode::from_string("ABCDEFGH".to_string()).split_into(3);
+    /// let sizes: Vec<usize> = parts.codes().iter().map(|code| code.data.len()).collect();
+    /// assert_eq!(sizes, [3, 3, 2]);
+    /// ```
+    #[must_use]
+    pub fn split_into(&self, count: u8) -> StructuredAppend {
+        self.try_split_into(count).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`split_into`](Self::split_into).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidOption`] if `count` is outside 1–16 or
+    /// exceeds the data length, or the code is not a standard QR code, and
+    /// [`QrcError::CapacityExceeded`] if a part does not fit in a symbol.
+    pub fn try_split_into(&self, count: u8) -> Result<StructuredAppend, QrcError> {
+        structured::split(self, Some(count))
+    }
+
     /// Combines multiple QR codes into a single larger QR code.
     ///
+    /// The result is a raw pixel buffer in [`data`](Self::data), not a
+    /// scannable code.
+    ///
     /// # Parameters
     ///
     /// * `codes`: A vector of `QRCode` instances to combine.
     ///
     /// # Returns
     ///
-    /// A `Result` which is either a combined `QRCode` instance or an error string.
+    /// A `Result` which is either a combined `QRCode` instance or an error.
     ///
     /// # Errors
     ///
-    /// Returns an error if `codes` is empty.
+    /// Returns [`QrcError::InvalidOption`] if `codes` is empty, or
+    /// [`QrcError::CapacityExceeded`] if any of the codes cannot be encoded.
+    #[deprecated(
+        since = "0.0.7",
+        note = "the result does not scan; use `to_structured_append` or `split_into` to spread data across linked symbols"
+    )]
     #[allow(clippy::cast_possible_truncation)]
-    pub fn combine_qr_codes(codes: &[Self]) -> Result<Self, &'static str> {
+    pub fn combine_qr_codes(codes: &[Self]) -> Result<Self, QrcError> {
         if codes.is_empty() {
-            return Err("No QR codes to combine");
+            return Err(QrcError::InvalidOption(
+                "no QR codes to combine".to_string(),
+            ));
         }
 
-        let total_width: u32 = codes
+        let symbols = codes
             .iter()
-            .map(|code| code.to_qrcode().width() as u32)
-            .sum();
+            .map(Self::try_to_qrcode)
+            .collect::<Result<Vec<_>, _>>()?;
+        let total_width: u32 = symbols.iter().map(|code| code.width() as u32).sum();
 
         let mut combined_image: RgbaImage =
             ImageBuffer::from_pixel(total_width, total_width, Rgba([255, 255, 255, 255]));
 
         let mut x_offset: u32 = 0;
 
-        for code in codes {
-            let qrcode = code.to_qrcode();
+        for qrcode in &symbols {
             let width = qrcode.width() as u32;
 
             for x in 0..width {
-                for y in 0..width {
+                for y in 0..qrcode.height() as u32 {
                     let pixel = qrcode[(x as usize, y as usize)];
                     let combined_x = x + x_offset;
 
@@ -705,11 +2214,13 @@ impl QRCode {
 
     /// Overlays an image at the **centre** of the QR code (e.g. a logo).
     ///
-    /// The code is rendered at a usable scale with the mandatory 4-module quiet
-    /// zone, and the overlay is centred (not pasted at the top-left corner, which
+    /// The code is rendered at a usable scale with the configured
+    /// [`quiet_zone`](Self::quiet_zone), and the overlay is centred (not pasted at the top-left corner, which
     /// previously covered a finder pattern). Fully-transparent overlay pixels are
     /// skipped. Keep the overlay small (≈ the central fifth) and pair it with a
-    /// high error-correction level so the result stays scannable.
+    /// high error-correction level so the result stays scannable, or use
+    /// [`to_image_with_logo`](Self::to_image_with_logo), which sizes the logo
+    /// to the correction budget.
     ///
     /// # Parameters
     ///
@@ -718,26 +2229,45 @@ impl QRCode {
     /// # Returns
     ///
     /// A combined `RgbaImage` with the overlay centred on the code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_overlay_image`](Self::try_overlay_image) for a fallible
+    /// alternative.
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn overlay_image(&self, overlay: &RgbaImage) -> RgbaImage {
+        self.try_overlay_image(overlay).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`overlay_image`](Self::overlay_image).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`], the quiet zone exceeds
+    /// [`MAX_QUIET_ZONE`], or the image would exceed [`MAX_RASTER_BYTES`].
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn try_overlay_image(&self, overlay: &RgbaImage) -> Result<RgbaImage, QrcError> {
         const MODULE_PX: u32 = 10;
-        const QUIET: u32 = 4;
 
-        let qrcode = self.to_qrcode();
-        let n = qrcode.width() as u32;
-        let dim = (n + 2 * QUIET) * MODULE_PX;
-        let mut combined_image: RgbaImage =
-            ImageBuffer::from_pixel(dim, dim, Rgba([255, 255, 255, 255]));
+        let qrcode = self.encode_for_render()?;
+        let quiet = self.quiet_zone;
+        let (dim_x, dim_y) = self.grid_pixels(&qrcode, MODULE_PX)?;
+        let mut combined_image = canvas(dim_x, dim_y, self.style.background())?;
 
-        for y in 0..qrcode.width() {
+        for y in 0..qrcode.height() {
             for x in 0..qrcode.width() {
                 if qrcode[(x, y)] == Color::Dark {
-                    let px0 = (x as u32 + QUIET) * MODULE_PX;
-                    let py0 = (y as u32 + QUIET) * MODULE_PX;
+                    let px0 = (x as u32 + quiet) * MODULE_PX;
+                    let py0 = (y as u32 + quiet) * MODULE_PX;
                     for dy in 0..MODULE_PX {
                         for dx in 0..MODULE_PX {
-                            combined_image.put_pixel(px0 + dx, py0 + dy, Rgba([0, 0, 0, 255]));
+                            let fx = f64::from(px0 + dx) / f64::from(MODULE_PX);
+                            let fy = f64::from(py0 + dy) / f64::from(MODULE_PX);
+                            let color = self.dark_color(&qrcode, fx, fy);
+                            combined_image.put_pixel(px0 + dx, py0 + dy, color);
                         }
                     }
                 }
@@ -747,26 +2277,121 @@ impl QRCode {
         // Centre the overlay, skipping fully-transparent pixels and clamping to
         // the canvas so an oversized logo can't panic.
         let (ow, oh) = overlay.dimensions();
-        let ox = dim.saturating_sub(ow) / 2;
-        let oy = dim.saturating_sub(oh) / 2;
+        let ox = dim_x.saturating_sub(ow) / 2;
+        let oy = dim_y.saturating_sub(oh) / 2;
         for (x, y, pixel) in overlay.enumerate_pixels() {
             if pixel[3] == 0 {
                 continue;
             }
             let (px, py) = (ox + x, oy + y);
-            if px < dim && py < dim {
+            if px < dim_x && py < dim_y {
                 combined_image.put_pixel(px, py, *pixel);
             }
         }
 
-        combined_image
+        Ok(combined_image)
+    }
+
+    /// Renders the code with `logo` centred on it, scaled to the largest size
+    /// the error-correction budget allows or to
+    /// [`LogoOptions::size`](LogoOptions::size).
+    ///
+    /// The logo keeps its aspect ratio and stays clear of the finder, timing,
+    /// alignment, format and version patterns, so versions with an alignment
+    /// pattern at the centre (7 to 13, 21 to 27 and 35 to 40) cannot carry
+    /// one. Modules are drawn with the configured
+    /// [`shape`](Self::shape), [`eye_style`](Self::eye_style) and
+    /// [`style`](Self::style).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded or the logo does not fit. Use
+    /// [`try_to_image_with_logo`](Self::try_to_image_with_logo) for a
+    /// fallible alternative.
+    #[must_use]
+    pub fn to_image_with_logo(&self, logo: &RgbaImage, opts: &LogoOptions) -> RgbaImage {
+        self.try_to_image_with_logo(logo, opts)
+            .expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_image_with_logo`](Self::to_image_with_logo).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the
+    /// [`style`](Self::style) fails [`QrStyle::validate`], an option is out
+    /// of range, the logo is empty, the requested size exceeds the budget,
+    /// or the image would be too large.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn try_to_image_with_logo(
+        &self,
+        logo: &RgbaImage,
+        opts: &LogoOptions,
+    ) -> Result<RgbaImage, QrcError> {
+        let qrcode = self.encode_for_render()?;
+        let placement = logo::place(&qrcode, logo.dimensions(), opts)?;
+        let module_px = opts.module_px;
+        let size = self.grid_pixels(&qrcode, module_px)?;
+        let mut img = self.render_aligned(&qrcode, module_px, size)?;
+
+        let [x0, x1, y0, y1] = placement.cells;
+        let quiet = self.quiet_zone as usize;
+        let px = |modules: f64| ((modules + quiet as f64) * f64::from(module_px)).round();
+        let cell_px = |cell: usize| (cell + quiet) as u32 * module_px;
+        if opts.plate {
+            for y in cell_px(y0)..cell_px(y1) {
+                for x in cell_px(x0)..cell_px(x1) {
+                    img.put_pixel(x, y, self.style.background());
+                }
+            }
+        }
+        let [left, top, w, h] = placement.logo;
+        let (lx, ly) = (px(left), px(top));
+        let (lw, lh) = (px(left + w) - lx, px(top + h) - ly);
+        let scaled = imageops::resize(
+            logo,
+            (lw as u32).max(1),
+            (lh as u32).max(1),
+            imageops::FilterType::Triangle,
+        );
+        imageops::overlay(&mut img, &scaled, lx as i64, ly as i64);
+        Ok(img)
+    }
+
+    /// The largest square logo the code can carry under `opts`, as a share
+    /// of the symbol width (quiet zone excluded), ignoring
+    /// [`LogoOptions::size`](LogoOptions::size).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if an option is out of range
+    /// or no logo fits at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{EcLevel, LogoOptions, QRCode};
+    ///
+    /// let qr = QRCode::from_string("https://example.com".to_string());
+    /// let low = qr.clone().with_ec_level(EcLevel::L).max_logo_size(&LogoOptions::default());
+    /// let high = qr.with_ec_level(EcLevel::H).max_logo_size(&LogoOptions::default());
+    /// assert!(high.unwrap() > low.unwrap_or(0.0));
+    /// ```
+    pub fn max_logo_size(&self, opts: &LogoOptions) -> Result<f64, QrcError> {
+        logo::max_size(&self.try_to_qrcode()?, opts)
     }
 
     /// Exports a square, high-contrast control image for a Stable Diffusion QR
     /// ControlNet (e.g. *QR Code Monster*).
     ///
     /// The modules are integer-scaled and centred on a `size`×`size` canvas with
-    /// a 4-module quiet zone; if they cannot fit exactly, the canvas grows to the
+    /// the configured [`quiet_zone`](Self::quiet_zone); if they cannot fit exactly, the canvas grows to the
     /// next whole module rather than distorting them. Pair with
     /// [`with_ec_level`](Self::with_ec_level) + `EcLevel::H` so the model has the
     /// most redundancy to hide art behind.
@@ -779,9 +2404,32 @@ impl QRCode {
     ///     .to_control_image(768);
     /// assert!(img.width() >= 768 && img.width() == img.height());
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_to_control_image`](Self::try_to_control_image) for a
+    /// fallible alternative.
     #[must_use]
     pub fn to_control_image(&self, size: u32) -> RgbaImage {
-        art::control_image(&self.to_qrcode(), size)
+        self.try_to_control_image(size).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_control_image`](Self::to_control_image).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`], the quiet zone exceeds
+    /// [`MAX_QUIET_ZONE`], or the image would exceed [`MAX_RASTER_BYTES`].
+    pub fn try_to_control_image(&self, size: u32) -> Result<RgbaImage, QrcError> {
+        art::control_image(
+            &self.encode_for_render()?,
+            size,
+            self.quiet_zone,
+            &self.style,
+        )
     }
 
     /// Weaves a `background` image through the code's data modules, returning a
@@ -802,12 +2450,86 @@ impl QRCode {
     ///     .blend_image(&bg, &BlendOptions::default());
     /// assert_eq!(art.width(), art.height());
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data cannot be encoded as a valid QR code.
+    /// Use [`try_blend_image`](Self::try_blend_image) for a fallible
+    /// alternative.
     #[must_use]
     pub fn blend_image(&self, background: &RgbaImage, opts: &BlendOptions) -> RgbaImage {
-        art::blend(&self.to_qrcode(), background, opts)
+        self.try_blend_image(background, opts).expect(ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`blend_image`](Self::blend_image).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if the [`style`](Self::style)
+    /// fails [`QrStyle::validate`], the quiet zone exceeds
+    /// [`MAX_QUIET_ZONE`], or the image would exceed [`MAX_RASTER_BYTES`].
+    pub fn try_blend_image(
+        &self,
+        background: &RgbaImage,
+        opts: &BlendOptions,
+    ) -> Result<RgbaImage, QrcError> {
+        let qrcode = self.encode_for_render()?;
+        let (width, height) = self.grid_pixels(&qrcode, opts.module_size.max(1))?;
+        check_raster(width, height)?;
+        Ok(art::blend(
+            &qrcode,
+            background,
+            opts,
+            self.quiet_zone,
+            &self.style,
+        ))
+    }
+
+    /// Grades `img`, a rendering of this code, module by module: samples it
+    /// back onto the module grid, marks modules that read as the wrong colour
+    /// or nearly so, and checks each Reed-Solomon block against its
+    /// correction budget.
+    ///
+    /// The image should show the whole code, quiet zone included, as the
+    /// renderers, [`blend_image`](Self::blend_image) and
+    /// [`overlay_image`](Self::overlay_image) produce it; the grid is aligned
+    /// through the finder patterns when the code does not fill the image.
+    /// Each module is read as the average of its middle, as a camera at a
+    /// distance sees it, which makes the check stricter than
+    /// [`decode`](decode::decode) on a sharp image.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::CapacityExceeded`] if the data does not fit in a
+    /// QR code, or [`QrcError::InvalidOption`] if `img` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{BlendOptions, QRCode};
+    /// use image::{Rgba, RgbaImage};
+    ///
+    /// let qr = QRCode::from_string("https://example.com".to_string());
+    /// let background = RgbaImage::from_pixel(64, 64, Rgba([90, 140, 200, 255]));
+    /// let art = qr.blend_image(&background, &BlendOptions::default());
+    /// let report = qr.scannability(&art).unwrap();
+    /// assert!(report.passed);
+    /// let heatmap = report.heatmap(4);
+    /// assert_eq!(heatmap.width(), report.size() as u32 * 4);
+    /// ```
+    pub fn scannability(&self, img: &RgbaImage) -> Result<ScanReport, QrcError> {
+        scannability::analyse(&self.encode_with_border()?, self.quiet_zone as usize, img)
     }
 
-    /// Sets the encoding format of the QR code.
+    /// Sets the encoding format of the QR code: transcodes the data into the
+    /// character set `format` names and announces it with an ECI designator.
+    ///
+    /// `format` is an IANA name or alias, ignoring case, as accepted by
+    /// [`CharacterSet::from_label`]: `"iso-8859-7"`, `"latin2"`,
+    /// `"shift_jis"`, `"utf-16be"`, `"utf-8"` and so on. The encoding format
+    /// then reads back as the set's canonical [`name`](CharacterSet::name),
+    /// so `"latin1"` becomes `"ISO-8859-1"`.
     ///
     /// # Parameters
     ///
@@ -815,21 +2537,65 @@ impl QRCode {
     ///
     /// # Returns
     ///
-    /// A `Result` which is either a new `QRCode` instance with updated encoding or an error string.
+    /// A `Result` which is either a new `QRCode` instance with updated encoding or an error.
     ///
     /// # Errors
     ///
-    /// Returns an error if the encoding format is not `"utf-8"`.
-    pub fn set_encoding_format(&self, format: &str) -> Result<Self, &'static str> {
-        if format != "utf-8" {
-            return Err("Unsupported encoding format");
-        }
+    /// Returns [`QrcError::InvalidOption`] if the encoding format is not a
+    /// supported character set, or the data cannot be transcoded into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{CharacterSet, QRCode};
+    ///
+    /// let qr = QRCode::from_string("Ciao, città".to_string())
+    ///     .set_encoding_format("ISO-8859-1")
+    ///     .unwrap();
+    /// assert_eq!(qr.character_set, Some(CharacterSet::Iso8859_1));
+    /// assert_eq!(qr.get_encoding_format(), "ISO-8859-1");
+    /// assert_eq!(qr.data, b"Ciao, citt\xe0");
+    /// ```
+    pub fn set_encoding_format(&self, format: &str) -> Result<Self, QrcError> {
+        let charset = CharacterSet::from_label(format).ok_or_else(|| {
+            QrcError::InvalidOption(format!("unsupported encoding format `{format}`"))
+        })?;
+        self.set_character_set(charset)
+    }
 
+    /// Transcodes the data into `charset` and announces it with an ECI
+    /// designator. The data is read as text in the current
+    /// [`character_set`](Self::character_set), or as UTF-8 if none is set.
+    ///
+    /// ECI designators are only defined for standard QR codes; Micro QR and
+    /// rMQR codes with a character set fail to encode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidOption`] if the data is not valid text in
+    /// its current character set, or has a character `charset` lacks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{CharacterSet, QRCode};
+    ///
+    /// let qr = QRCode::from_string("Привет".to_string())
+    ///     .set_character_set(CharacterSet::Iso8859_5)
+    ///     .unwrap();
+    /// assert_eq!(qr.data.len(), 6);
+    /// assert_eq!(qr.get_encoding_format(), "ISO-8859-5");
+    /// ```
+    pub fn set_character_set(&self, charset: CharacterSet) -> Result<Self, QrcError> {
+        let current = self.character_set.unwrap_or(CharacterSet::Utf8);
+        let text = current.decode(&self.data).ok_or_else(|| {
+            QrcError::InvalidOption(format!("the data is not valid {} text", current.name()))
+        })?;
         Ok(Self {
-            data: self.data.clone(),
-            encoding_format: format.to_string(),
-            ec_level: self.ec_level,
-            shape: self.shape,
+            data: charset.encode(&text)?,
+            encoding_format: charset.name().to_string(),
+            character_set: Some(charset),
+            ..self.clone()
         })
     }
 
@@ -844,6 +2610,31 @@ impl QRCode {
     }
 }
 
+/// Checks that a `width`×`height` RGBA image fits in [`MAX_RASTER_BYTES`].
+pub(crate) fn check_raster(width: u32, height: u32) -> Result<(), QrcError> {
+    let bytes = (u64::from(width) * u64::from(height)).checked_mul(4);
+    if bytes.map_or(true, |bytes| bytes > MAX_RASTER_BYTES) {
+        return Err(QrcError::InvalidOption(format!(
+            "a {width}×{height} px image exceeds the {MAX_RASTER_BYTES}-byte raster limit"
+        )));
+    }
+    Ok(())
+}
+
+/// A `width`×`height` image filled with `pixel`, once [`check_raster`]
+/// allows it.
+pub(crate) fn canvas(width: u32, height: u32, pixel: Rgba<u8>) -> Result<RgbaImage, QrcError> {
+    check_raster(width, height)?;
+    Ok(ImageBuffer::from_pixel(width, height, pixel))
+}
+
+/// Height, in whole pixels, of an image `width` pixels wide showing a
+/// `cols`×`rows` module grid.
+fn proportional_height(width: u32, cols: usize, rows: usize) -> u32 {
+    let (cols, rows) = (cols as u64, rows as u64);
+    u32::try_from((u64::from(width) * rows + cols / 2) / cols).unwrap_or(u32::MAX)
+}
+
 /// Checks whether a point (x, y) is inside a rounded rectangle of the
 /// given dimensions and corner radius.
 #[allow(clippy::many_single_char_names)]
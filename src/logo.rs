@@ -0,0 +1,293 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Logo placement within the error-correction budget.
+//!
+//! A logo hides every module beneath it, and a scanner only recovers them if
+//! no Reed-Solomon block loses more codewords than it can correct. The
+//! placement here is centred, stays clear of the finder, timing, alignment,
+//! format and version patterns, and is sized by counting the codewords it
+//! damages in each block, so the largest logo that fits can be computed
+//! rather than guessed. From version 7 to 13 (and 21 to 27, 35 to 40) an
+//! alignment pattern sits at the very centre, so no logo fits there.
+
+use crate::decode::{alignment_positions, data_modules, Blocks};
+use crate::QrcError;
+use crate::{Symbol, SymbolType};
+use qrcode::Version;
+
+/// Modules kept clear along each edge of the symbol: the finder patterns,
+/// their separators and the format information around them.
+const EDGE: usize = 9;
+
+/// Step, in modules, between logo sizes tried by the budget search.
+const STEP: f64 = 0.25;
+
+/// Options for [`QRCode::to_image_with_logo`](crate::QRCode::to_image_with_logo).
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{EcLevel, LogoOptions, QRCode};
+/// use image::{Rgba, RgbaImage};
+///
+/// let qr = QRCode::from_string("https://example.com".to_string()).with_ec_level(EcLevel::H);
+/// let logo = RgbaImage::from_pixel(64, 32, Rgba([200, 0, 0, 255]));
+/// let img = qr.to_image_with_logo(&logo, &LogoOptions::default().with_padding(0));
+/// assert_eq!(img.width(), (29 + 2 * 4) * 10);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct LogoOptions {
+    /// Longer side of the logo as a share of the symbol width, quiet zone
+    /// excluded, in `0.0..=1.0`; `None` picks the largest size the budget
+    /// allows.
+    pub size: Option<f64>,
+    /// Share of each block's correction capacity the logo may use, in
+    /// `0.0..=1.0`. The rest is left for print defects and camera noise.
+    pub budget: f64,
+    /// Clear the modules behind the logo to the light colour, so a logo
+    /// with transparency does not sit on half-visible modules.
+    pub plate: bool,
+    /// Whole modules of plate around the logo on every side.
+    pub padding: u32,
+    /// Pixels per module in the output image.
+    pub module_px: u32,
+}
+
+impl Default for LogoOptions {
+    fn default() -> Self {
+        LogoOptions {
+            size: None,
+            budget: 0.5,
+            plate: true,
+            padding: 1,
+            module_px: 10,
+        }
+    }
+}
+
+impl LogoOptions {
+    /// Sets [`size`](Self::size) (builder pattern).
+    #[must_use]
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets [`budget`](Self::budget) (builder pattern).
+    #[must_use]
+    pub fn with_budget(mut self, budget: f64) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Sets [`plate`](Self::plate) (builder pattern).
+    #[must_use]
+    pub fn with_plate(mut self, plate: bool) -> Self {
+        self.plate = plate;
+        self
+    }
+
+    /// Sets [`padding`](Self::padding) (builder pattern).
+    #[must_use]
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets [`module_px`](Self::module_px) (builder pattern).
+    #[must_use]
+    pub fn with_module_px(mut self, module_px: u32) -> Self {
+        self.module_px = module_px;
+        self
+    }
+
+    /// Rejects out-of-range options.
+    fn validate(&self) -> Result<(), QrcError> {
+        let share = |value: f64| value > 0.0 && value <= 1.0;
+        if !share(self.budget) {
+            return Err(QrcError::InvalidOption(format!(
+                "logo budget must be in (0, 1], got {}",
+                self.budget
+            )));
+        }
+        if let Some(size) = self.size.filter(|&size| !share(size)) {
+            return Err(QrcError::InvalidOption(format!(
+                "logo size must be in (0, 1], got {size}"
+            )));
+        }
+        if self.module_px == 0 {
+            return Err(QrcError::InvalidOption(
+                "pixels per module must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Where a logo goes on the symbol, in module coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Placement {
+    /// Left, top, width and height of the logo.
+    pub(crate) logo: [f64; 4],
+    /// The modules hidden by the logo or its plate: `x0..x1` by `y0..y1`.
+    pub(crate) cells: [usize; 4],
+}
+
+/// The symbol's version, rejecting the Micro QR and rMQR symbols a centred
+/// logo cannot fit in.
+fn normal_version(code: &Symbol) -> Result<usize, QrcError> {
+    match (code.symbol_type(), code.version()) {
+        (SymbolType::Qr, Version::Normal(version)) => Ok(usize::try_from(version).unwrap_or(1)),
+        _ => Err(QrcError::InvalidOption(
+            "logos need a standard QR code".to_string(),
+        )),
+    }
+}
+
+/// The placement of a logo whose longer side spans `side` modules and whose
+/// shorter side is `aspect` times that, or `None` when it would cover a
+/// finder, timing, alignment, format or version pattern or damage more
+/// codewords than `allowed` in some block. `alignment` lists the alignment
+/// pattern centres along either axis.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn fit(
+    code: &Symbol,
+    data: &[(usize, usize)],
+    alignment: &[usize],
+    interleaving: &[usize],
+    (blocks, allowed): (usize, usize),
+    (side, aspect, landscape): (f64, f64, bool),
+    opts: &LogoOptions,
+) -> Option<Placement> {
+    let n = code.width();
+    let (w, h) = if landscape {
+        (side, side * aspect)
+    } else {
+        (side * aspect, side)
+    };
+    let centre = n as f64 / 2.0;
+    let (left, top) = (centre - w / 2.0, centre - h / 2.0);
+    let padding = if opts.plate { opts.padding as usize } else { 0 };
+    let x0 = (left.floor() as usize).checked_sub(padding)?;
+    let y0 = (top.floor() as usize).checked_sub(padding)?;
+    let x1 = (left + w).ceil() as usize + padding;
+    let y1 = (top + h).ceil() as usize + padding;
+    if x0 < EDGE || y0 < EDGE || x1 > n - EDGE || y1 > n - EDGE {
+        return None;
+    }
+    // Each alignment pattern spans the 5×5 modules around its centre; the
+    // ones that would overlap a finder lie inside the edge band.
+    let covers = |c: usize, lo: usize, hi: usize| c + 3 > lo && c - 2 < hi;
+    if alignment.iter().any(|&cx| covers(cx, x0, x1))
+        && alignment.iter().any(|&cy| covers(cy, y0, y1))
+    {
+        return None;
+    }
+    let mut damaged = vec![0; blocks];
+    for (codeword, &b) in data.chunks_exact(8).zip(interleaving) {
+        if codeword
+            .iter()
+            .any(|&(x, y)| (x0..x1).contains(&x) && (y0..y1).contains(&y))
+        {
+            damaged[b] += 1;
+        }
+    }
+    damaged
+        .iter()
+        .all(|&count| count <= allowed)
+        .then_some(Placement {
+            logo: [left, top, w, h],
+            cells: [x0, x1, y0, y1],
+        })
+}
+
+/// Places a `width`×`height` pixel logo on `code`: at the requested
+/// [`size`](LogoOptions::size), or as large as the budget allows.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub(crate) fn place(
+    code: &Symbol,
+    (width, height): (u32, u32),
+    opts: &LogoOptions,
+) -> Result<Placement, QrcError> {
+    opts.validate()?;
+    if width == 0 || height == 0 {
+        return Err(QrcError::InvalidOption("the logo is empty".to_string()));
+    }
+    let version = normal_version(code)?;
+    let level = code.error_correction_level();
+    let layout = Blocks::new(version, level);
+    let allowed = (opts.budget * layout.correctable() as f64 + 1e-9).floor() as usize;
+    let data = data_modules(version);
+    let alignment = alignment_positions(version);
+    let interleaving = layout.interleaving();
+    let shape = (
+        f64::from(width.min(height)) / f64::from(width.max(height)),
+        width >= height,
+    );
+    let at = |side: f64| {
+        fit(
+            code,
+            &data,
+            &alignment,
+            &interleaving,
+            (layout.count, allowed),
+            (side, shape.0, shape.1),
+            opts,
+        )
+    };
+
+    let largest = || {
+        let mut best = None;
+        let mut side = STEP;
+        while let Some(placement) = at(side) {
+            best = Some(placement);
+            side += STEP;
+        }
+        best
+    };
+    let n = code.width() as f64;
+    let too_large = |requested: Option<f64>, best: Option<Placement>| {
+        let room = best.map_or_else(
+            || "no logo fits".to_string(),
+            |placement| {
+                let side = placement.logo[2].max(placement.logo[3]);
+                format!("at most {:.1}% fits", side / n * 100.0)
+            },
+        );
+        let requested = requested.map_or_else(String::new, |size| {
+            format!(
+                "a logo {:.1}% of the symbol wide is too large: ",
+                size * 100.0
+            )
+        });
+        QrcError::InvalidOption(format!(
+            "{requested}{room} at version {version} with EcLevel::{level:?} and a {:.0}% budget",
+            opts.budget * 100.0
+        ))
+    };
+    match opts.size {
+        Some(size) => at(size * n).ok_or_else(|| too_large(Some(size), largest())),
+        None => largest().ok_or_else(|| too_large(None, None)),
+    }
+}
+
+/// The largest square logo `code` can carry under `opts`, as a share of the
+/// symbol width.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn max_size(code: &Symbol, opts: &LogoOptions) -> Result<f64, QrcError> {
+    let square = LogoOptions {
+        size: None,
+        ..*opts
+    };
+    let placement = place(code, (1, 1), &square)?;
+    Ok(placement.logo[2] / code.width() as f64)
+}
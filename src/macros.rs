@@ -65,8 +65,8 @@ macro_rules! qr_code_with_ec {
 #[macro_export]
 /// Macro to create a QR code in a specified format with a given width.
 ///
-/// All arms return `Result<Vec<u8>, image::ImageError>` containing the encoded
-/// image bytes (or an encoding error, e.g. a zero width).
+/// All arms return `Result<Vec<u8>, qrc::QrcError>` containing the encoded
+/// image bytes (or an error, e.g. oversized data or a zero width).
 ///
 /// # Parameters
 /// * `$data` - The data to be encoded in the QR code.
@@ -193,6 +193,12 @@ macro_rules! compress_data_macro {
 #[macro_export]
 /// Combines multiple QR codes into a single QR code.
 ///
+/// Deprecated along with
+/// [`QRCode::combine_qr_codes`](crate::QRCode::combine_qr_codes): the result
+/// does not scan. Use
+/// [`QRCode::to_structured_append`](crate::QRCode::to_structured_append)
+/// instead.
+///
 /// # Parameters
 /// - An array of `QRCode` instances to combine.
 ///
@@ -205,6 +211,7 @@ macro_rules! compress_data_macro {
 /// let qr_code2 = QRCode::from_string("Data 2".to_string());
 /// let qr_code3 = QRCode::from_string("Data 3".to_string());
 ///
+/// # #[allow(deprecated)]
 /// let combined_qr_code = combine_qr_codes!([qr_code1, qr_code2, qr_code3]);
 /// ```
 macro_rules! combine_qr_codes {
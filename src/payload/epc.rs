@@ -0,0 +1,346 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! EPC SEPA credit-transfer payloads ("GiroCode").
+//!
+//! Builds the line-based `BCD` payload of the European Payments Council's
+//! [EPC069-12 guidelines][epc] that banking apps scan to pre-fill a SEPA
+//! transfer: beneficiary, IBAN, optional BIC, amount in euro, and either a
+//! structured creditor reference or free remittance text. The IBAN is checked
+//! with its mod-97 check digits, the BIC against its layout, every field
+//! against its length, and the encoded payload against the 331-byte limit.
+//!
+//! ```
+//! use qrc::payload::epc::CreditTransfer;
+//! use qrc::EcLevel;
+//!
+//! let transfer = CreditTransfer::new("Red Cross Belgium", "BE72 0000 0000 1616")
+//!     .bic("BPOTBEB1")
+//!     .amount_cents(1000)
+//!     .text("Donation");
+//! let qr = transfer.to_qr_code().unwrap();
+//! assert_eq!(qr.ec_level, EcLevel::M);
+//! assert!(qr.try_to_qrcode().is_ok());
+//! ```
+//!
+//! [epc]: https://www.europeanpaymentscouncil.eu/document-library/guidance-documents/quick-response-code-guidelines-enable-data-capture-initiation
+
+use crate::{CharacterSet, EcLevel, QRCode, QrcError};
+
+/// The most bytes an EPC payload may take.
+pub const MAX_BYTES: usize = 331;
+
+/// The character sets EPC069-12 allows, in the order of their line-3 codes.
+const CHARSETS: [CharacterSet; 8] = [
+    CharacterSet::Utf8,
+    CharacterSet::Iso8859_1,
+    CharacterSet::Iso8859_2,
+    CharacterSet::Iso8859_4,
+    CharacterSet::Iso8859_5,
+    CharacterSet::Iso8859_7,
+    CharacterSet::Iso8859_10,
+    CharacterSet::Iso8859_15,
+];
+
+/// The smallest and largest amounts, in euro cents.
+const AMOUNTS: std::ops::RangeInclusive<u64> = 1..=99_999_999_999;
+
+/// Whether `iban` is a well-formed IBAN with valid check digits. Spaces are
+/// ignored and letters may be either case.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::payload::epc::is_valid_iban;
+///
+/// assert!(is_valid_iban("DE89 3704 0044 0532 0130 00"));
+/// assert!(!is_valid_iban("DE88 3704 0044 0532 0130 00"));
+/// ```
+#[must_use]
+pub fn is_valid_iban(iban: &str) -> bool {
+    let iban = compact(iban);
+    let bytes = iban.as_bytes();
+    if !(15..=34).contains(&bytes.len())
+        || !bytes[..2].iter().all(u8::is_ascii_uppercase)
+        || !bytes[2..4].iter().all(u8::is_ascii_digit)
+        || !bytes.iter().all(u8::is_ascii_alphanumeric)
+    {
+        return false;
+    }
+    mod97(bytes[4..].iter().chain(&bytes[..4])) == Some(1)
+}
+
+/// Whether `bic` is a well-formed BIC: a 4-letter institution, a 2-letter
+/// country, a 2-character location and an optional 3-character branch.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::payload::epc::is_valid_bic;
+///
+/// assert!(is_valid_bic("COBADEFFXXX"));
+/// assert!(!is_valid_bic("COBA1EFF"));
+/// ```
+#[must_use]
+pub fn is_valid_bic(bic: &str) -> bool {
+    let bytes = bic.as_bytes();
+    matches!(bytes.len(), 8 | 11)
+        && bytes[..6].iter().all(u8::is_ascii_uppercase)
+        && bytes[6..]
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Whether `reference` is an ISO 11649 creditor reference: `RF`, two check
+/// digits and up to 21 letters and digits, with valid mod-97 check digits.
+/// Spaces are ignored and letters may be either case.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::payload::epc::is_valid_creditor_reference;
+///
+/// assert!(is_valid_creditor_reference("RF18 5390 0754 7034"));
+/// assert!(!is_valid_creditor_reference("RF19 5390 0754 7034"));
+/// ```
+#[must_use]
+pub fn is_valid_creditor_reference(reference: &str) -> bool {
+    let rf = compact(reference);
+    let bytes = rf.as_bytes();
+    rf.starts_with("RF")
+        && (5..=25).contains(&bytes.len())
+        && bytes[2..4].iter().all(u8::is_ascii_digit)
+        && mod97(bytes[4..].iter().chain(&bytes[..4])) == Some(1)
+}
+
+/// `value` upper-cased with its spaces removed.
+pub(crate) fn compact(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+/// The ISO 7064 mod-97 remainder of `chars`, letters counting as 10–35.
+fn mod97<'a>(chars: impl Iterator<Item = &'a u8>) -> Option<u32> {
+    let mut rest = 0;
+    for &c in chars {
+        let value = u32::from(match c {
+            b'0'..=b'9' => c - b'0',
+            b'A'..=b'Z' => c - b'A' + 10,
+            _ => return None,
+        });
+        rest = (rest * if value < 10 { 10 } else { 100 } + value) % 97;
+    }
+    Some(rest)
+}
+
+/// A SEPA credit transfer that serialises to an EPC069-12 payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreditTransfer {
+    /// Beneficiary name, up to 70 characters.
+    name: String,
+    /// Beneficiary IBAN.
+    iban: String,
+    /// Beneficiary bank BIC; optional within the EEA.
+    bic: Option<String>,
+    /// Amount in euro cents.
+    amount: Option<u64>,
+    /// ISO 20022 purpose code, four letters.
+    purpose: Option<String>,
+    /// Structured creditor reference, up to 35 characters.
+    reference: Option<String>,
+    /// Unstructured remittance text, up to 140 characters.
+    text: Option<String>,
+    /// Note to the payer, up to 70 characters.
+    information: Option<String>,
+    /// Character set of the payload.
+    charset: CharacterSet,
+}
+
+impl CreditTransfer {
+    /// Creates a transfer to `name` at `iban`, in UTF-8 with no amount.
+    #[must_use]
+    pub fn new(name: impl Into<String>, iban: impl Into<String>) -> Self {
+        CreditTransfer {
+            name: name.into(),
+            iban: iban.into(),
+            bic: None,
+            amount: None,
+            purpose: None,
+            reference: None,
+            text: None,
+            information: None,
+            charset: CharacterSet::Utf8,
+        }
+    }
+
+    /// Sets the BIC of the beneficiary's bank.
+    #[must_use]
+    pub fn bic(mut self, bic: impl Into<String>) -> Self {
+        self.bic = Some(bic.into());
+        self
+    }
+
+    /// Sets the amount in euro cents, from 1 to 99 999 999 999.
+    #[must_use]
+    pub fn amount_cents(mut self, cents: u64) -> Self {
+        self.amount = Some(cents);
+        self
+    }
+
+    /// Sets the four-letter ISO 20022 purpose code, such as `CHAR` or `GDDS`.
+    #[must_use]
+    pub fn purpose(mut self, purpose: impl Into<String>) -> Self {
+        self.purpose = Some(purpose.into());
+        self
+    }
+
+    /// Sets the structured creditor reference, such as an ISO 11649 `RF`
+    /// reference. It cannot be combined with [`text`](Self::text).
+    #[must_use]
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    /// Sets the unstructured remittance text. It cannot be combined with
+    /// [`reference`](Self::reference).
+    #[must_use]
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Sets a note shown to the payer.
+    #[must_use]
+    pub fn information(mut self, information: impl Into<String>) -> Self {
+        self.information = Some(information.into());
+        self
+    }
+
+    /// Sets the character set: UTF-8 (the default) or ISO-8859-1, -2, -4,
+    /// -5, -7, -10 or -15.
+    #[must_use]
+    pub fn charset(mut self, charset: CharacterSet) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Serialises to the payload text, one field per line, with trailing
+    /// empty fields left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidPayload`] if the IBAN or BIC is invalid,
+    /// the amount or a field length is out of range, both a reference and
+    /// text are set, or the character set is not one EPC069-12 allows.
+    pub fn to_epc_string(&self) -> Result<String, QrcError> {
+        let invalid = |why: String| Err(QrcError::InvalidPayload(why));
+        let Some(code) = CHARSETS.iter().position(|&c| c == self.charset) else {
+            return invalid(format!("EPC payloads cannot use {}", self.charset.name()));
+        };
+        let iban = compact(&self.iban);
+        if !is_valid_iban(&iban) {
+            return invalid(format!("invalid IBAN {:?}", self.iban));
+        }
+        let bic = self.bic.as_deref().map(compact).unwrap_or_default();
+        if self.bic.is_some() && !is_valid_bic(&bic) {
+            return invalid(format!("invalid BIC {bic:?}"));
+        }
+        let amount = match self.amount {
+            Some(cents) if AMOUNTS.contains(&cents) => {
+                format!("EUR{}.{:02}", cents / 100, cents % 100)
+            }
+            Some(cents) => return invalid(format!("amount of {cents} cents is out of range")),
+            None => String::new(),
+        };
+        if let Some(purpose) = &self.purpose {
+            if purpose.len() != 4 || !purpose.bytes().all(|b| b.is_ascii_uppercase()) {
+                return invalid(format!("purpose code {purpose:?} is not four letters"));
+            }
+        }
+        if self.reference.is_some() && self.text.is_some() {
+            return invalid(
+                "a structured reference and remittance text cannot both be set".to_string(),
+            );
+        }
+        if let Some(reference) = &self.reference {
+            if compact(reference).starts_with("RF") && !is_valid_creditor_reference(reference) {
+                return invalid(format!("invalid RF creditor reference {reference:?}"));
+            }
+        }
+        for (field, value, max) in [
+            ("name", Some(&self.name), 70),
+            ("reference", self.reference.as_ref(), 35),
+            ("text", self.text.as_ref(), 140),
+            ("information", self.information.as_ref(), 70),
+        ] {
+            if let Some(value) = value {
+                if value.chars().count() > max || value.contains(['\n', '\r']) {
+                    return invalid(format!(
+                        "{field} must be one line of at most {max} characters"
+                    ));
+                }
+            }
+        }
+        if self.name.trim().is_empty() {
+            return invalid("the beneficiary name is required".to_string());
+        }
+
+        let lines = [
+            "BCD",
+            "002",
+            &(code + 1).to_string(),
+            "SCT",
+            &bic,
+            &self.name,
+            &iban,
+            &amount,
+            self.purpose.as_deref().unwrap_or_default(),
+            self.reference.as_deref().unwrap_or_default(),
+            self.text.as_deref().unwrap_or_default(),
+            self.information.as_deref().unwrap_or_default(),
+        ];
+        let used = lines.iter().rposition(|line| !line.is_empty()).unwrap_or(0);
+        Ok(lines[..=used].join("\n"))
+    }
+
+    /// Serialises to the payload bytes in the chosen character set.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_epc_string`](Self::to_epc_string), and
+    /// [`QrcError::InvalidPayload`] if a character is missing from the set
+    /// or the payload exceeds [`MAX_BYTES`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>, QrcError> {
+        let bytes = self
+            .charset
+            .encode(&self.to_epc_string()?)
+            .map_err(|err| match err {
+                QrcError::InvalidOption(msg) => QrcError::InvalidPayload(msg),
+                other => other,
+            })?;
+        if bytes.len() > MAX_BYTES {
+            return Err(QrcError::InvalidPayload(format!(
+                "EPC payload is {} bytes, at most {MAX_BYTES} allowed",
+                bytes.len()
+            )));
+        }
+        Ok(bytes)
+    }
+
+    /// A [`QRCode`] of the payload at [`EcLevel::M`], the level EPC069-12
+    /// requires. The character set is named in the payload, so no ECI
+    /// designator is written.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_bytes`](Self::to_bytes).
+    pub fn to_qr_code(&self) -> Result<QRCode, QrcError> {
+        let mut qr = QRCode::new(self.to_bytes()?).with_ec_level(EcLevel::M);
+        qr.encoding_format = self.charset.name().to_string();
+        Ok(qr)
+    }
+}
@@ -0,0 +1,412 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! GS1 element strings and GS1 Digital Link URIs for product marking.
+//!
+//! [`ElementString`] collects Application Identifier (AI) values — GTIN,
+//! batch, expiry date, serial number and the others in its table — checks
+//! their lengths, character sets, dates and check digits, and serialises
+//! them in the [GS1 General Specifications][genspecs] layout: fixed-length
+//! fields first, variable-length fields ended by a group separator. A
+//! [`QRCode`] built from it with [`ElementString::to_qr_code`] carries the
+//! FNC1 first-position indicator that tells scanners the data is GS1.
+//!
+//! ```
+//! use qrc::payload::gs1::ElementString;
+//!
+//! let product = ElementString::new()
+//!     .gtin("9506000134352")
+//!     .batch("ABC123")
+//!     .expiry("261231");
+//! assert_eq!(
+//!     product.to_hri().unwrap(),
+//!     "(01)09506000134352(17)261231(10)ABC123"
+//! );
+//! let qr = product.to_qr_code().unwrap();
+//! assert!(qr.gs1);
+//! assert!(qr.try_to_qrcode().is_ok());
+//! ```
+//!
+//! [`DigitalLink`] writes the same data as a [GS1 Digital Link][dl] URI,
+//! which any phone camera opens as a web address:
+//!
+//! ```
+//! use qrc::payload::gs1::{DigitalLink, ElementString};
+//!
+//! let product = ElementString::new()
+//!     .gtin("09506000134352")
+//!     .serial("12345")
+//!     .expiry("261231");
+//! let link = DigitalLink::new(product).domain("https://example.com");
+//! assert_eq!(
+//!     link.to_uri().unwrap(),
+//!     "https://example.com/01/09506000134352/21/12345?17=261231"
+//! );
+//! ```
+//!
+//! [genspecs]: https://www.gs1.org/standards/barcodes-epcrfid-id-keys/gs1-general-specifications
+//! [dl]: https://www.gs1.org/standards/gs1-digital-link
+
+use crate::{QRCode, QrcError};
+
+/// The group separator that ends a variable-length field.
+pub const GROUP_SEPARATOR: char = '\u{1d}';
+
+/// How the value of an Application Identifier is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// Exactly this many digits, the last a GS1 check digit.
+    Key(usize),
+    /// A `YYMMDD` date; a day of `00` means the end of the month.
+    Date,
+    /// Up to this many digits.
+    Digits(usize),
+    /// Up to this many characters of the GS1 AI encodable set.
+    Text(usize),
+}
+
+impl Format {
+    /// Whether the field has a predefined length and needs no separator.
+    fn fixed(self) -> bool {
+        matches!(self, Format::Key(_) | Format::Date)
+    }
+}
+
+/// The Application Identifiers this builder knows: code, data title and
+/// format.
+const AIS: &[(&str, &str, Format)] = &[
+    ("00", "SSCC", Format::Key(18)),
+    ("01", "GTIN", Format::Key(14)),
+    ("02", "CONTENT", Format::Key(14)),
+    ("10", "BATCH/LOT", Format::Text(20)),
+    ("11", "PROD DATE", Format::Date),
+    ("12", "DUE DATE", Format::Date),
+    ("13", "PACK DATE", Format::Date),
+    ("15", "BEST BEFORE or BEST BY", Format::Date),
+    ("16", "SELL BY", Format::Date),
+    ("17", "USE BY OR EXPIRY", Format::Date),
+    ("21", "SERIAL", Format::Text(20)),
+    ("22", "CPV", Format::Text(20)),
+    ("30", "VAR. COUNT", Format::Digits(8)),
+    ("37", "COUNT", Format::Digits(8)),
+    ("400", "ORDER NUMBER", Format::Text(30)),
+    ("414", "LOC No.", Format::Key(13)),
+    ("8200", "PRODUCT URL", Format::Text(70)),
+];
+
+/// Looks up the data title and format of `ai` in [`AIS`].
+fn format_of(ai: &str) -> Result<(&'static str, Format), QrcError> {
+    AIS.iter()
+        .find(|(code, _, _)| *code == ai)
+        .map(|&(_, title, format)| (title, format))
+        .ok_or_else(|| QrcError::InvalidPayload(format!("unsupported GS1 AI ({ai})")))
+}
+
+/// The GS1 mod-10 check digit of `digits`: weights 3 and 1 alternate from
+/// the rightmost digit.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::payload::gs1::check_digit;
+///
+/// assert_eq!(check_digit("0950600013435"), Some(2));
+/// assert_eq!(check_digit("95O6"), None);
+/// ```
+#[must_use]
+pub fn check_digit(digits: &str) -> Option<u8> {
+    let mut sum = 0;
+    for (i, c) in digits.bytes().rev().enumerate() {
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        sum += u32::from(c - b'0') * if i % 2 == 0 { 3 } else { 1 };
+    }
+    u8::try_from((10 - sum % 10) % 10).ok()
+}
+
+/// Whether `c` is in GS1 AI encodable character set 82.
+fn encodable(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!\"%&'()*+,-./:;<=>?_".contains(c)
+}
+
+/// Checks `value` against the format of `ai`.
+fn validate(ai: &str, value: &str) -> Result<Format, QrcError> {
+    let (title, format) = format_of(ai)?;
+    let invalid = |why: &str| {
+        Err(QrcError::InvalidPayload(format!(
+            "GS1 AI ({ai}) {title} {why}"
+        )))
+    };
+    let digits = value.bytes().all(|b| b.is_ascii_digit());
+    match format {
+        Format::Key(len) => {
+            if value.len() != len || !digits {
+                return invalid(&format!("must be {len} digits, got {value:?}"));
+            }
+            let (body, check) = value.split_at(len - 1);
+            if check_digit(body) != Some(check.as_bytes()[0] - b'0') {
+                return invalid(&format!("has a wrong check digit in {value}"));
+            }
+        }
+        Format::Date => {
+            if value.len() != 6 || !digits {
+                return invalid(&format!("must be a YYMMDD date, got {value:?}"));
+            }
+            let field = |at: usize| {
+                u32::from((value.as_bytes()[at] - b'0') * 10 + value.as_bytes()[at + 1] - b'0')
+            };
+            let (year, month, day) = (field(0), field(2), field(4));
+            let days = match month {
+                2 if year % 4 == 0 => 29,
+                2 => 28,
+                4 | 6 | 9 | 11 => 30,
+                1..=12 => 31,
+                _ => return invalid(&format!("has no month {month} in {value}")),
+            };
+            if day > days {
+                return invalid(&format!("has no day {day} in {value}"));
+            }
+        }
+        Format::Digits(max) => {
+            if value.is_empty() || value.len() > max || !digits {
+                return invalid(&format!("must be 1-{max} digits, got {value:?}"));
+            }
+        }
+        Format::Text(max) => {
+            if value.is_empty() || value.len() > max {
+                return invalid(&format!("must be 1-{max} characters, got {value:?}"));
+            }
+            if let Some(c) = value.chars().find(|&c| !encodable(c)) {
+                return invalid(&format!("cannot contain {c:?}"));
+            }
+        }
+    }
+    Ok(format)
+}
+
+/// A GS1 element string: Application Identifiers and their values.
+///
+/// Values are checked when the string is serialised; a GTIN of 8, 12 or 13
+/// digits is padded to the 14 of AI (01).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ElementString {
+    /// AI and value pairs, in the order they were added.
+    elements: Vec<(String, String)>,
+}
+
+impl ElementString {
+    /// Creates an empty element string.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the value of any AI in the builder's table.
+    #[must_use]
+    pub fn element(mut self, ai: impl Into<String>, value: impl Into<String>) -> Self {
+        self.elements.push((ai.into(), value.into()));
+        self
+    }
+
+    /// Sets the Global Trade Item Number (AI 01): 8, 12, 13 or 14 digits,
+    /// check digit included.
+    #[must_use]
+    pub fn gtin(self, gtin: impl Into<String>) -> Self {
+        let gtin = gtin.into();
+        let gtin = if matches!(gtin.len(), 8 | 12 | 13) {
+            format!("{gtin:0>14}")
+        } else {
+            gtin
+        };
+        self.element("01", gtin)
+    }
+
+    /// Sets the batch or lot number (AI 10), up to 20 characters.
+    #[must_use]
+    pub fn batch(self, batch: impl Into<String>) -> Self {
+        self.element("10", batch)
+    }
+
+    /// Sets the expiry date (AI 17) as `YYMMDD`.
+    #[must_use]
+    pub fn expiry(self, date: impl Into<String>) -> Self {
+        self.element("17", date)
+    }
+
+    /// Sets the best-before date (AI 15) as `YYMMDD`.
+    #[must_use]
+    pub fn best_before(self, date: impl Into<String>) -> Self {
+        self.element("15", date)
+    }
+
+    /// Sets the serial number (AI 21), up to 20 characters.
+    #[must_use]
+    pub fn serial(self, serial: impl Into<String>) -> Self {
+        self.element("21", serial)
+    }
+
+    /// The value of `ai`, if set.
+    #[must_use]
+    pub fn get(&self, ai: &str) -> Option<&str> {
+        self.elements
+            .iter()
+            .find(|(code, _)| code == ai)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The elements checked and in serialisation order: fixed-length fields
+    /// first, each group in the order added.
+    fn checked(&self) -> Result<Vec<(&str, &str, Format)>, QrcError> {
+        if self.elements.is_empty() {
+            return Err(QrcError::InvalidPayload(
+                "a GS1 element string needs at least one AI".to_string(),
+            ));
+        }
+        let mut out = Vec::with_capacity(self.elements.len());
+        for (i, (ai, value)) in self.elements.iter().enumerate() {
+            if self.elements[..i].iter().any(|(code, _)| code == ai) {
+                return Err(QrcError::InvalidPayload(format!(
+                    "GS1 AI ({ai}) is given twice"
+                )));
+            }
+            let format = validate(ai, value)?;
+            out.push((ai.as_str(), value.as_str(), format));
+        }
+        out.sort_by_key(|&(_, _, format)| !format.fixed());
+        Ok(out)
+    }
+
+    /// Serialises to the element string encoded in the symbol, with a
+    /// [`GROUP_SEPARATOR`] after every variable-length field but the last.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidPayload`] if the string is empty, an AI is
+    /// unknown or repeated, or a value breaks its AI's format.
+    pub fn to_gs1_string(&self) -> Result<String, QrcError> {
+        let elements = self.checked()?;
+        let mut s = String::new();
+        for (i, (ai, value, format)) in elements.iter().enumerate() {
+            s.push_str(ai);
+            s.push_str(value);
+            if !format.fixed() && i + 1 < elements.len() {
+                s.push(GROUP_SEPARATOR);
+            }
+        }
+        Ok(s)
+    }
+
+    /// Serialises to the human-readable interpretation printed under a
+    /// symbol, with each AI in brackets.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_gs1_string`](Self::to_gs1_string).
+    pub fn to_hri(&self) -> Result<String, QrcError> {
+        Ok(self
+            .checked()?
+            .iter()
+            .map(|(ai, value, _)| format!("({ai}){value}"))
+            .collect())
+    }
+
+    /// A [`QRCode`] of the element string in FNC1 mode.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_gs1_string`](Self::to_gs1_string).
+    pub fn to_qr_code(&self) -> Result<QRCode, QrcError> {
+        Ok(QRCode::from_string(self.to_gs1_string()?).with_gs1(true))
+    }
+}
+
+/// Primary keys a Digital Link path can start with, each followed by the key
+/// qualifiers it allows, in path order.
+const PRIMARY_KEYS: &[(&str, &[&str])] = &[("01", &["22", "10", "21"]), ("00", &[]), ("414", &[])];
+
+/// The Digital Link resolver of GS1 itself.
+const DEFAULT_DOMAIN: &str = "https://id.gs1.org";
+
+/// Percent-encodes everything but the URI unreserved characters.
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            out.push(char::from(byte));
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// A GS1 Digital Link URI: a primary key and its qualifiers in the path,
+/// other attributes in the query string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DigitalLink {
+    /// Scheme and host the path is appended to.
+    domain: String,
+    /// The data to link.
+    elements: ElementString,
+}
+
+impl DigitalLink {
+    /// Creates a link to `elements` on GS1's resolver, `https://id.gs1.org`.
+    #[must_use]
+    pub fn new(elements: ElementString) -> Self {
+        DigitalLink {
+            domain: DEFAULT_DOMAIN.to_string(),
+            elements,
+        }
+    }
+
+    /// Sets the scheme and host, such as a brand's own resolver.
+    #[must_use]
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = domain.into();
+        self
+    }
+
+    /// Serialises to the URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidPayload`] if the elements are invalid, as
+    /// for [`ElementString::to_gs1_string`], or hold no primary key (GTIN,
+    /// SSCC or GLN).
+    pub fn to_uri(&self) -> Result<String, QrcError> {
+        let elements = self.elements.checked()?;
+        let (key, qualifiers) = PRIMARY_KEYS
+            .iter()
+            .find(|(key, _)| self.elements.get(key).is_some())
+            .ok_or_else(|| {
+                QrcError::InvalidPayload("a GS1 Digital Link needs a GTIN, SSCC or GLN".to_string())
+            })?;
+        let mut uri = self.domain.trim_end_matches('/').to_string();
+        for ai in std::iter::once(key).chain(qualifiers.iter()) {
+            if let Some(value) = self.elements.get(ai) {
+                uri.push_str(&format!("/{ai}/{}", percent_encode(value)));
+            }
+        }
+        let query: Vec<String> = elements
+            .iter()
+            .filter(|(ai, _, _)| ai != key && !qualifiers.contains(ai))
+            .map(|(ai, value, _)| format!("{ai}={}", percent_encode(value)))
+            .collect();
+        if !query.is_empty() {
+            uri.push('?');
+            uri.push_str(&query.join("&"));
+        }
+        Ok(uri)
+    }
+
+    /// A [`QRCode`] of the URI.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_uri`](Self::to_uri).
+    pub fn to_qr_code(&self) -> Result<QRCode, QrcError> {
+        Ok(QRCode::from_string(self.to_uri()?))
+    }
+}
@@ -9,11 +9,17 @@
 //! dependencies.
 
 pub mod emvco;
+pub mod epc;
+pub mod gs1;
 pub mod mecard;
+pub mod swiss_qr_bill;
 pub mod vcard;
 pub mod wifi;
 
 pub use emvco::{MerchantAccount, MerchantPayment};
+pub use epc::CreditTransfer;
+pub use gs1::{DigitalLink, ElementString};
 pub use mecard::MeCard;
+pub use swiss_qr_bill::QrBill;
 pub use vcard::BusinessCard;
 pub use wifi::{WifiNetwork, WifiSecurity};
@@ -0,0 +1,498 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Swiss QR-bill payment payloads and their Swiss QR Code.
+//!
+//! [`QrBill`] builds the `SPC` payload of the [Swiss Implementation
+//! Guidelines for the QR-bill][ig]: the account, structured creditor and
+//! debtor addresses, the amount in CHF or EUR, and a reference. A QR-IBAN
+//! takes a 27-digit QR reference (QRR); an ordinary IBAN takes an ISO 11649
+//! creditor reference (SCOR) or none. Check digits, field lengths, the
+//! permitted characters and the 997-character limit are all checked.
+//!
+//! The Swiss QR Code is a QR code at [`EcLevel::M`] printed 46 mm wide with
+//! a 7 mm Swiss cross over its centre. [`QrBill::to_image`] and
+//! [`QrBill::to_svg`] render it at that physical size.
+//!
+//! ```
+//! use qrc::payload::swiss_qr_bill::{Address, Currency, QrBill, Reference};
+//!
+//! let creditor = Address::new("Robert Schneider AG", "2501", "Biel", "CH")
+//!     .street("Rue du Lac")
+//!     .building_number("1268");
+//! let bill = QrBill::new("CH44 3199 9123 0008 8901 2", creditor, Currency::Chf)
+//!     .amount_cents(194_975)
+//!     .reference(Reference::Qrr("210000000003139471430009017".to_string()));
+//! let payload = bill.to_qr_string().unwrap();
+//! assert!(payload.starts_with("SPC\n0200\n1\nCH4431999123000889012\nS\n"));
+//! // 46 mm of symbol at 300 dpi, plus its quiet zone.
+//! let img = bill.to_image(300).unwrap();
+//! assert!(img.width() > 543);
+//! ```
+//!
+//! [ig]: https://www.six-group.com/en/products-services/banking-services/payment-standardization/standards/qr-bill.html
+
+use super::epc::{compact, is_valid_creditor_reference, is_valid_iban};
+use crate::{EcLevel, Length, QRCode, QrcError, SvgOptions};
+use image::{Rgba, RgbaImage};
+
+/// Side of the Swiss QR Code, quiet zone excluded, in millimetres.
+pub const QR_SIZE_MM: f64 = 46.0;
+
+/// Side of the Swiss cross, white border included, in millimetres.
+pub const CROSS_SIZE_MM: f64 = 7.0;
+
+/// The most characters a QR-bill payload may take.
+pub const MAX_CHARS: usize = 997;
+
+/// The smallest and largest amounts, in cents.
+const AMOUNTS: std::ops::RangeInclusive<u64> = 1..=99_999_999_999;
+
+/// Weights of the recursive mod-10 check digit of QR references.
+const MOD10: [u32; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+
+/// The cross as rectangles in millimetres, each `(x, y, width, height,
+/// dark)`: the white ground, the black square inset by half a millimetre,
+/// and the two white bars of the cross in the flag's proportions within the
+/// black square.
+const CROSS: [(f64, f64, f64, f64, bool); 4] = [
+    (0.0, 0.0, 7.0, 7.0, false),
+    (0.5, 0.5, 6.0, 6.0, true),
+    (2.9375, 1.625, 1.125, 3.75, false),
+    (1.625, 2.9375, 3.75, 1.125, false),
+];
+
+/// The currency of the payment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Currency {
+    /// Swiss francs.
+    #[default]
+    Chf,
+    /// Euro.
+    Eur,
+}
+
+impl Currency {
+    /// The ISO 4217 code written in the payload.
+    fn code(self) -> &'static str {
+        match self {
+            Currency::Chf => "CHF",
+            Currency::Eur => "EUR",
+        }
+    }
+}
+
+/// The payment reference.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Reference {
+    /// A 27-digit QR reference, last digit a mod-10 check; required with a
+    /// QR-IBAN.
+    Qrr(String),
+    /// An ISO 11649 creditor reference (`RF…`); only with an ordinary IBAN.
+    Scor(String),
+    /// No reference; only with an ordinary IBAN.
+    #[default]
+    None,
+}
+
+/// The recursive mod-10 check digit that ends a QR reference.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::payload::swiss_qr_bill::qrr_check_digit;
+///
+/// assert_eq!(qrr_check_digit("21000000000313947143000901"), Some(7));
+/// ```
+#[must_use]
+pub fn qrr_check_digit(digits: &str) -> Option<u8> {
+    let mut carry = 0;
+    for c in digits.bytes() {
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        carry = MOD10[((carry + u32::from(c - b'0')) % 10) as usize];
+    }
+    u8::try_from((10 - carry) % 10).ok()
+}
+
+/// Whether `iban` is a QR-IBAN: a Swiss or Liechtenstein IBAN whose
+/// institution number is 30000–31999.
+#[must_use]
+pub fn is_qr_iban(iban: &str) -> bool {
+    let iban = compact(iban);
+    is_valid_iban(&iban)
+        && iban.len() == 21
+        && iban[4..9]
+            .parse::<u32>()
+            .is_ok_and(|iid| (30_000..=31_999).contains(&iid))
+}
+
+/// Whether `c` is in the character set the QR-bill permits: printable
+/// Basic Latin, Latin-1 Supplement and Latin Extended-A, `ȘșȚț` and `€`.
+fn permitted(c: char) -> bool {
+    matches!(c, ' '..='~' | '\u{A0}'..='\u{17F}' | '\u{218}'..='\u{21B}' | '€')
+}
+
+/// A structured postal address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Address {
+    /// Name or company, up to 70 characters.
+    name: String,
+    /// Street or post-office box, up to 70 characters.
+    street: Option<String>,
+    /// Building number, up to 16 characters.
+    building_number: Option<String>,
+    /// Postal code, up to 16 characters.
+    postal_code: String,
+    /// Town, up to 35 characters.
+    town: String,
+    /// ISO 3166-1 alpha-2 country code.
+    country: String,
+}
+
+impl Address {
+    /// Creates an address with the fields the QR-bill requires.
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        postal_code: impl Into<String>,
+        town: impl Into<String>,
+        country: impl Into<String>,
+    ) -> Self {
+        Address {
+            name: name.into(),
+            street: None,
+            building_number: None,
+            postal_code: postal_code.into(),
+            town: town.into(),
+            country: country.into(),
+        }
+    }
+
+    /// Sets the street or post-office box.
+    #[must_use]
+    pub fn street(mut self, street: impl Into<String>) -> Self {
+        self.street = Some(street.into());
+        self
+    }
+
+    /// Sets the building number.
+    #[must_use]
+    pub fn building_number(mut self, number: impl Into<String>) -> Self {
+        self.building_number = Some(number.into());
+        self
+    }
+
+    /// The seven payload lines of the address, checked.
+    fn lines(&self, party: &str) -> Result<[&str; 7], QrcError> {
+        for (field, value, max) in [
+            ("name", Some(&self.name), 70),
+            ("street", self.street.as_ref(), 70),
+            ("building number", self.building_number.as_ref(), 16),
+            ("postal code", Some(&self.postal_code), 16),
+            ("town", Some(&self.town), 35),
+        ] {
+            check_text(&format!("{party} {field}"), value, max)?;
+        }
+        for (field, value) in [
+            ("name", &self.name),
+            ("postal code", &self.postal_code),
+            ("town", &self.town),
+        ] {
+            if value.trim().is_empty() {
+                return Err(QrcError::InvalidPayload(format!(
+                    "the {party} {field} is required"
+                )));
+            }
+        }
+        if self.country.len() != 2 || !self.country.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(QrcError::InvalidPayload(format!(
+                "{party} country {:?} is not an ISO 3166-1 alpha-2 code",
+                self.country
+            )));
+        }
+        Ok([
+            "S",
+            &self.name,
+            self.street.as_deref().unwrap_or_default(),
+            self.building_number.as_deref().unwrap_or_default(),
+            &self.postal_code,
+            &self.town,
+            &self.country,
+        ])
+    }
+}
+
+/// Rejects a `field` longer than `max` characters or holding characters
+/// outside the permitted set.
+fn check_text(field: &str, value: Option<&String>, max: usize) -> Result<(), QrcError> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    if value.chars().count() > max {
+        return Err(QrcError::InvalidPayload(format!(
+            "{field} is longer than {max} characters"
+        )));
+    }
+    if let Some(c) = value.chars().find(|&c| !permitted(c)) {
+        return Err(QrcError::InvalidPayload(format!(
+            "{field} cannot contain {c:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// A Swiss QR-bill that serialises to an `SPC` payload and renders as a
+/// Swiss QR Code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QrBill {
+    /// Creditor IBAN or QR-IBAN, Swiss or Liechtenstein.
+    iban: String,
+    /// Creditor address.
+    creditor: Address,
+    /// Amount in cents; the payer enters it when absent.
+    amount: Option<u64>,
+    /// Currency of the amount.
+    currency: Currency,
+    /// Debtor address; the payer enters it when absent.
+    debtor: Option<Address>,
+    /// Payment reference.
+    reference: Reference,
+    /// Unstructured message, up to 140 characters.
+    message: Option<String>,
+    /// Structured bill information, up to 140 characters.
+    bill_information: Option<String>,
+}
+
+impl QrBill {
+    /// Creates a bill payable to `creditor` at `iban`, with no amount,
+    /// debtor or reference.
+    #[must_use]
+    pub fn new(iban: impl Into<String>, creditor: Address, currency: Currency) -> Self {
+        QrBill {
+            iban: iban.into(),
+            creditor,
+            amount: None,
+            currency,
+            debtor: None,
+            reference: Reference::None,
+            message: None,
+            bill_information: None,
+        }
+    }
+
+    /// Sets the amount in cents, from 1 to 99 999 999 999.
+    #[must_use]
+    pub fn amount_cents(mut self, cents: u64) -> Self {
+        self.amount = Some(cents);
+        self
+    }
+
+    /// Sets the debtor address.
+    #[must_use]
+    pub fn debtor(mut self, debtor: Address) -> Self {
+        self.debtor = Some(debtor);
+        self
+    }
+
+    /// Sets the payment reference.
+    #[must_use]
+    pub fn reference(mut self, reference: Reference) -> Self {
+        self.reference = reference;
+        self
+    }
+
+    /// Sets the unstructured message to the payer.
+    #[must_use]
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Sets the structured bill information, such as a Swico `//S1/…`
+    /// string.
+    #[must_use]
+    pub fn bill_information(mut self, information: impl Into<String>) -> Self {
+        self.bill_information = Some(information.into());
+        self
+    }
+
+    /// Serialises to the payload text, one element per line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidPayload`] if the IBAN is not a valid Swiss
+    /// or Liechtenstein IBAN, the reference does not suit it or fails its
+    /// check digits, the amount or a field is out of range, or the payload
+    /// exceeds [`MAX_CHARS`].
+    pub fn to_qr_string(&self) -> Result<String, QrcError> {
+        let invalid = |why: String| Err(QrcError::InvalidPayload(why));
+        let iban = compact(&self.iban);
+        if iban.len() != 21 || !is_valid_iban(&iban) || !matches!(&iban[..2], "CH" | "LI") {
+            return invalid(format!(
+                "{:?} is not a valid Swiss or Liechtenstein IBAN",
+                self.iban
+            ));
+        }
+        let (kind, reference) = match (&self.reference, is_qr_iban(&iban)) {
+            (Reference::Qrr(reference), true) => {
+                let digits = compact(reference);
+                let valid = digits.len() == 27
+                    && digits.bytes().all(|b| b.is_ascii_digit())
+                    && qrr_check_digit(&digits[..26])
+                        .is_some_and(|check| digits.ends_with(char::from(b'0' + check)));
+                if !valid {
+                    return invalid(format!("invalid QR reference {reference:?}"));
+                }
+                ("QRR", digits)
+            }
+            (Reference::Scor(reference), false) => {
+                if !is_valid_creditor_reference(reference) {
+                    return invalid(format!("invalid creditor reference {reference:?}"));
+                }
+                ("SCOR", compact(reference))
+            }
+            (Reference::None, false) => ("NON", String::new()),
+            (_, true) => return invalid("a QR-IBAN needs a QR reference".to_string()),
+            (Reference::Qrr(_), false) => {
+                return invalid("a QR reference needs a QR-IBAN".to_string())
+            }
+        };
+        let amount = match self.amount {
+            Some(cents) if AMOUNTS.contains(&cents) => {
+                format!("{}.{:02}", cents / 100, cents % 100)
+            }
+            Some(cents) => return invalid(format!("amount of {cents} cents is out of range")),
+            None => String::new(),
+        };
+        check_text("message", self.message.as_ref(), 140)?;
+        check_text("bill information", self.bill_information.as_ref(), 140)?;
+
+        let mut lines = vec!["SPC", "0200", "1", &iban];
+        lines.extend(self.creditor.lines("creditor")?);
+        // The ultimate creditor is reserved for future use and left empty.
+        lines.extend([""; 7]);
+        lines.extend([amount.as_str(), self.currency.code()]);
+        match &self.debtor {
+            Some(debtor) => lines.extend(debtor.lines("debtor")?),
+            None => lines.extend([""; 7]),
+        }
+        lines.extend([
+            kind,
+            &reference,
+            self.message.as_deref().unwrap_or_default(),
+            "EPD",
+        ]);
+        if let Some(information) = &self.bill_information {
+            lines.push(information);
+        }
+        let payload = lines.join("\n");
+        let chars = payload.chars().count();
+        if chars > MAX_CHARS {
+            return invalid(format!(
+                "QR-bill payload is {chars} characters, at most {MAX_CHARS} allowed"
+            ));
+        }
+        Ok(payload)
+    }
+
+    /// A [`QRCode`] of the payload at [`EcLevel::M`], the level the QR-bill
+    /// requires. It carries no Swiss cross; render with
+    /// [`to_image`](Self::to_image) or [`to_svg`](Self::to_svg) for that.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_qr_string`](Self::to_qr_string).
+    pub fn to_qr_code(&self) -> Result<QRCode, QrcError> {
+        Ok(QRCode::from_string(self.to_qr_string()?).with_ec_level(EcLevel::M))
+    }
+
+    /// Renders the Swiss QR Code at `dpi`: the symbol 46 mm wide, as near
+    /// as whole pixels per module allow, with its quiet zone, and the 7 mm
+    /// Swiss cross over the centre.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_qr_string`](Self::to_qr_string), and
+    /// [`QrcError::InvalidOption`] if `dpi` is `0`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn to_image(&self, dpi: u32) -> Result<RgbaImage, QrcError> {
+        if dpi == 0 {
+            return Err(QrcError::InvalidOption(
+                "DPI must be at least 1".to_string(),
+            ));
+        }
+        let qr = self.to_qr_code()?;
+        let modules = qr.try_to_qrcode()?.width() as f64;
+        let px_per_mm = f64::from(dpi) * Length::Millimetres(1.0).to_inches();
+        let module_px = (QR_SIZE_MM * px_per_mm / modules).round().max(1.0) as u32;
+        let mut img = qr.try_to_image_scaled(module_px)?;
+
+        // The cross keeps its own size whatever the module size came to.
+        let side = CROSS_SIZE_MM * px_per_mm;
+        let left = (f64::from(img.width()) - side) / 2.0;
+        let top = (f64::from(img.height()) - side) / 2.0;
+        let (first, last) = (left.max(0.0) as u32, (left + side).ceil() as u32);
+        for y in first..last.min(img.height()) {
+            for x in first..last.min(img.width()) {
+                let (u, v) = (
+                    (f64::from(x) + 0.5 - left) / px_per_mm,
+                    (f64::from(y) + 0.5 - top) / px_per_mm,
+                );
+                let dark = CROSS
+                    .iter()
+                    .rev()
+                    .find(|&&(x0, y0, w, h, _)| u >= x0 && u < x0 + w && v >= y0 && v < y0 + h)
+                    .map(|&(_, _, _, _, dark)| dark);
+                match dark {
+                    Some(true) => img.put_pixel(x, y, Rgba([0, 0, 0, 255])),
+                    Some(false) => img.put_pixel(x, y, Rgba([255, 255, 255, 255])),
+                    None => {}
+                }
+            }
+        }
+        Ok(img)
+    }
+
+    /// Renders the Swiss QR Code as an SVG document sized in millimetres:
+    /// the symbol exactly 46 mm wide with its quiet zone, and the 7 mm Swiss
+    /// cross over the centre.
+    ///
+    /// # Errors
+    ///
+    /// As for [`to_qr_string`](Self::to_qr_string).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_svg(&self) -> Result<String, QrcError> {
+        let qr = self.to_qr_code()?;
+        let modules = qr.try_to_qrcode()?.width() as f64;
+        let total = modules + 2.0 * qr.quiet_zone as f64;
+        let module_mm = QR_SIZE_MM / modules;
+        let options = SvgOptions::default().with_size(Length::Millimetres(total * module_mm));
+        let svg = qr.try_to_svg_with_options(0, &options)?;
+
+        // The document is in module units; the cross is in millimetres.
+        let unit = 1.0 / module_mm;
+        let origin = (total - CROSS_SIZE_MM * unit) / 2.0;
+        let num = |v: f64| (v * 1e4).round() / 1e4;
+        let cross: String = CROSS
+            .iter()
+            .map(|&(x, y, w, h, dark)| {
+                format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    num(origin + x * unit),
+                    num(origin + y * unit),
+                    num(w * unit),
+                    num(h * unit),
+                    if dark { "#000" } else { "#fff" }
+                )
+            })
+            .collect();
+        let end = svg.rfind("</svg>").unwrap_or(svg.len());
+        Ok(format!("{}<g>{cross}</g>{}", &svg[..end], &svg[end..]))
+    }
+}
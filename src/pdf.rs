@@ -0,0 +1,240 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A minimal single-page PDF writer for vector QR codes.
+//!
+//! The page holds one Flate-compressed content stream. Layers are drawn in
+//! module units under a transform that flips the y axis, so the outlines are
+//! the same [`Path`](crate::vector::Path)s the SVG writer uses. Gradients
+//! become axial or radial shadings clipped to their layer; the shading
+//! dictionaries are shared with the EPS writer, whose PostScript syntax for
+//! them is identical.
+
+use crate::style::GradientGeometry;
+use crate::vector::{number, Fill, Layer};
+use crate::Gradient;
+use image::Rgba;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use std::fmt::Write as _;
+
+/// Largest page side Acrobat accepts, in points (200 inches).
+pub(crate) const MAX_PAGE: f64 = 14_400.0;
+
+/// Decimals written for coordinates and colour components.
+const PRECISION: u8 = 4;
+
+/// A PDF page `side` points wide showing `layers`, drawn in a grid of
+/// `modules` columns and rows, over an optional `background`. The page is
+/// as high as the grid's proportions ask.
+pub(crate) fn document(
+    layers: &[Layer],
+    background: Option<Rgba<u8>>,
+    modules: (f64, f64),
+    side: f64,
+) -> Vec<u8> {
+    let num = |value: f64| number(value, PRECISION);
+    let scale = side / modules.0;
+    let height = scale * modules.1;
+    let mut content = format!(
+        "q\n{} 0 0 {} 0 {} cm\n",
+        num(scale),
+        num(-scale),
+        num(height)
+    );
+    if let Some(color) = background {
+        let (w, h) = (num(modules.0), num(modules.1));
+        let _ = writeln!(content, "{}0 0 {w} {h} re\nf", rgb(color));
+    }
+    let mut shadings: Vec<(String, String)> = Vec::new();
+    for layer in layers {
+        let path = layer.path.to_postfix(["m", "l", "c", "h"], PRECISION);
+        let star = if layer.even_odd { "*" } else { "" };
+        match &layer.fill {
+            Fill::Solid(color) => {
+                let _ = writeln!(content, "{}{path}f{star}", rgb(*color));
+            }
+            Fill::Gradient {
+                name,
+                gradient,
+                x,
+                y,
+                size,
+            } => {
+                if !shadings.iter().any(|(known, _)| known == name) {
+                    shadings.push((
+                        name.clone(),
+                        shading(gradient, *x, *y, *size, ColorModel::Rgb),
+                    ));
+                }
+                let cm = match gradient.geometry(*x, *y, *size).transform() {
+                    Some(m) => format!("{} cm\n", m.map(num).join(" ")),
+                    None => String::new(),
+                };
+                let _ = write!(content, "q\n{path}W{star} n\n{cm}/{name} sh\nQ\n");
+            }
+        }
+    }
+    content.push_str("Q\n");
+
+    let mut resources = String::from("<< ");
+    if !shadings.is_empty() {
+        resources.push_str("/Shading << ");
+        for (name, dict) in &shadings {
+            let _ = write!(resources, "/{name} {dict} ");
+        }
+        resources.push_str(">> ");
+    }
+    resources.push_str(">>");
+
+    let stream = compress_to_vec_zlib(content.as_bytes(), 9);
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {w} {h}] /Resources {resources} /Contents 4 0 R >>",
+            w = num(side),
+            h = num(height)
+        ),
+        format!("<< /Length {} /Filter /FlateDecode >>", stream.len()),
+        format!(
+            "<< /Producer (qrc {}) >>",
+            env!("CARGO_PKG_VERSION")
+        ),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{object}\n", i + 1).as_bytes());
+        if i == 3 {
+            pdf.extend_from_slice(b"stream\n");
+            pdf.extend_from_slice(&stream);
+            pdf.extend_from_slice(b"\nendstream\n");
+        }
+        pdf.extend_from_slice(b"endobj\n");
+    }
+    let xref = pdf.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(trailer, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        trailer,
+        "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    pdf.extend_from_slice(trailer.as_bytes());
+    pdf
+}
+
+/// Device colour space used for fills and shadings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColorModel {
+    /// `DeviceRGB`.
+    Rgb,
+    /// `DeviceCMYK`, converted naively from RGB (no ICC profile).
+    Cmyk,
+}
+
+impl ColorModel {
+    /// The PDF/PostScript colour space name.
+    fn name(self) -> &'static str {
+        match self {
+            ColorModel::Rgb => "DeviceRGB",
+            ColorModel::Cmyk => "DeviceCMYK",
+        }
+    }
+
+    /// Components of `color` in `0..=1`, space-separated; alpha is not
+    /// representable without transparency groups and is ignored.
+    pub(crate) fn components(self, color: Rgba<u8>) -> String {
+        let [r, g, b, _] = color.0.map(|c| f64::from(c) / 255.0);
+        let values = match self {
+            ColorModel::Rgb => vec![r, g, b],
+            ColorModel::Cmyk => {
+                let k = 1.0 - r.max(g).max(b);
+                if k >= 1.0 {
+                    vec![0.0, 0.0, 0.0, 1.0]
+                } else {
+                    let ink = |c: f64| (1.0 - c - k) / (1.0 - k);
+                    vec![ink(r), ink(g), ink(b), k]
+                }
+            }
+        };
+        values
+            .into_iter()
+            .map(|v| number(v, PRECISION))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// `r g b rg` fill-colour operator for `color`.
+fn rgb(color: Rgba<u8>) -> String {
+    format!("{} rg\n", ColorModel::Rgb.components(color))
+}
+
+/// Shading dictionary for `gradient` over the box at (`x`, `y`) with width
+/// and height `size` in colour space `model`, extended past both ends like
+/// SVG's `pad` spread. Elliptical radial shadings are drawn as circles under
+/// [`GradientGeometry::transform`], which the caller applies.
+pub(crate) fn shading(
+    gradient: &Gradient,
+    x: f64,
+    y: f64,
+    size: (f64, f64),
+    model: ColorModel,
+) -> String {
+    let num = |value: f64| number(value, PRECISION);
+    let (kind, coords) = match gradient.geometry(x, y, size) {
+        GradientGeometry::Linear(c) => (2, c.map(num).join(" ")),
+        GradientGeometry::Radial { cx, cy, r, .. } => (
+            3,
+            format!("{x} {y} 0 {x} {y} {}", num(r), x = num(cx), y = num(cy)),
+        ),
+    };
+    format!(
+        "<< /ShadingType {kind} /ColorSpace /{} /Coords [{coords}] /Function {} /Extend [true true] >>",
+        model.name(),
+        stops_function(gradient, model)
+    )
+}
+
+/// A stitching function through the gradient stops, padded with flat
+/// segments so it spans the whole `0..=1` domain.
+fn stops_function(gradient: &Gradient, model: ColorModel) -> String {
+    let mut stops: Vec<(f64, Rgba<u8>)> = gradient
+        .stops
+        .iter()
+        .map(|stop| (f64::from(stop.offset) / 100.0, stop.color))
+        .collect();
+    let (Some(&first), Some(&last)) = (stops.first(), stops.last()) else {
+        let black = model.components(Rgba([0, 0, 0, 255]));
+        return format!("<< /FunctionType 2 /Domain [0 1] /C0 [{black}] /C1 [{black}] /N 1 >>");
+    };
+    if first.0 > 0.0 {
+        stops.insert(0, (0.0, first.1));
+    }
+    if last.0 < 1.0 {
+        stops.push((1.0, last.1));
+    }
+    let segment = |from: Rgba<u8>, to: Rgba<u8>| {
+        format!(
+            "<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>",
+            model.components(from),
+            model.components(to)
+        )
+    };
+    let functions: Vec<String> = stops.windows(2).map(|w| segment(w[0].1, w[1].1)).collect();
+    let bounds: Vec<String> = stops[1..stops.len() - 1]
+        .iter()
+        .map(|stop| number(stop.0, PRECISION))
+        .collect();
+    let encode = vec!["0 1"; functions.len()].join(" ");
+    format!(
+        "<< /FunctionType 3 /Domain [0 1] /Functions [{}] /Bounds [{}] /Encode [{encode}] >>",
+        functions.join(" "),
+        bounds.join(" ")
+    )
+}
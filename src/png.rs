@@ -0,0 +1,307 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A PNG writer tuned for QR codes.
+//!
+//! A rendered code usually holds two colours, so storing it as 32-bit RGBA
+//! wastes most of every pixel. The writer picks the smallest lossless colour
+//! type the image allows — 1-bit greyscale for black on white, a 1/2/4/8-bit
+//! palette for up to 256 colours, RGB or RGBA otherwise — and can add `pHYs`
+//! resolution and `tEXt`/`iTXt` metadata chunks.
+
+use crate::QrcError;
+use image::error::{ImageError, ParameterError, ParameterErrorKind};
+use image::{Rgba, RgbaImage};
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use std::collections::{HashMap, HashSet};
+
+/// Metres per inch, for converting DPI to the `pHYs` pixels-per-metre unit.
+const METRES_PER_INCH: f64 = 0.0254;
+
+/// Options for [`QRCode::to_png_bytes_with_options`](crate::QRCode::to_png_bytes_with_options).
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{PngOptions, QRCode};
+///
+/// let qr = QRCode::from_string("https://example.com".to_string());
+/// let options = PngOptions::default()
+///     .with_dpi(300)
+///     .with_payload(true)
+///     .with_text("Title", "Signup link");
+/// let png = qr.to_png_bytes_with_options(256, &options).unwrap();
+/// assert!(png.windows(4).any(|chunk| chunk == b"pHYs"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PngOptions {
+    /// Resolution recorded in a `pHYs` chunk, in dots per inch, so print
+    /// tools place the image at its intended physical size.
+    pub dpi: Option<u32>,
+    /// Store the encoded data as text under the `Payload` keyword. The data
+    /// must be valid UTF-8.
+    pub embed_payload: bool,
+    /// Record the generator (`qrc` and its version) under `Software`.
+    pub software: bool,
+    /// Extra keyword/text pairs. Latin-1 text goes in `tEXt` chunks, other
+    /// Unicode text in uncompressed `iTXt` chunks.
+    pub text: Vec<(String, String)>,
+}
+
+impl PngOptions {
+    /// Sets [`dpi`](Self::dpi) (builder pattern).
+    #[must_use]
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.dpi = Some(dpi);
+        self
+    }
+
+    /// Sets [`embed_payload`](Self::embed_payload) (builder pattern).
+    #[must_use]
+    pub fn with_payload(mut self, embed: bool) -> Self {
+        self.embed_payload = embed;
+        self
+    }
+
+    /// Sets [`software`](Self::software) (builder pattern).
+    #[must_use]
+    pub fn with_software(mut self, software: bool) -> Self {
+        self.software = software;
+        self
+    }
+
+    /// Appends a keyword/text pair (builder pattern).
+    #[must_use]
+    pub fn with_text(mut self, keyword: &str, text: &str) -> Self {
+        self.text.push((keyword.to_string(), text.to_string()));
+        self
+    }
+}
+
+/// How pixels are stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Layout {
+    /// 1-bit greyscale: black and white only.
+    Mono,
+    /// Palette indices of `depth` bits.
+    Indexed {
+        /// Bits per index: 1, 2, 4 or 8.
+        depth: u8,
+        /// The palette, in first-seen order.
+        palette: Vec<Rgba<u8>>,
+    },
+    /// 8-bit RGB, or RGBA when `alpha` is set.
+    Truecolor {
+        /// Keep the alpha channel.
+        alpha: bool,
+    },
+}
+
+/// Encodes `img` as PNG with the metadata `text` (keyword/text pairs) and
+/// optional `dpi`.
+pub(crate) fn encode(
+    img: &RgbaImage,
+    dpi: Option<u32>,
+    text: &[(String, String)],
+) -> Result<Vec<u8>, QrcError> {
+    let (width, height) = img.dimensions();
+    if width == 0 || height == 0 {
+        return Err(QrcError::ImageEncode(ImageError::Parameter(
+            ParameterError::from_kind(ParameterErrorKind::DimensionMismatch),
+        )));
+    }
+    let layout = layout(img);
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let (depth, color_type) = match &layout {
+        Layout::Mono => (1, 0),
+        Layout::Indexed { depth, .. } => (*depth, 3),
+        Layout::Truecolor { alpha: false } => (8, 2),
+        Layout::Truecolor { alpha: true } => (8, 6),
+    };
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[depth, color_type, 0, 0, 0]);
+    chunk(&mut png, b"IHDR", &ihdr);
+
+    if let Some(dpi) = dpi {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let ppm = (f64::from(dpi) / METRES_PER_INCH).round() as u32;
+        let mut phys = Vec::with_capacity(9);
+        phys.extend_from_slice(&ppm.to_be_bytes());
+        phys.extend_from_slice(&ppm.to_be_bytes());
+        phys.push(1);
+        chunk(&mut png, b"pHYs", &phys);
+    }
+    for (keyword, value) in text {
+        text_chunk(&mut png, keyword, value)?;
+    }
+
+    if let Layout::Indexed { palette, .. } = &layout {
+        let rgb: Vec<u8> = palette
+            .iter()
+            .flat_map(|c| [c.0[0], c.0[1], c.0[2]])
+            .collect();
+        chunk(&mut png, b"PLTE", &rgb);
+        if palette.iter().any(|c| c.0[3] != 255) {
+            let alpha: Vec<u8> = palette.iter().map(|c| c.0[3]).collect();
+            chunk(&mut png, b"tRNS", &alpha);
+        }
+    }
+
+    let data = compress_to_vec_zlib(&scanlines(img, &layout), 9);
+    chunk(&mut png, b"IDAT", &data);
+    chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+/// The smallest layout that stores `img` losslessly.
+fn layout(img: &RgbaImage) -> Layout {
+    let mut palette: Vec<Rgba<u8>> = Vec::new();
+    let mut seen = HashSet::new();
+    for pixel in img.pixels() {
+        if seen.insert(pixel.0) {
+            if palette.len() == 256 {
+                return Layout::Truecolor {
+                    alpha: img.pixels().any(|p| p.0[3] != 255),
+                };
+            }
+            palette.push(*pixel);
+        }
+    }
+    let black = Rgba([0, 0, 0, 255]);
+    let white = Rgba([255, 255, 255, 255]);
+    if palette.iter().all(|&c| c == black || c == white) {
+        return Layout::Mono;
+    }
+    let depth = match palette.len() {
+        0..=2 => 1,
+        3..=4 => 2,
+        5..=16 => 4,
+        _ => 8,
+    };
+    Layout::Indexed { depth, palette }
+}
+
+/// Filtered scanlines for `layout`. Each row takes whichever of the None,
+/// Sub and Up filters gives the smallest sum of absolute differences.
+fn scanlines(img: &RgbaImage, layout: &Layout) -> Vec<u8> {
+    let index: HashMap<[u8; 4], u8> = match layout {
+        Layout::Indexed { palette, .. } => palette
+            .iter()
+            .zip(0..=u8::MAX)
+            .map(|(color, i)| (color.0, i))
+            .collect(),
+        _ => HashMap::new(),
+    };
+    let (channels, bpp) = match layout {
+        Layout::Truecolor { alpha: true } => (4, 4),
+        Layout::Truecolor { alpha: false } => (3, 3),
+        _ => (0, 1),
+    };
+    let cost = |bytes: &[u8]| -> u64 {
+        bytes
+            .iter()
+            .map(|&b| u64::from(b.min(b.wrapping_neg())))
+            .sum()
+    };
+    let mut out = Vec::new();
+    let mut previous: Vec<u8> = Vec::new();
+    for row in img.rows() {
+        let row: Vec<u8> = match layout {
+            Layout::Mono => pack(row.map(|p| u8::from(p.0[0] == 255)), 1),
+            Layout::Indexed { depth, .. } => {
+                pack(row.map(|p| index.get(&p.0).copied().unwrap_or(0)), *depth)
+            }
+            Layout::Truecolor { .. } => row.flat_map(|p| p.0.into_iter().take(channels)).collect(),
+        };
+        let sub: Vec<u8> = (0..row.len())
+            .map(|i| row[i].wrapping_sub(if i >= bpp { row[i - bpp] } else { 0 }))
+            .collect();
+        let up: Vec<u8> = (0..row.len())
+            .map(|i| row[i].wrapping_sub(previous.get(i).copied().unwrap_or(0)))
+            .collect();
+        let (filter, bytes) = [(0u8, &row), (1, &sub), (2, &up)]
+            .into_iter()
+            .min_by_key(|(_, bytes)| cost(bytes))
+            .unwrap_or((0, &row));
+        out.push(filter);
+        out.extend_from_slice(bytes);
+        previous = row;
+    }
+    out
+}
+
+/// Packs `depth`-bit samples into bytes, most significant bits first.
+fn pack(samples: impl Iterator<Item = u8>, depth: u8) -> Vec<u8> {
+    let per_byte = 8 / depth;
+    let mut out = Vec::new();
+    for (i, sample) in samples.enumerate() {
+        let slot = u8::try_from(i % usize::from(per_byte)).unwrap_or(0);
+        if slot == 0 {
+            out.push(0);
+        }
+        if let Some(last) = out.last_mut() {
+            *last |= sample << (8 - depth * (slot + 1));
+        }
+    }
+    out
+}
+
+/// Appends a `tEXt` chunk, or an uncompressed `iTXt` chunk when `text` is
+/// not Latin-1.
+fn text_chunk(png: &mut Vec<u8>, keyword: &str, text: &str) -> Result<(), QrcError> {
+    let printable = |c: char| matches!(u32::from(c), 32..=126 | 161..=255);
+    if keyword.is_empty()
+        || keyword.chars().count() > 79
+        || !keyword.chars().all(printable)
+        || keyword.starts_with(' ')
+        || keyword.ends_with(' ')
+        || keyword.contains("  ")
+    {
+        return Err(QrcError::InvalidOption(format!(
+            "PNG text keyword must be 1-79 printable Latin-1 characters, got {keyword:?}"
+        )));
+    }
+    let latin1 = |s: &str| -> Option<Vec<u8>> {
+        s.chars().map(|c| u8::try_from(u32::from(c)).ok()).collect()
+    };
+    let mut data = latin1(keyword).unwrap_or_default();
+    data.push(0);
+    if let Some(bytes) = latin1(text) {
+        data.extend_from_slice(&bytes);
+        chunk(png, b"tEXt", &data);
+    } else {
+        // Compression flag and method, then empty language and translated
+        // keyword.
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(text.as_bytes());
+        chunk(png, b"iTXt", &data);
+    }
+    Ok(())
+}
+
+/// Appends a chunk with its length and CRC.
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let length = u32::try_from(data.len()).unwrap_or(u32::MAX);
+    png.extend_from_slice(&length.to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// CRC-32 (ISO 3309) as used by PNG chunks.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
@@ -0,0 +1,479 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Rectangular Micro QR (rMQR, ISO/IEC 23941) encoding.
+//!
+//! The `qrcode` crate stops at square symbols, so the rMQR tables, bit
+//! stream and module placement live here; only the Reed-Solomon arithmetic,
+//! which is the same in both standards, is borrowed. A symbol has one finder
+//! pattern at the left, a smaller finder sub-pattern at the bottom right,
+//! corner marks in the other two corners, timing patterns along every edge
+//! and alignment patterns down the middle. Data uses a single fixed mask.
+
+use crate::QrcError;
+use qrcode::ec::create_error_correction_code;
+use qrcode::types::{Color, EcLevel, Mode};
+
+/// Number of rMQR sizes.
+pub(crate) const SIZES: usize = 32;
+
+/// A [`TABLE`] row.
+type Row = ((usize, usize), [usize; 4], [usize; 2], [usize; 2]);
+
+/// Height and width, character count indicator bits for numeric,
+/// alphanumeric, byte and kanji segments, and Reed-Solomon codewords per
+/// block and number of blocks at levels M and H, for versions 1–32: R7x43
+/// to R17x139 (ISO/IEC 23941).
+const TABLE: [Row; SIZES] = [
+    ((7, 43), [4, 3, 3, 2], [7, 10], [1, 1]),
+    ((7, 59), [5, 5, 4, 3], [9, 14], [1, 1]),
+    ((7, 77), [6, 5, 5, 4], [12, 22], [1, 1]),
+    ((7, 99), [7, 6, 5, 5], [16, 30], [1, 1]),
+    ((7, 139), [7, 6, 6, 5], [12, 22], [2, 2]),
+    ((9, 43), [5, 5, 4, 3], [9, 14], [1, 1]),
+    ((9, 59), [6, 5, 5, 4], [12, 22], [1, 1]),
+    ((9, 77), [7, 6, 5, 5], [9, 16], [2, 2]),
+    ((9, 99), [7, 6, 6, 5], [12, 22], [2, 2]),
+    ((9, 139), [8, 7, 6, 6], [18, 22], [2, 3]),
+    ((11, 27), [4, 4, 3, 2], [8, 10], [1, 1]),
+    ((11, 43), [6, 5, 5, 4], [12, 20], [1, 1]),
+    ((11, 59), [7, 6, 5, 5], [16, 16], [1, 2]),
+    ((11, 77), [7, 6, 6, 5], [12, 22], [2, 2]),
+    ((11, 99), [8, 7, 6, 6], [16, 30], [2, 2]),
+    ((11, 139), [8, 7, 7, 6], [16, 30], [3, 3]),
+    ((13, 27), [5, 5, 4, 3], [9, 14], [1, 1]),
+    ((13, 43), [6, 6, 5, 5], [14, 14], [1, 2]),
+    ((13, 59), [7, 6, 6, 5], [11, 20], [2, 2]),
+    ((13, 77), [7, 7, 6, 6], [16, 28], [2, 2]),
+    ((13, 99), [8, 7, 7, 6], [20, 26], [2, 3]),
+    ((13, 139), [8, 8, 7, 7], [14, 28], [4, 4]),
+    ((15, 43), [7, 6, 6, 5], [18, 18], [1, 2]),
+    ((15, 59), [7, 7, 6, 5], [14, 24], [2, 2]),
+    ((15, 77), [8, 7, 7, 6], [18, 24], [2, 3]),
+    ((15, 99), [8, 7, 7, 6], [24, 22], [2, 4]),
+    ((15, 139), [9, 8, 7, 7], [24, 26], [3, 5]),
+    ((17, 43), [7, 6, 6, 5], [22, 20], [1, 2]),
+    ((17, 59), [8, 7, 6, 6], [16, 30], [2, 2]),
+    ((17, 77), [8, 7, 7, 6], [22, 28], [2, 3]),
+    ((17, 99), [8, 8, 7, 6], [20, 26], [3, 4]),
+    ((17, 139), [9, 8, 8, 7], [20, 26], [4, 6]),
+];
+
+/// BCH generator of the format information, x¹² + x¹¹ + x¹⁰ + x⁹ + x⁸ + x⁵ +
+/// x² + 1.
+const FORMAT_GENERATOR: u32 = 0b1_1111_0010_0101;
+
+/// Masks applied to the format information beside the finder pattern and
+/// beside the finder sub-pattern.
+const FORMAT_MASKS: [u32; 2] = [0b01_1111_1010_1011_0010, 0b10_0000_1010_0111_1011];
+
+/// Pad codewords appended alternately after the data.
+const PADDING: [u8; 2] = [0b1110_1100, 0b0001_0001];
+
+/// The alphanumeric character set, in value order.
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Height and width in modules of version `version`, 1–32.
+pub(crate) fn dimensions(version: usize) -> (usize, usize) {
+    TABLE[version - 1].0
+}
+
+/// The version number, 1–32, of the `height`×`width` size, if there is one.
+pub(crate) fn version_of(height: usize, width: usize) -> Option<usize> {
+    TABLE
+        .iter()
+        .position(|row| row.0 == (height, width))
+        .map(|i| i + 1)
+}
+
+/// The codeword layout of one version at one error-correction level.
+#[derive(Debug)]
+struct Layout {
+    /// Character count indicator bits, as in [`TABLE`].
+    counts: [usize; 4],
+    /// Codewords the symbol holds.
+    total: usize,
+    /// Reed-Solomon codewords per block.
+    ec: usize,
+    /// Number of blocks.
+    blocks: usize,
+}
+
+impl Layout {
+    /// The layout of `version` at `ec_level`, which must be M or H. The
+    /// codewords are counted from the modules the function patterns leave.
+    fn new(version: usize, ec_level: EcLevel) -> Self {
+        let ((height, width), counts, ec, blocks) = TABLE[version - 1];
+        let mut grid = Grid::new(width, height);
+        grid.draw_function_patterns();
+        grid.draw_format(version, ec_level);
+        let free = grid
+            .modules
+            .iter()
+            .filter(|module| module.is_none())
+            .count();
+        let level = usize::from(ec_level == EcLevel::H);
+        Layout {
+            counts,
+            total: free / 8,
+            ec: ec[level],
+            blocks: blocks[level],
+        }
+    }
+
+    /// Data codewords across all blocks.
+    fn data_len(&self) -> usize {
+        self.total - self.ec * self.blocks
+    }
+
+    /// Data codewords in block `b`; the last `total % blocks` blocks hold
+    /// one more than the others.
+    fn block_len(&self, b: usize) -> usize {
+        let long = self.total % self.blocks;
+        self.total / self.blocks - self.ec + usize::from(b >= self.blocks - long)
+    }
+}
+
+/// Index into [`Layout::counts`] and the 3-bit mode indicator of `mode`.
+fn indicator(mode: Mode) -> (usize, u32) {
+    match mode {
+        Mode::Numeric => (0, 0b001),
+        Mode::Alphanumeric => (1, 0b010),
+        Mode::Byte => (2, 0b011),
+        Mode::Kanji => (3, 0b100),
+    }
+}
+
+/// Characters `data` counts as in `mode`.
+fn characters(data: &[u8], mode: Mode) -> usize {
+    if mode == Mode::Kanji {
+        data.len() / 2
+    } else {
+        data.len()
+    }
+}
+
+/// Bits taken by `data` in `mode`, headers excluded.
+fn payload_bits(data: &[u8], mode: Mode) -> usize {
+    let n = data.len();
+    match mode {
+        Mode::Numeric => 10 * (n / 3) + [0, 4, 7][n % 3],
+        Mode::Alphanumeric => 11 * (n / 2) + 6 * (n % 2),
+        Mode::Byte => 8 * n,
+        Mode::Kanji => 13 * (n / 2),
+    }
+}
+
+/// Chooses the size for `data` as a single `mode` segment: the one of
+/// smallest area among `versions` that holds it.
+///
+/// Returns the version number, 1–32, its data codewords, and the bits the
+/// segment takes.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn fit(
+    data: &[u8],
+    ec_level: EcLevel,
+    mode: Mode,
+    versions: std::ops::RangeInclusive<usize>,
+) -> Result<(usize, Vec<u8>, usize), QrcError> {
+    let (slot, indicator) = indicator(mode);
+    let mut candidates: Vec<usize> = versions.collect();
+    candidates.sort_by_key(|&version| {
+        let (height, width) = dimensions(version);
+        (height * width, version)
+    });
+    let mut shortfall = (0, 0);
+    let chosen = candidates.into_iter().find(|&version| {
+        let layout = Layout::new(version, ec_level);
+        let count = layout.counts[slot];
+        let needed = 3 + count + payload_bits(data, mode);
+        let available = layout.data_len() * 8;
+        let fits = characters(data, mode) < 1 << count && needed <= available;
+        if !fits {
+            shortfall = (needed, available);
+        }
+        fits
+    });
+    let version = chosen.ok_or(QrcError::CapacityExceeded {
+        needed: shortfall.0,
+        available: shortfall.1,
+    })?;
+
+    let layout = Layout::new(version, ec_level);
+    let capacity = layout.data_len();
+    let mut bits = Bits::default();
+    bits.push(indicator, 3);
+    bits.push(characters(data, mode) as u32, layout.counts[slot]);
+    push_payload(&mut bits, data, mode);
+    let used = bits.len;
+    // Terminator, as much of it as fits, then zeros to the byte boundary.
+    bits.push(0, 3.min(capacity * 8 - bits.len));
+    bits.push(0, (8 - bits.len % 8) % 8);
+    let mut codewords = bits.bytes;
+    for pad in PADDING.iter().cycle().take(capacity - codewords.len()) {
+        codewords.push(*pad);
+    }
+    Ok((version, codewords, used))
+}
+
+/// Appends `data` encoded in `mode`, which it has been checked to fit.
+#[allow(clippy::cast_possible_truncation)]
+fn push_payload(bits: &mut Bits, data: &[u8], mode: Mode) {
+    match mode {
+        Mode::Numeric => {
+            for group in data.chunks(3) {
+                let value = group
+                    .iter()
+                    .fold(0, |acc, &digit| acc * 10 + u32::from(digit - b'0'));
+                bits.push(value, [0, 4, 7, 10][group.len()]);
+            }
+        }
+        Mode::Alphanumeric => {
+            let value = |c: &u8| ALPHANUMERIC.iter().position(|a| a == c).unwrap_or(0) as u32;
+            for pair in data.chunks(2) {
+                match pair {
+                    [a, b] => bits.push(value(a) * 45 + value(b), 11),
+                    [a] => bits.push(value(a), 6),
+                    _ => {}
+                }
+            }
+        }
+        Mode::Byte => {
+            for &byte in data {
+                bits.push(u32::from(byte), 8);
+            }
+        }
+        Mode::Kanji => {
+            for pair in data.chunks_exact(2) {
+                let code = u32::from(u16::from_be_bytes([pair[0], pair[1]]));
+                let offset = code - if code <= 0x9FFC { 0x8140 } else { 0xC140 };
+                bits.push((offset >> 8) * 0xC0 + (offset & 0xFF), 13);
+            }
+        }
+    }
+}
+
+/// A bit stream, most significant bit first.
+#[derive(Debug, Default)]
+struct Bits {
+    /// The bytes written so far; the last may be partly filled.
+    bytes: Vec<u8>,
+    /// Bits written.
+    len: usize,
+}
+
+impl Bits {
+    /// Appends the low `count` bits of `value`.
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if self.len % 8 == 0 {
+                self.bytes.push(0);
+            }
+            if value >> i & 1 == 1 {
+                if let Some(last) = self.bytes.last_mut() {
+                    *last |= 0x80 >> (self.len % 8);
+                }
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Draws version `version` at `ec_level` holding the data codewords `data`,
+/// returning its modules row by row.
+pub(crate) fn draw(version: usize, ec_level: EcLevel, data: &[u8]) -> Vec<Color> {
+    let ((height, width), ..) = TABLE[version - 1];
+    let mut grid = Grid::new(width, height);
+    grid.draw_function_patterns();
+    grid.draw_format(version, ec_level);
+    grid.draw_data(&codewords(&Layout::new(version, ec_level), data));
+    grid.modules
+        .into_iter()
+        .map(|module| module.unwrap_or(Color::Light))
+        .collect()
+}
+
+/// The data codewords split into blocks and followed by their Reed-Solomon
+/// codewords, interleaved for placement.
+fn codewords(layout: &Layout, data: &[u8]) -> Vec<u8> {
+    let mut blocks = Vec::with_capacity(layout.blocks);
+    let mut rest = data;
+    for b in 0..layout.blocks {
+        let (block, tail) = rest.split_at(layout.block_len(b));
+        blocks.push((block, create_error_correction_code(block, layout.ec)));
+        rest = tail;
+    }
+    let mut out = Vec::with_capacity(layout.total);
+    for i in 0..layout.block_len(layout.blocks - 1) {
+        out.extend(blocks.iter().filter_map(|(block, _)| block.get(i)));
+    }
+    for i in 0..layout.ec {
+        out.extend(blocks.iter().map(|(_, ec)| ec[i]));
+    }
+    out
+}
+
+/// A symbol under construction.
+#[derive(Debug)]
+struct Grid {
+    /// Width in modules.
+    width: usize,
+    /// Height in modules.
+    height: usize,
+    /// Modules row by row, `None` until drawn.
+    modules: Vec<Option<Color>>,
+}
+
+impl Grid {
+    /// An empty `width`×`height` grid.
+    fn new(width: usize, height: usize) -> Self {
+        Grid {
+            width,
+            height,
+            modules: vec![None; width * height],
+        }
+    }
+
+    /// Sets module (`x`, `y`) to a function-pattern colour.
+    fn put(&mut self, x: usize, y: usize, dark: bool) {
+        let color = if dark { Color::Dark } else { Color::Light };
+        self.modules[y * self.width + x] = Some(color);
+    }
+
+    /// Sets module (`x`, `y`) unless it is already drawn.
+    fn fill(&mut self, x: usize, y: usize, dark: bool) {
+        if self.modules[y * self.width + x].is_none() {
+            self.put(x, y, dark);
+        }
+    }
+
+    /// Draws the finder pattern and its separator, the finder sub-pattern,
+    /// the corner marks, the alignment patterns and the timing patterns.
+    fn draw_function_patterns(&mut self) {
+        let (w, h) = (self.width, self.height);
+        let ring = |i: usize, j: usize, side: usize| {
+            let edge = i.min(j).min(side - 1 - i).min(side - 1 - j);
+            edge % 2 == 0
+        };
+        for y in 0..7 {
+            for x in 0..7 {
+                self.put(
+                    x,
+                    y,
+                    ring(x, y, 7) || (2..5).contains(&x) && (2..5).contains(&y),
+                );
+            }
+        }
+        for i in 0..8 {
+            if i < h {
+                self.put(7, i, false);
+            }
+            if h > 7 {
+                self.put(i, 7, false);
+            }
+        }
+        for y in 0..5 {
+            for x in 0..5 {
+                self.put(w - 5 + x, h - 5 + y, ring(x, y, 5));
+            }
+        }
+
+        // Corner marks: top right always, bottom left below the finder.
+        self.put(w - 1, 0, true);
+        self.put(w - 2, 0, true);
+        self.put(w - 1, 1, true);
+        self.put(w - 2, 1, false);
+        if h > 7 {
+            for x in 0..3 {
+                self.put(x, h - 1, true);
+            }
+        }
+        if h >= 11 {
+            self.put(0, h - 2, true);
+            self.put(1, h - 2, false);
+        }
+
+        let centres = alignment_centres(w);
+        for &cx in centres {
+            for j in 0..3 {
+                for i in 0..3 {
+                    let dark = i != 1 || j != 1;
+                    self.put(cx + i - 1, j, dark);
+                    self.put(cx + i - 1, h - 1 - j, dark);
+                }
+            }
+        }
+
+        for x in 0..w {
+            self.fill(x, 0, x % 2 == 0);
+            self.fill(x, h - 1, x % 2 == 0);
+        }
+        for y in 0..h {
+            for &x in [0, w - 1].iter().chain(centres) {
+                self.fill(x, y, y % 2 == 0);
+            }
+        }
+    }
+
+    /// Draws both copies of the format information: the level and version,
+    /// BCH-protected and masked differently beside each finder.
+    #[allow(clippy::cast_possible_truncation)]
+    fn draw_format(&mut self, version: usize, ec_level: EcLevel) {
+        let (w, h) = (self.width, self.height);
+        let value = u32::from(ec_level == EcLevel::H) << 5 | (version as u32 - 1);
+        let mut remainder = value << 12;
+        for shift in (0..6).rev() {
+            if remainder & 1 << (shift + 12) != 0 {
+                remainder ^= FORMAT_GENERATOR << shift;
+            }
+        }
+        let format = value << 12 | remainder;
+        let [near, far] = FORMAT_MASKS.map(|mask| format ^ mask);
+        for n in 0..18 {
+            self.put(8 + n / 5, 1 + n % 5, near >> n & 1 == 1);
+        }
+        for n in 0..15 {
+            self.put(w - 8 + n / 5, h - 6 + n % 5, far >> n & 1 == 1);
+        }
+        for n in 15..18 {
+            self.put(w - 20 + n, h - 6, far >> n & 1 == 1);
+        }
+    }
+
+    /// Places `codewords` in two-module columns from the right, alternately
+    /// upward and downward, then fills the remainder bits and applies the
+    /// data mask.
+    fn draw_data(&mut self, codewords: &[u8]) {
+        let (w, h) = (self.width, self.height);
+        let mut bits = codewords
+            .iter()
+            .flat_map(|&byte| (0..8).rev().map(move |i| byte >> i & 1 == 1));
+        let mut upward = true;
+        for right in (1..w - 1).rev().step_by(2) {
+            for step in 1..h - 1 {
+                let y = if upward { h - 1 - step } else { step };
+                for x in [right, right - 1] {
+                    let i = y * w + x;
+                    if self.modules[i].is_none() {
+                        let masked = (y / 2 + x / 3) % 2 == 0;
+                        let dark = bits.next().unwrap_or(false) != masked;
+                        self.modules[i] = Some(if dark { Color::Dark } else { Color::Light });
+                    }
+                }
+            }
+            upward = !upward;
+        }
+    }
+}
+
+/// Centre columns of the alignment patterns of a symbol `width` modules wide.
+fn alignment_centres(width: usize) -> &'static [usize] {
+    match width {
+        43 => &[21],
+        59 => &[19, 39],
+        77 => &[25, 51],
+        99 => &[23, 49, 75],
+        139 => &[27, 55, 83, 111],
+        _ => &[],
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Scannability analysis of rendered codes.
+//!
+//! Styling trades contrast for looks: blended backgrounds, small centre dots
+//! and logos all push modules toward the wrong side of a scanner's
+//! threshold. [`QRCode::scannability`](crate::QRCode::scannability) samples a
+//! rendering back onto the module grid it was drawn from, grades every
+//! module, and weighs the misread ones against the Reed-Solomon budget of
+//! the block they fall in.
+
+use crate::decode::detect::{self, Binary, Point, Transform};
+use crate::decode::{data_modules, format_modules, version_modules, Blocks};
+use crate::QrcError;
+use crate::{Symbol, SymbolType};
+use image::{Rgba, RgbaImage};
+use qrcode::{Color, Version};
+
+/// Normalised distance from the threshold below which a correctly read
+/// module counts as risky: under half the gap between the typical dark and
+/// light module.
+const RISK_MARGIN: f64 = 0.5;
+
+/// Misread modules either copy of the format or version information can
+/// absorb; both are BCH codes with a minimum distance of 7 or more.
+const METADATA_ERRORS: usize = 3;
+
+/// Detected finder trios tried as alternatives to the full-canvas geometry.
+const MAX_TRIOS: usize = 3;
+
+/// How a module of a rendering reads back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModuleStatus {
+    /// Read correctly with a comfortable margin.
+    Clear,
+    /// Read correctly, but close enough to the threshold that glare, blur or
+    /// a cheap camera could flip it.
+    Risky,
+    /// Read as the wrong colour.
+    Misread,
+}
+
+/// The result of [`QRCode::scannability`](crate::QRCode::scannability).
+///
+/// Module coordinates include the quiet zone, so (0, 0) is the top-left
+/// corner of the rendering.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{ModuleStatus, QRCode};
+///
+/// let qr = QRCode::from_string("https://example.com".to_string());
+/// let report = qr.scannability(&qr.to_image(256)).unwrap();
+/// assert!(report.passed);
+/// assert_eq!(report.misread, 0);
+/// assert_eq!(report.status(0, 0), ModuleStatus::Clear);
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScanReport {
+    /// Whether the format information, the finder patterns and every
+    /// Reed-Solomon block survive.
+    pub passed: bool,
+    /// Modules read as the wrong colour.
+    pub misread: usize,
+    /// Modules read correctly but with little margin.
+    pub risky: usize,
+    /// Codewords holding at least one misread module, per block.
+    pub block_errors: Vec<usize>,
+    /// Codewords each block can correct.
+    pub correctable: usize,
+    /// Whether a copy of the format information, and of the version
+    /// information from version 7, reads back.
+    pub format_readable: bool,
+    /// Whether the centre row and column of every finder pattern read back.
+    pub finders_intact: bool,
+    /// Side of the module grid, quiet zone included.
+    size: usize,
+    /// Expected colour of each module, row by row, `true` for dark.
+    expected: Vec<bool>,
+    /// Status of each module, row by row.
+    status: Vec<ModuleStatus>,
+}
+
+impl ScanReport {
+    /// Side of the module grid, quiet zone included.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Status of module (`x`, `y`); modules outside the grid are
+    /// [`Clear`](ModuleStatus::Clear).
+    #[must_use]
+    pub fn status(&self, x: usize, y: usize) -> ModuleStatus {
+        if x < self.size && y < self.size {
+            self.status[y * self.size + x]
+        } else {
+            ModuleStatus::Clear
+        }
+    }
+
+    /// Share of the correction budget left in the most damaged block, from
+    /// 1.0 (no errors) down to 0.0 (the code fails).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn score(&self) -> f64 {
+        if !self.passed {
+            return 0.0;
+        }
+        let worst = self.block_errors.iter().copied().max().unwrap_or(0);
+        if worst == 0 {
+            1.0
+        } else {
+            1.0 - worst as f64 / self.correctable as f64
+        }
+    }
+
+    /// Draws the grid with `module_px` pixels per module: clear modules in
+    /// dark or light grey by their intended colour, risky ones amber and
+    /// misread ones red.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image would exceed [`MAX_RASTER_BYTES`](crate::MAX_RASTER_BYTES).
+    /// Use [`try_heatmap`](Self::try_heatmap) for a fallible alternative.
+    #[must_use]
+    pub fn heatmap(&self, module_px: u32) -> RgbaImage {
+        self.try_heatmap(module_px).expect(crate::ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`heatmap`](Self::heatmap).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidOption`] if the image would exceed
+    /// [`MAX_RASTER_BYTES`](crate::MAX_RASTER_BYTES).
+    pub fn try_heatmap(&self, module_px: u32) -> Result<RgbaImage, QrcError> {
+        let module_px = module_px.max(1);
+        let Some(side) = u32::try_from(self.size)
+            .ok()
+            .and_then(|size| size.checked_mul(module_px))
+        else {
+            return Err(QrcError::InvalidOption(format!(
+                "{} modules at {module_px} px each is too large an image",
+                self.size
+            )));
+        };
+        crate::check_raster(side, side)?;
+        Ok(RgbaImage::from_fn(side, side, |px, py| {
+            let i = (py / module_px) as usize * self.size + (px / module_px) as usize;
+            match (self.status[i], self.expected[i]) {
+                (ModuleStatus::Misread, _) => Rgba([220, 30, 30, 255]),
+                (ModuleStatus::Risky, _) => Rgba([255, 176, 0, 255]),
+                (ModuleStatus::Clear, true) => Rgba([64, 64, 64, 255]),
+                (ModuleStatus::Clear, false) => Rgba([235, 235, 235, 255]),
+            }
+        }))
+    }
+}
+
+/// Grades `img`, a rendering of `code` with a `quiet`-module quiet zone.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn analyse(
+    code: &Symbol,
+    quiet: usize,
+    img: &RgbaImage,
+) -> Result<ScanReport, QrcError> {
+    let (SymbolType::Qr, Version::Normal(version)) = (code.symbol_type(), code.version()) else {
+        return Err(QrcError::InvalidOption(
+            "scannability analysis supports standard QR codes only".to_string(),
+        ));
+    };
+    if img.width() == 0 || img.height() == 0 {
+        return Err(QrcError::InvalidOption(
+            "cannot analyse an empty image".to_string(),
+        ));
+    }
+    let version = usize::try_from(version).unwrap_or(1);
+    let n = code.width();
+    let colors = code.to_colors();
+    let dark = |x: usize, y: usize| colors[y * n + x] == Color::Dark;
+
+    let luma = Luma {
+        values: detect::luma(img),
+        width: img.width() as usize,
+        height: img.height() as usize,
+    };
+    let total = n + 2 * quiet;
+    let (w, h) = (f64::from(img.width()), f64::from(img.height()));
+    let corner = |u: f64, v: f64| Point {
+        x: (u + quiet as f64) * w / total as f64,
+        y: (v + quiet as f64) * h / total as f64,
+    };
+    let side = n as f64;
+    let mut geometries: Vec<Transform> = Transform::between(
+        [(0.0, 0.0), (side, 0.0), (0.0, side), (side, side)],
+        [
+            corner(0.0, 0.0),
+            corner(side, 0.0),
+            corner(0.0, side),
+            corner(side, side),
+        ],
+    )
+    .into_iter()
+    .collect();
+    let binary = Binary::new(img, 0);
+    geometries.extend(
+        detect::locate(&binary)
+            .iter()
+            .take(MAX_TRIOS)
+            .filter_map(|finders| finders.transform(&binary, n)),
+    );
+
+    // The geometry that best separates the finder and timing patterns wins.
+    let pattern: Vec<(usize, usize)> = (0..n)
+        .flat_map(|y| (0..n).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            let finder = (x < 7 || x >= n - 7) && y < 7 || x < 7 && y >= n - 7;
+            finder || (x == 6 || y == 6) && (8..n - 8).contains(&(x + y - 6))
+        })
+        .collect();
+    let separation = |transform: &Transform| {
+        let (mut sums, mut counts) = ([0.0; 2], [0.0; 2]);
+        for &(x, y) in &pattern {
+            let side = usize::from(dark(x, y));
+            sums[side] += luma.module(transform, x as f64, y as f64);
+            counts[side] += 1.0;
+        }
+        sums[0] / counts[0] - sums[1] / counts[1]
+    };
+    let transform = geometries
+        .into_iter()
+        .max_by(|a, b| separation(a).total_cmp(&separation(b)))
+        .ok_or_else(|| QrcError::InvalidOption("cannot map the image onto the code".to_string()))?;
+
+    // Threshold halfway between the typical dark and light module.
+    let mut samples = [Vec::new(), Vec::new()];
+    let mut values = Vec::with_capacity(total * total);
+    let mut expected = Vec::with_capacity(total * total);
+    for y in 0..total {
+        for x in 0..total {
+            let (u, v) = (x as f64 - quiet as f64, y as f64 - quiet as f64);
+            let inside = (quiet..quiet + n).contains(&x) && (quiet..quiet + n).contains(&y);
+            let is_dark = inside && dark(x - quiet, y - quiet);
+            let value = luma.module(&transform, u, v);
+            if inside {
+                samples[usize::from(is_dark)].push(value);
+            }
+            values.push(value);
+            expected.push(is_dark);
+        }
+    }
+    let [light, dark_level] = samples.map(|mut s| {
+        s.sort_by(f64::total_cmp);
+        s[s.len() / 2]
+    });
+    let threshold = (light + dark_level) / 2.0;
+    let half_gap = (light - dark_level) / 2.0;
+    let status: Vec<ModuleStatus> = values
+        .iter()
+        .zip(&expected)
+        .map(|(&value, &is_dark)| {
+            let margin = if half_gap <= 0.0 {
+                -1.0
+            } else if is_dark {
+                (threshold - value) / half_gap
+            } else {
+                (value - threshold) / half_gap
+            };
+            if margin <= 0.0 {
+                ModuleStatus::Misread
+            } else if margin < RISK_MARGIN {
+                ModuleStatus::Risky
+            } else {
+                ModuleStatus::Clear
+            }
+        })
+        .collect();
+    let misread_at =
+        |x: usize, y: usize| status[(y + quiet) * total + x + quiet] == ModuleStatus::Misread;
+
+    let level = code.error_correction_level();
+    let blocks = Blocks::new(version, level);
+    let mut block_errors = vec![0; blocks.count];
+    for (codeword, b) in data_modules(version)
+        .chunks_exact(8)
+        .zip(blocks.interleaving())
+    {
+        if codeword.iter().any(|&(x, y)| misread_at(x, y)) {
+            block_errors[b] += 1;
+        }
+    }
+    let correctable = blocks.correctable();
+
+    let readable = |copies: [Vec<(usize, usize)>; 2]| {
+        copies
+            .iter()
+            .any(|copy| copy.iter().filter(|&&(x, y)| misread_at(x, y)).count() <= METADATA_ERRORS)
+    };
+    let format_readable =
+        readable(format_modules(n)) && (version < 7 || readable(version_modules(n)));
+    let finders_intact = [(0, 0), (n - 7, 0), (0, n - 7)].iter().all(|&(left, top)| {
+        (0..7).all(|i| !misread_at(left + i, top + 3) && !misread_at(left + 3, top + i))
+    });
+
+    let misread = status
+        .iter()
+        .filter(|&&s| s == ModuleStatus::Misread)
+        .count();
+    let risky = status.iter().filter(|&&s| s == ModuleStatus::Risky).count();
+    Ok(ScanReport {
+        passed: format_readable
+            && finders_intact
+            && block_errors.iter().all(|&errors| errors <= correctable),
+        misread,
+        risky,
+        block_errors,
+        correctable,
+        format_readable,
+        finders_intact,
+        size: total,
+        expected,
+        status,
+    })
+}
+
+/// Greyscale pixels of the image under analysis.
+#[derive(Debug)]
+struct Luma {
+    /// Grey level of each pixel, row by row.
+    values: Vec<u8>,
+    /// Width in pixels.
+    width: usize,
+    /// Height in pixels.
+    height: usize,
+}
+
+impl Luma {
+    /// Mean grey level over the middle of module (`u`, `v`), where a
+    /// scanner samples; points outside the image read as white.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn module(&self, transform: &Transform, u: f64, v: f64) -> f64 {
+        const OFFSETS: [f64; 5] = [0.3, 0.4, 0.5, 0.6, 0.7];
+        let mut sum = 0.0;
+        for dv in OFFSETS {
+            for du in OFFSETS {
+                let p = transform.apply(u + du, v + dv);
+                let inside = p.x >= 0.0
+                    && p.y >= 0.0
+                    && (p.x as usize) < self.width
+                    && (p.y as usize) < self.height;
+                sum += if inside {
+                    f64::from(self.values[p.y as usize * self.width + p.x as usize])
+                } else {
+                    255.0
+                };
+            }
+        }
+        sum / (OFFSETS.len() * OFFSETS.len()) as f64
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Minimum-length segmentation of the data into numeric, alphanumeric, byte
+//! and Kanji runs.
+//!
+//! Every way of splitting the data is a path through its byte positions, and
+//! the cost of a path is the exact number of bits it encodes to: a mode
+//! indicator and character count for each segment, plus the characters at
+//! the rate of their mode. Numeric and alphanumeric modes pack three and two
+//! characters together, so the search tracks where a character falls in its
+//! group. A shortest path over those states is the smallest encoding there
+//! is for the version, which the greedy split cannot promise.
+
+use crate::SegmentMode;
+use qrcode::optimize::Segment;
+use qrcode::types::{Mode, Version};
+use std::ops::Range;
+
+/// Characters alphanumeric mode encodes.
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// A run of the data encoded in one mode, as reported by
+/// [`Symbol::segments`](crate::Symbol::segments).
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{QRCode, SegmentMode};
+///
+/// let symbol = QRCode::from_string("ABC12345678".to_string())
+///     .with_mode(SegmentMode::Optimal)
+///     .to_qrcode();
+/// let spans = symbol.segments();
+/// assert_eq!(spans[0].mode, SegmentMode::Alphanumeric);
+/// assert_eq!(spans[1].mode, SegmentMode::Numeric);
+/// assert_eq!(spans[1].range, 3..11);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SegmentSpan {
+    /// The mode the run is encoded in.
+    pub mode: SegmentMode,
+    /// The bytes of the data the run covers.
+    pub range: Range<usize>,
+}
+
+impl SegmentSpan {
+    /// The span of a `qrcode` segment.
+    pub(crate) fn of(segment: &Segment) -> Self {
+        let mode = match segment.mode {
+            Mode::Numeric => SegmentMode::Numeric,
+            Mode::Alphanumeric => SegmentMode::Alphanumeric,
+            Mode::Byte => SegmentMode::Byte,
+            Mode::Kanji => SegmentMode::Kanji,
+        };
+        SegmentSpan {
+            mode,
+            range: segment.begin..segment.end,
+        }
+    }
+}
+
+/// Whether `byte` is in the alphanumeric set.
+pub(crate) fn is_alphanumeric(byte: u8) -> bool {
+    ALPHANUMERIC.contains(&byte)
+}
+
+/// Whether `pair` is a Shift JIS character that Kanji mode encodes.
+pub(crate) fn is_kanji(pair: [u8; 2]) -> bool {
+    let code = u16::from_be_bytes(pair);
+    (matches!(code, 0x8140..=0x9FFC) || matches!(code, 0xE040..=0xEBBF))
+        && matches!(pair[1], 0x40..=0xFC)
+        && pair[1] != 0x7F
+}
+
+/// Search states: where the last character sits in its mode's group.
+const NUMERIC_1: usize = 0;
+const NUMERIC_2: usize = 1;
+const NUMERIC_3: usize = 2;
+const ALPHANUMERIC_1: usize = 3;
+const ALPHANUMERIC_2: usize = 4;
+const BYTE: usize = 5;
+const KANJI: usize = 6;
+const STATES: usize = 7;
+
+/// How the best path reached a state: the position and state it came from,
+/// and whether it opened a new segment there.
+#[derive(Clone, Copy)]
+struct Step {
+    bits: usize,
+    from: usize,
+    state: usize,
+    opens: bool,
+}
+
+/// The mode a search state encodes in.
+fn mode_of(state: usize) -> Mode {
+    match state {
+        NUMERIC_1 | NUMERIC_2 | NUMERIC_3 => Mode::Numeric,
+        ALPHANUMERIC_1 | ALPHANUMERIC_2 => Mode::Alphanumeric,
+        BYTE => Mode::Byte,
+        _ => Mode::Kanji,
+    }
+}
+
+/// Whether `version` can hold segments of `mode` at all.
+fn supports(version: Version, mode: Mode) -> bool {
+    match version {
+        Version::Micro(1) => mode == Mode::Numeric,
+        Version::Micro(2) => matches!(mode, Mode::Numeric | Mode::Alphanumeric),
+        _ => true,
+    }
+}
+
+/// The segments that encode `data` in the fewest bits at `version`, or
+/// `None` if the version cannot hold the data in any mix of modes.
+///
+/// Kanji segments are only considered when `kanji` is set, since a reader
+/// decodes them as Shift JIS whatever character set the data is in. Under
+/// FNC1 (`gs1`) an alphanumeric `%` stands for a group separator, so a
+/// literal `%` is kept out of alphanumeric segments.
+pub(crate) fn optimal(
+    data: &[u8],
+    version: Version,
+    kanji: bool,
+    gs1: bool,
+) -> Option<Vec<Segment>> {
+    let n = data.len();
+    let header = |mode: Mode| version.mode_bits_count() + mode.length_bits_count(version);
+    let allowed = |mode: Mode| supports(version, mode) && (mode != Mode::Kanji || kanji);
+    let mut best: Vec<[Option<Step>; STATES]> = vec![[None; STATES]; n + 1];
+
+    for i in 0..n {
+        // Opening a segment costs the same whatever came before, so it
+        // starts from the cheapest way of reaching `i`.
+        let start = if i == 0 {
+            Some((0, STATES))
+        } else {
+            (0..STATES)
+                .filter_map(|s| best[i][s].map(|step| (step.bits, s)))
+                .min()
+        };
+        let byte = data[i];
+        let here = best[i];
+        let mut relax = |to: usize, state: usize, bits: usize, from_state: usize, opens: bool| {
+            let slot = &mut best[to][state];
+            if slot.map_or(true, |step| bits < step.bits) {
+                *slot = Some(Step {
+                    bits,
+                    from: i,
+                    state: from_state,
+                    opens,
+                });
+            }
+        };
+
+        if byte.is_ascii_digit() && allowed(Mode::Numeric) {
+            for (from, to, cost) in [
+                (NUMERIC_1, NUMERIC_2, 3),
+                (NUMERIC_2, NUMERIC_3, 3),
+                (NUMERIC_3, NUMERIC_1, 4),
+            ] {
+                if let Some(step) = here[from] {
+                    relax(i + 1, to, step.bits + cost, from, false);
+                }
+            }
+            if let Some((bits, state)) = start {
+                relax(
+                    i + 1,
+                    NUMERIC_1,
+                    bits + header(Mode::Numeric) + 4,
+                    state,
+                    true,
+                );
+            }
+        }
+        if is_alphanumeric(byte) && !(gs1 && byte == b'%') && allowed(Mode::Alphanumeric) {
+            for (from, to, cost) in [
+                (ALPHANUMERIC_1, ALPHANUMERIC_2, 5),
+                (ALPHANUMERIC_2, ALPHANUMERIC_1, 6),
+            ] {
+                if let Some(step) = here[from] {
+                    relax(i + 1, to, step.bits + cost, from, false);
+                }
+            }
+            if let Some((bits, state)) = start {
+                relax(
+                    i + 1,
+                    ALPHANUMERIC_1,
+                    bits + header(Mode::Alphanumeric) + 6,
+                    state,
+                    true,
+                );
+            }
+        }
+        if allowed(Mode::Byte) {
+            if let Some(step) = here[BYTE] {
+                relax(i + 1, BYTE, step.bits + 8, BYTE, false);
+            }
+            if let Some((bits, state)) = start {
+                relax(i + 1, BYTE, bits + header(Mode::Byte) + 8, state, true);
+            }
+        }
+        if i + 1 < n && is_kanji([byte, data[i + 1]]) && allowed(Mode::Kanji) {
+            if let Some(step) = here[KANJI] {
+                relax(i + 2, KANJI, step.bits + 13, KANJI, false);
+            }
+            if let Some((bits, state)) = start {
+                relax(i + 2, KANJI, bits + header(Mode::Kanji) + 13, state, true);
+            }
+        }
+    }
+
+    if n == 0 {
+        return Some(Vec::new());
+    }
+    let (_, mut state) = (0..STATES)
+        .filter_map(|s| best[n][s].map(|step| (step.bits, s)))
+        .min()?;
+    let mut segments = Vec::new();
+    let mut end = n;
+    let mut at = n;
+    while at > 0 {
+        let step = best[at][state]?;
+        if step.opens {
+            segments.push(Segment {
+                mode: mode_of(state),
+                begin: step.from,
+                end,
+            });
+            end = step.from;
+        }
+        at = step.from;
+        state = step.state;
+    }
+    segments.reverse();
+
+    // A run too long for its character count indicator does not fit here.
+    let fits = segments.iter().all(|segment| {
+        let chars = match segment.mode {
+            Mode::Kanji => (segment.end - segment.begin) / 2,
+            _ => segment.end - segment.begin,
+        };
+        chars >> segment.mode.length_bits_count(version) == 0
+    });
+    fits.then_some(segments)
+}
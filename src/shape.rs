@@ -0,0 +1,243 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module geometry shared by the raster and SVG renderers.
+//!
+//! The connected shapes ([`ModuleShape::Liquid`],
+//! [`ModuleShape::HorizontalBars`], [`ModuleShape::VerticalBars`] and
+//! [`ModuleShape::ConnectedDots`]) look at a module's eight neighbours to
+//! decide which corners to round and which gaps to bridge. Each module is
+//! described as a handful of [`Piece`]s in unit coordinates; the raster
+//! renderer tests pixels against them and `to_svg` traces them as outlines,
+//! so both outputs draw exactly the same outline.
+
+use crate::vector::Path;
+use crate::ModuleShape;
+
+/// Inset of bars and dots from the module edge, as a fraction of a module.
+const GAP: f64 = 0.1;
+/// Half the thickness of the bridges between connected dots.
+const BRIDGE: f64 = 0.2;
+
+/// Which of a module's eight neighbours are dark.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Neighbours {
+    /// Up, right, down, left.
+    pub(crate) sides: [bool; 4],
+    /// Up-left, up-right, down-right, down-left.
+    pub(crate) diagonals: [bool; 4],
+}
+
+/// One building block of a module outline, in unit coordinates (`0.0..=1.0`
+/// across the module).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Piece {
+    /// A rectangle at (`x`, `y`) of size `w`×`h` with corner radii
+    /// (top-left, top-right, bottom-right, bottom-left).
+    Rect {
+        /// Left edge.
+        x: f64,
+        /// Top edge.
+        y: f64,
+        /// Width.
+        w: f64,
+        /// Height.
+        h: f64,
+        /// Corner radii.
+        r: [f64; 4],
+    },
+    /// A concave fillet filling corner `corner` (0 = top-left, clockwise) of
+    /// a light module between two dark neighbours, with radius `r`.
+    Fillet {
+        /// Which corner.
+        corner: usize,
+        /// Radius of the carved-out quarter circle.
+        r: f64,
+    },
+}
+
+impl ModuleShape {
+    /// Whether the shape depends on the neighbouring modules.
+    pub(crate) fn is_connected(self) -> bool {
+        matches!(
+            self,
+            ModuleShape::Liquid
+                | ModuleShape::HorizontalBars
+                | ModuleShape::VerticalBars
+                | ModuleShape::ConnectedDots
+        )
+    }
+}
+
+/// The pieces that make up a connected-shape module that is `dark` (or light,
+/// for the fillets of [`ModuleShape::Liquid`]) with neighbours `n`.
+pub(crate) fn pieces(shape: ModuleShape, dark: bool, n: Neighbours) -> Vec<Piece> {
+    let [up, right, down, left] = n.sides;
+    if !dark {
+        if shape != ModuleShape::Liquid {
+            return Vec::new();
+        }
+        // Fill the inside corner where three dark neighbours meet.
+        let pairs = [(up, left), (up, right), (down, right), (down, left)];
+        return (0..4)
+            .filter(|&c| pairs[c].0 && pairs[c].1 && n.diagonals[c])
+            .map(|corner| Piece::Fillet { corner, r: 0.5 })
+            .collect();
+    }
+    let round = |a: bool, b: bool, r: f64| if a || b { 0.0 } else { r };
+    match shape {
+        ModuleShape::Liquid => vec![Piece::Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 1.0,
+            h: 1.0,
+            r: [
+                round(up, left, 0.5),
+                round(up, right, 0.5),
+                round(down, right, 0.5),
+                round(down, left, 0.5),
+            ],
+        }],
+        ModuleShape::HorizontalBars => {
+            let r = 0.5 - GAP;
+            vec![Piece::Rect {
+                x: 0.0,
+                y: GAP,
+                w: 1.0,
+                h: 1.0 - 2.0 * GAP,
+                r: [
+                    round(left, false, r),
+                    round(right, false, r),
+                    round(right, false, r),
+                    round(left, false, r),
+                ],
+            }]
+        }
+        ModuleShape::VerticalBars => {
+            let r = 0.5 - GAP;
+            vec![Piece::Rect {
+                x: GAP,
+                y: 0.0,
+                w: 1.0 - 2.0 * GAP,
+                h: 1.0,
+                r: [
+                    round(up, false, r),
+                    round(up, false, r),
+                    round(down, false, r),
+                    round(down, false, r),
+                ],
+            }]
+        }
+        _ => {
+            let r = 0.5 - GAP;
+            let mut out = vec![Piece::Rect {
+                x: GAP,
+                y: GAP,
+                w: 2.0 * r,
+                h: 2.0 * r,
+                r: [r; 4],
+            }];
+            let (lo, hi) = (0.5 - BRIDGE, 2.0 * BRIDGE);
+            let bridges = [
+                (up, lo, 0.0, hi, 0.5),
+                (right, 0.5, lo, 0.5, hi),
+                (down, lo, 0.5, hi, 0.5),
+                (left, 0.0, lo, 0.5, hi),
+            ];
+            for (dark, x, y, w, h) in bridges {
+                if dark {
+                    out.push(Piece::Rect {
+                        x,
+                        y,
+                        w,
+                        h,
+                        r: [0.0; 4],
+                    });
+                }
+            }
+            out
+        }
+    }
+}
+
+impl Piece {
+    /// Whether unit point (`u`, `v`) is covered by this piece.
+    pub(crate) fn contains(self, u: f64, v: f64) -> bool {
+        match self {
+            Piece::Rect { x, y, w, h, r } => rounded_rect_contains(u - x, v - y, w, h, r),
+            Piece::Fillet { corner, r } => {
+                let (cx, cy) = corner_point(corner);
+                let (dx, dy) = ((u - cx).abs(), (v - cy).abs());
+                dx <= r && dy <= r && (r - dx).hypot(r - dy) >= r
+            }
+        }
+    }
+
+    /// Appends this piece as a clockwise subpath, placed at (`ox`, `oy`) for a
+    /// module `size` units wide.
+    pub(crate) fn trace(self, path: &mut Path, ox: f64, oy: f64, size: f64) {
+        match self {
+            Piece::Rect { x, y, w, h, r } => path.rounded_rect(
+                ox + x * size,
+                oy + y * size,
+                w * size,
+                h * size,
+                r.map(|radius| radius * size),
+            ),
+            Piece::Fillet { corner, r } => {
+                // Corner point, then along one edge, arc back in, along the
+                // other edge. Clockwise for every corner.
+                let (cx, cy) = corner_point(corner);
+                let (sx, sy) = (
+                    if cx > 0.5 { -1.0 } else { 1.0 },
+                    if cy > 0.5 { -1.0 } else { 1.0 },
+                );
+                let px = |u: f64| ox + u * size;
+                let py = |v: f64| oy + v * size;
+                let radius = r * size;
+                let (a, b) = if sx * sy > 0.0 {
+                    ((cx + sx * r, cy), (cx, cy + sy * r))
+                } else {
+                    ((cx, cy + sy * r), (cx + sx * r, cy))
+                };
+                path.move_to(px(cx), py(cy));
+                path.line_to(px(a.0), py(a.1));
+                path.arc_to(radius, false, false, px(b.0), py(b.1));
+                path.close();
+            }
+        }
+    }
+}
+
+/// Unit coordinates of corner `corner` (0 = top-left, clockwise).
+fn corner_point(corner: usize) -> (f64, f64) {
+    match corner {
+        0 => (0.0, 0.0),
+        1 => (1.0, 0.0),
+        2 => (1.0, 1.0),
+        _ => (0.0, 1.0),
+    }
+}
+
+/// Whether (`x`, `y`) lies in the `w`×`h` rectangle at the origin with corner
+/// radii `r` (top-left, top-right, bottom-right, bottom-left).
+pub(crate) fn rounded_rect_contains(x: f64, y: f64, w: f64, h: f64, r: [f64; 4]) -> bool {
+    if !(0.0..=w).contains(&x) || !(0.0..=h).contains(&y) {
+        return false;
+    }
+    let corners = [
+        (r[0], r[0], r[0]),
+        (w - r[1], r[1], r[1]),
+        (w - r[2], h - r[2], r[2]),
+        (r[3], h - r[3], r[3]),
+    ];
+    for (i, (cx, cy, radius)) in corners.into_iter().enumerate() {
+        let in_corner_x = if i == 0 || i == 3 { x < cx } else { x > cx };
+        let in_corner_y = if i < 2 { y < cy } else { y > cy };
+        if radius > 0.0 && in_corner_x && in_corner_y {
+            let (dx, dy) = (x - cx, y - cy);
+            return dx * dx + dy * dy <= radius * radius;
+        }
+    }
+    true
+}
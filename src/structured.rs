@@ -0,0 +1,297 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Structured Append: one message split across up to 16 linked symbols.
+//!
+//! Each symbol opens with a 20-bit header giving its position in the
+//! sequence, the number of symbols and a parity byte computed over the whole
+//! message, so a reader can collect the symbols in any order, put them back
+//! in sequence and check that they belong together.
+
+use crate::{QRCode, QrcError, SegmentMode, SymbolType};
+use image::{imageops, Rgba, RgbaImage};
+
+/// The most symbols a Structured Append sequence can span.
+pub const MAX_SYMBOLS: u8 = 16;
+
+/// Bits the header takes at the start of each symbol: the mode indicator,
+/// the position, the count and the parity byte.
+pub(crate) const HEADER_BITS: usize = 4 + 4 + 4 + 8;
+
+/// The Structured Append header of one symbol.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::Sequence;
+///
+/// let header = Sequence::new(0, 3, b"Hello");
+/// assert_eq!((header.position, header.total), (0, 3));
+/// assert_eq!(header.parity, b'H' ^ b'e' ^ b'l' ^ b'l' ^ b'o');
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sequence {
+    /// Position of the symbol in the sequence, from 0.
+    pub position: u8,
+    /// Symbols in the sequence, 1–16.
+    pub total: u8,
+    /// XOR of every byte of the whole message.
+    pub parity: u8,
+}
+
+impl Sequence {
+    /// The header of symbol `position` of `total` carrying part of
+    /// `message`.
+    #[must_use]
+    pub fn new(position: u8, total: u8, message: &[u8]) -> Self {
+        Sequence {
+            position,
+            total,
+            parity: message.iter().fold(0, |parity, &byte| parity ^ byte),
+        }
+    }
+
+    /// Rejects positions and counts the header cannot represent.
+    pub(crate) fn validate(self) -> Result<(), QrcError> {
+        if !(1..=MAX_SYMBOLS).contains(&self.total) || self.position >= self.total {
+            return Err(QrcError::InvalidOption(format!(
+                "Structured Append position {} of {} is out of range; at most {MAX_SYMBOLS} symbols",
+                self.position, self.total
+            )));
+        }
+        Ok(())
+    }
+
+    /// The header as it is written: the mode indicator, the position, the
+    /// count less one and the parity.
+    pub(crate) fn bits(self) -> u32 {
+        0b0011 << 16
+            | u32::from(self.position) << 12
+            | u32::from(self.total - 1) << 8
+            | u32::from(self.parity)
+    }
+
+    /// Reads a header from the 16 bits after its mode indicator.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_bits(bits: u32) -> Self {
+        Sequence {
+            position: (bits >> 12 & 0xF) as u8,
+            total: (bits >> 8 & 0xF) as u8 + 1,
+            parity: (bits & 0xFF) as u8,
+        }
+    }
+}
+
+/// A message split across linked symbols, from
+/// [`QRCode::to_structured_append`](crate::QRCode::to_structured_append) or
+/// [`QRCode::split_into`](crate::QRCode::split_into).
+///
+/// Each part is a [`QRCode`] with the options of the original and its share
+/// of the data, so it renders with any of the usual methods; the symbols are
+/// only meaningful once a reader has collected and joined them all.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::QRCode;
+///
+/// let qr = QRCode::from_string("A longer message, in three parts".to_string());
+/// let parts = qr.split_into(3);
+/// assert_eq!(parts.codes().len(), 3);
+/// assert_eq!(parts.codes()[2].structured_append.unwrap().position, 2);
+/// let sheet = parts.to_sheet(4, 3);
+/// assert_eq!(sheet.height(), (21 + 8) * 4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructuredAppend {
+    /// The parts, in sequence order.
+    codes: Vec<QRCode>,
+}
+
+impl StructuredAppend {
+    /// The parts, in sequence order.
+    #[must_use]
+    pub fn codes(&self) -> &[QRCode] {
+        &self.codes
+    }
+
+    /// The parts, in sequence order.
+    #[must_use]
+    pub fn into_codes(self) -> Vec<QRCode> {
+        self.codes
+    }
+
+    /// The parity byte every symbol carries: the XOR of the whole message.
+    #[must_use]
+    pub fn parity(&self) -> u8 {
+        self.codes[0].structured_append.map_or(0, |s| s.parity)
+    }
+
+    /// Lays the symbols out left to right and top to bottom, `columns` to a
+    /// row, each at `module_px` pixels per module and centred in a cell the
+    /// size of the largest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a part cannot be rendered.
+    /// Use [`try_to_sheet`](Self::try_to_sheet) for a fallible alternative.
+    #[must_use]
+    pub fn to_sheet(&self, module_px: u32, columns: u32) -> RgbaImage {
+        self.try_to_sheet(module_px, columns)
+            .expect(crate::ENCODE_FAILED)
+    }
+
+    /// Fallible version of [`to_sheet`](Self::to_sheet).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidOption`] if `module_px` or `columns` is
+    /// `0`, the style is illegible, or the sheet would be too large.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn try_to_sheet(&self, module_px: u32, columns: u32) -> Result<RgbaImage, QrcError> {
+        if columns == 0 {
+            return Err(QrcError::InvalidOption(
+                "a sheet needs at least one column".to_string(),
+            ));
+        }
+        if module_px == 0 {
+            return Err(QrcError::InvalidOption(
+                "pixels per module must be at least 1".to_string(),
+            ));
+        }
+        // Size the sheet before drawing anything, so an oversized one fails
+        // without first rendering every symbol.
+        let symbols = self
+            .codes
+            .iter()
+            .map(|code| {
+                let qrcode = code.encode_for_render()?;
+                let size = code.grid_pixels(&qrcode, module_px)?;
+                Ok((code, qrcode, size))
+            })
+            .collect::<Result<Vec<_>, QrcError>>()?;
+        let cell_w = symbols.iter().map(|&(_, _, (w, _))| w).max().unwrap_or(0);
+        let cell_h = symbols.iter().map(|&(_, _, (_, h))| h).max().unwrap_or(0);
+        let count = symbols.len() as u32;
+        let columns = columns.min(count);
+        let rows = count.div_ceil(columns);
+        let style = &self.codes[0].style;
+        let background = if style.transparent_background {
+            Rgba([0, 0, 0, 0])
+        } else {
+            style.light
+        };
+        let (Some(width), Some(height)) = (cell_w.checked_mul(columns), cell_h.checked_mul(rows))
+        else {
+            return Err(QrcError::InvalidOption(format!(
+                "{columns} columns of {cell_w} px images is too large a sheet"
+            )));
+        };
+        let mut sheet = crate::canvas(width, height, background)?;
+        let images = symbols
+            .into_iter()
+            .map(|(code, qrcode, size)| code.render_aligned(&qrcode, module_px, size))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (i, img) in (0..).zip(&images) {
+            let x = (i % columns) * cell_w + (cell_w - img.width()) / 2;
+            let y = (i / columns) * cell_h + (cell_h - img.height()) / 2;
+            imageops::replace(&mut sheet, img, i64::from(x), i64::from(y));
+        }
+        Ok(sheet)
+    }
+}
+
+/// Splits `code` across `count` symbols, or across the fewest that hold the
+/// data when `count` is `None`.
+pub(crate) fn split(code: &QRCode, count: Option<u8>) -> Result<StructuredAppend, QrcError> {
+    if code.symbol_type != SymbolType::Qr {
+        return Err(QrcError::InvalidOption(
+            "Structured Append needs standard QR codes".to_string(),
+        ));
+    }
+    // Kanji characters are two bytes and must not be cut in half.
+    let unit = if code.mode == SegmentMode::Kanji {
+        2
+    } else {
+        1
+    };
+    let units = code.data.len() / unit;
+    let parts = |total: u8| -> Result<StructuredAppend, QrcError> {
+        let n = usize::from(total);
+        let mut codes = Vec::with_capacity(n);
+        let mut start = 0;
+        for position in 0..total {
+            // The first `units % n` symbols take one unit more.
+            let len = (units / n + usize::from(usize::from(position) < units % n)) * unit;
+            let end = if position + 1 == total {
+                code.data.len()
+            } else {
+                start + len
+            };
+            let part = QRCode {
+                data: code.data[start..end].to_vec(),
+                structured_append: Some(Sequence::new(position, total, &code.data)),
+                ..code.clone()
+            };
+            part.try_to_qrcode()?;
+            codes.push(part);
+            start = end;
+        }
+        Ok(StructuredAppend { codes })
+    };
+
+    match count {
+        Some(total) => {
+            if !(1..=MAX_SYMBOLS).contains(&total) {
+                return Err(QrcError::InvalidOption(format!(
+                    "Structured Append spans 1-{MAX_SYMBOLS} symbols, got {total}"
+                )));
+            }
+            if usize::from(total) > units.max(1) {
+                return Err(QrcError::InvalidOption(format!(
+                    "cannot split {} bytes across {total} symbols",
+                    code.data.len()
+                )));
+            }
+            parts(total)
+        }
+        None => {
+            let most = u8::try_from(units.clamp(1, usize::from(MAX_SYMBOLS))).unwrap_or(1);
+            let mut result = parts(1);
+            for total in 2..=most {
+                if !matches!(result, Err(QrcError::CapacityExceeded { .. })) {
+                    break;
+                }
+                result = parts(total);
+            }
+            result
+        }
+    }
+}
+
+/// Data codewords for one symbol of `capacity` codewords: the header, the
+/// first `len` bits of `body`, then the terminator and padding.
+pub(crate) fn codewords(sequence: Sequence, body: &[u8], len: usize, capacity: usize) -> Vec<u8> {
+    let mut out = vec![0; capacity];
+    let mut at = 0;
+    let mut push = |bit: bool| {
+        if bit {
+            out[at / 8] |= 0x80 >> (at % 8);
+        }
+        at += 1;
+    };
+    let header = sequence.bits();
+    for i in (0..HEADER_BITS).rev() {
+        push(header >> i & 1 == 1);
+    }
+    for i in 0..len {
+        push(body[i / 8] & 0x80 >> (i % 8) != 0);
+    }
+    // The terminator and the alignment bits are zeros already; pad the
+    // remaining codewords.
+    let used = (at + 4).min(capacity * 8).div_ceil(8);
+    for (slot, &pad) in out[used..].iter_mut().zip([0xEC, 0x11].iter().cycle()) {
+        *slot = pad;
+    }
+    out
+}
@@ -0,0 +1,539 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Colour styling shared by every renderer.
+//!
+//! A [`QrStyle`] is attached to a [`QRCode`](crate::QRCode) with
+//! [`with_style`](crate::QRCode::with_style) and is honoured by the raster
+//! renderers, the byte encoders, `to_svg`, `overlay_image` and the art
+//! primitives. The `try_*` entry points reject combinations whose contrast is
+//! too low for scanners to separate dark from light modules.
+//!
+//! Dark modules can also be painted with a [`Gradient`] — linear at any angle
+//! or radial, with any number of stops — across the whole symbol, and the three
+//! finder patterns ("eyes") can carry a gradient of their own.
+
+use crate::vector::number;
+use crate::QrcError;
+use image::Rgba;
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+
+/// Minimum WCAG contrast ratio between the dark and light colours that the
+/// renderers accept.
+pub const MIN_CONTRAST_RATIO: f64 = 3.0;
+
+/// Foreground/background colours for a rendered QR code.
+///
+/// # Examples
+///
+/// ```
+/// use image::Rgba;
+/// use qrc::{QRCode, QrStyle};
+///
+/// let style = QrStyle::new(Rgba([0, 51, 102, 255]), Rgba([255, 255, 240, 255]));
+/// let img = QRCode::from_string("Hello".to_string())
+///     .with_style(style)
+///     .to_png(128);
+/// assert_eq!(*img.get_pixel(0, 0), Rgba([255, 255, 240, 255]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QrStyle {
+    /// Colour of dark modules.
+    pub dark: Rgba<u8>,
+    /// Colour of light modules and the quiet zone.
+    pub light: Rgba<u8>,
+    /// Leave light modules and the quiet zone fully transparent instead of
+    /// painting them with [`light`](Self::light). Formats without an alpha
+    /// channel (JPEG) fall back to the light colour. The light colour is still
+    /// used for the contrast check, as the backdrop the code is expected to sit
+    /// on.
+    pub transparent_background: bool,
+    /// Paints dark modules with a gradient spanning the symbol instead of the
+    /// flat [`dark`](Self::dark) colour.
+    pub gradient: Option<Gradient>,
+    /// Paints each finder pattern with its own copy of this gradient, spanning
+    /// that pattern's 7×7 box. Takes precedence over
+    /// [`gradient`](Self::gradient) inside the finder patterns.
+    pub eye_gradient: Option<Gradient>,
+    /// Flat colour for the finder frames, overriding every gradient.
+    pub eye_frame: Option<Rgba<u8>>,
+    /// Flat colour for the finder centres, overriding every gradient.
+    pub eye_ball: Option<Rgba<u8>>,
+}
+
+impl Default for QrStyle {
+    fn default() -> Self {
+        QrStyle {
+            dark: Rgba([0, 0, 0, 255]),
+            light: Rgba([255, 255, 255, 255]),
+            transparent_background: false,
+            gradient: None,
+            eye_gradient: None,
+            eye_frame: None,
+            eye_ball: None,
+        }
+    }
+}
+
+impl PartialOrd for QrStyle {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QrStyle {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            self.dark.0,
+            self.light.0,
+            self.transparent_background,
+            &self.gradient,
+            &self.eye_gradient,
+            self.eye_frame.map(|c| c.0),
+            self.eye_ball.map(|c| c.0),
+        )
+            .cmp(&(
+                other.dark.0,
+                other.light.0,
+                other.transparent_background,
+                &other.gradient,
+                &other.eye_gradient,
+                other.eye_frame.map(|c| c.0),
+                other.eye_ball.map(|c| c.0),
+            ))
+    }
+}
+
+impl QrStyle {
+    /// Creates a style with the given dark and light colours on an opaque
+    /// background.
+    #[must_use]
+    pub fn new(dark: Rgba<u8>, light: Rgba<u8>) -> Self {
+        QrStyle {
+            dark,
+            light,
+            ..QrStyle::default()
+        }
+    }
+
+    /// Sets whether the background is left transparent (builder pattern).
+    #[must_use]
+    pub fn with_transparent_background(mut self, transparent: bool) -> Self {
+        self.transparent_background = transparent;
+        self
+    }
+
+    /// Paints dark modules with `gradient` (builder pattern).
+    #[must_use]
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// Paints each finder pattern with `gradient` (builder pattern).
+    #[must_use]
+    pub fn with_eye_gradient(mut self, gradient: Gradient) -> Self {
+        self.eye_gradient = Some(gradient);
+        self
+    }
+
+    /// Paints the finder frames and centres with their own flat colours
+    /// (builder pattern).
+    #[must_use]
+    pub fn with_eye_colors(mut self, frame: Rgba<u8>, ball: Rgba<u8>) -> Self {
+        self.eye_frame = Some(frame);
+        self.eye_ball = Some(ball);
+        self
+    }
+
+    /// WCAG 2.x contrast ratio between the dark and light colours, from `1.0`
+    /// (identical) to `21.0` (black on white). A translucent dark colour is
+    /// first composited over the light one, so it counts only as much as
+    /// it shows.
+    #[must_use]
+    pub fn contrast_ratio(&self) -> f64 {
+        contrast_ratio(over(self.dark, self.light), self.light)
+    }
+
+    /// Checks that the style is legible.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QrcError::InvalidOption`] if the dark colour is not darker
+    /// than the light colour (inverted codes are unreadable by many scanners)
+    /// or if their contrast ratio is below [`MIN_CONTRAST_RATIO`]; the eye
+    /// colours are held to the same rules. Translucent colours are judged
+    /// as they appear composited over the light colour. Gradients
+    /// are rejected if they have no stops, an offset above 100, a stop that is
+    /// not darker than the light colour, or a darkest stop that misses the
+    /// minimum contrast.
+    pub fn validate(&self) -> Result<(), QrcError> {
+        check_dark("dark colour", self.dark, self.light)?;
+        if let Some(frame) = self.eye_frame {
+            check_dark("eye frame colour", frame, self.light)?;
+        }
+        if let Some(ball) = self.eye_ball {
+            check_dark("eye ball colour", ball, self.light)?;
+        }
+        for gradient in self.gradient.iter().chain(&self.eye_gradient) {
+            gradient.validate(self.light)?;
+        }
+        Ok(())
+    }
+
+    /// The pixel painted for light modules and the quiet zone.
+    pub(crate) fn background(&self) -> Rgba<u8> {
+        if self.transparent_background {
+            let [r, g, b, _] = self.light.0;
+            Rgba([r, g, b, 0])
+        } else {
+            self.light
+        }
+    }
+}
+
+/// Checks that `color` (described as `what`), composited over `light`, is
+/// darker than `light` by at least [`MIN_CONTRAST_RATIO`].
+fn check_dark(what: &str, color: Rgba<u8>, light: Rgba<u8>) -> Result<(), QrcError> {
+    let color = over(color, light);
+    if relative_luminance(color) >= relative_luminance(light) {
+        return Err(QrcError::InvalidOption(format!(
+            "{what} must be darker than the light colour"
+        )));
+    }
+    let ratio = contrast_ratio(color, light);
+    if ratio < MIN_CONTRAST_RATIO {
+        return Err(QrcError::InvalidOption(format!(
+            "{what} contrast ratio {ratio:.2}:1 is below the minimum of {MIN_CONTRAST_RATIO}:1"
+        )));
+    }
+    Ok(())
+}
+
+/// A colour stop of a [`Gradient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GradientStop {
+    /// Position along the gradient, in percent (`0..=100`).
+    pub offset: u8,
+    /// Colour at this position.
+    pub color: Rgba<u8>,
+}
+
+impl PartialOrd for GradientStop {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GradientStop {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.offset, self.color.0).cmp(&(other.offset, other.color.0))
+    }
+}
+
+/// Geometry of a [`Gradient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GradientKind {
+    /// A straight ramp across the painted box. `angle` is in degrees,
+    /// clockwise from left-to-right: `0` runs left → right, `90` top → bottom.
+    Linear {
+        /// Direction of the ramp, in degrees.
+        angle: u16,
+    },
+    /// Rings from the centre of the painted box (offset `0`) out to its
+    /// corners (offset `100`).
+    Radial,
+}
+
+/// A multi-stop gradient used to paint dark modules.
+///
+/// # Examples
+///
+/// ```
+/// use image::Rgba;
+/// use qrc::{Gradient, QRCode, QrStyle};
+///
+/// let gradient = Gradient::linear(45, Rgba([0, 51, 102, 255]), Rgba([102, 0, 51, 255]))
+///     .with_stop(50, Rgba([0, 0, 0, 255]));
+/// let qr = QRCode::from_string("Hello".to_string())
+///     .with_style(QrStyle::default().with_gradient(gradient));
+/// assert!(qr.to_svg(256).contains("<linearGradient"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Gradient {
+    /// Linear or radial geometry.
+    pub kind: GradientKind,
+    /// Colour stops, kept sorted by offset.
+    pub stops: Vec<GradientStop>,
+}
+
+impl Gradient {
+    /// A two-stop linear gradient from `from` to `to` at `angle` degrees.
+    #[must_use]
+    pub fn linear(angle: u16, from: Rgba<u8>, to: Rgba<u8>) -> Self {
+        Gradient {
+            kind: GradientKind::Linear { angle },
+            stops: vec![
+                GradientStop {
+                    offset: 0,
+                    color: from,
+                },
+                GradientStop {
+                    offset: 100,
+                    color: to,
+                },
+            ],
+        }
+    }
+
+    /// A two-stop radial gradient from `inner` at the centre to `outer` at the
+    /// corners.
+    #[must_use]
+    pub fn radial(inner: Rgba<u8>, outer: Rgba<u8>) -> Self {
+        Gradient {
+            kind: GradientKind::Radial,
+            ..Gradient::linear(0, inner, outer)
+        }
+    }
+
+    /// Adds a colour stop at `offset` percent (builder pattern).
+    #[must_use]
+    pub fn with_stop(mut self, offset: u8, color: Rgba<u8>) -> Self {
+        let at = self.stops.partition_point(|stop| stop.offset <= offset);
+        self.stops.insert(at, GradientStop { offset, color });
+        self
+    }
+
+    /// Checks the stops against the `light` background colour.
+    fn validate(&self, light: Rgba<u8>) -> Result<(), QrcError> {
+        let invalid = |msg: String| Err(QrcError::InvalidOption(msg));
+        if self.stops.is_empty() {
+            return invalid("gradient has no colour stops".to_string());
+        }
+        let light_luminance = relative_luminance(light);
+        for stop in &self.stops {
+            if stop.offset > 100 {
+                return invalid(format!(
+                    "gradient stop offset {}% exceeds 100%",
+                    stop.offset
+                ));
+            }
+            if relative_luminance(over(stop.color, light)) >= light_luminance {
+                return invalid(format!(
+                    "gradient stop {} is not darker than the light colour",
+                    hex(stop.color)
+                ));
+            }
+        }
+        let darkest = self
+            .stops
+            .iter()
+            .map(|stop| contrast_ratio(over(stop.color, light), light))
+            .fold(0.0, f64::max);
+        if darkest < MIN_CONTRAST_RATIO {
+            return invalid(format!(
+                "darkest gradient stop has contrast {darkest:.2}:1, below the minimum of {MIN_CONTRAST_RATIO}:1"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Colour at normalised position (`u`, `v`) of the painted box, where
+    /// `(0, 0)` is its top-left and `(1, 1)` its bottom-right corner.
+    pub(crate) fn color_at(&self, u: f64, v: f64) -> Rgba<u8> {
+        let (du, dv) = (u - 0.5, v - 0.5);
+        let t = match self.kind {
+            GradientKind::Linear { angle } => {
+                let (sin, cos) = f64::from(angle).to_radians().sin_cos();
+                0.5 + du.mul_add(cos, dv * sin) / (cos.abs() + sin.abs())
+            }
+            GradientKind::Radial => du.hypot(dv) / std::f64::consts::FRAC_1_SQRT_2,
+        };
+        self.sample(t.clamp(0.0, 1.0) * 100.0)
+    }
+
+    /// Interpolates the stops at `offset` percent.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn sample(&self, offset: f64) -> Rgba<u8> {
+        let Some(first) = self.stops.first() else {
+            return Rgba([0, 0, 0, 255]);
+        };
+        let mut prev = first;
+        for stop in &self.stops {
+            let at = f64::from(stop.offset);
+            if offset <= at {
+                let from = f64::from(prev.offset);
+                let f = if at > from {
+                    (offset - from) / (at - from)
+                } else {
+                    1.0
+                };
+                let mut out = [0; 4];
+                for (i, channel) in out.iter_mut().enumerate() {
+                    let (a, b) = (f64::from(prev.color.0[i]), f64::from(stop.color.0[i]));
+                    *channel = (b - a).mul_add(f, a).round() as u8;
+                }
+                return Rgba(out);
+            }
+            prev = stop;
+        }
+        prev.color
+    }
+
+    /// Where the gradient runs over the box at (`x`, `y`) with width and
+    /// height `size`, matching [`color_at`](Self::color_at): the start and
+    /// end points of a linear gradient, or the centre, horizontal radius and
+    /// vertical squash of a radial one.
+    pub(crate) fn geometry(&self, x: f64, y: f64, (w, h): (f64, f64)) -> GradientGeometry {
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        match self.kind {
+            GradientKind::Linear { angle } => {
+                // `color_at` ramps along (cos / w, sin / h) in box units; the
+                // end points lie one ramp length apart along that direction.
+                let (sin, cos) = f64::from(angle).to_radians().sin_cos();
+                let reach = cos.abs() + sin.abs();
+                let (gx, gy) = (cos / (w * reach), sin / (h * reach));
+                let norm = gx.mul_add(gx, gy * gy);
+                let (dx, dy) = (gx / norm / 2.0, gy / norm / 2.0);
+                GradientGeometry::Linear([cx - dx, cy - dy, cx + dx, cy + dy])
+            }
+            GradientKind::Radial => GradientGeometry::Radial {
+                cx,
+                cy,
+                r: w * std::f64::consts::FRAC_1_SQRT_2,
+                squash: h / w,
+            },
+        }
+    }
+
+    /// SVG `<linearGradient>`/`<radialGradient>` element with id `id`, in user
+    /// space over the box at (`x`, `y`) with width and height `size`.
+    pub(crate) fn svg_def(
+        &self,
+        id: &str,
+        x: f64,
+        y: f64,
+        size: (f64, f64),
+        precision: u8,
+    ) -> String {
+        let num = |value: f64| number(value, precision);
+        let geometry = self.geometry(x, y, size);
+        let transform = match geometry.transform() {
+            Some(m) => format!(" gradientTransform=\"matrix({})\"", m.map(num).join(" ")),
+            None => String::new(),
+        };
+        let mut def = match geometry {
+            GradientGeometry::Linear([x1, y1, x2, y2]) => format!(
+                "<linearGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">",
+                num(x1),
+                num(y1),
+                num(x2),
+                num(y2)
+            ),
+            GradientGeometry::Radial { cx, cy, r, .. } => format!(
+                "<radialGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" r=\"{}\"{transform}>",
+                num(cx),
+                num(cy),
+                num(r)
+            ),
+        };
+        for stop in &self.stops {
+            let alpha = stop.color.0[3];
+            let _ = write!(
+                def,
+                "<stop offset=\"{}%\" stop-color=\"{}\"",
+                stop.offset,
+                hex(stop.color)
+            );
+            if alpha != 255 {
+                let _ = write!(def, " stop-opacity=\"{:.3}\"", f64::from(alpha) / 255.0);
+            }
+            def.push_str("/>");
+        }
+        def.push_str(match self.kind {
+            GradientKind::Linear { .. } => "</linearGradient>",
+            GradientKind::Radial => "</radialGradient>",
+        });
+        def
+    }
+}
+
+/// Placement of a [`Gradient`] in output coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum GradientGeometry {
+    /// From (`x1`, `y1`) to (`x2`, `y2`).
+    Linear([f64; 4]),
+    /// Circle centred at (`cx`, `cy`) with radius `r`, scaled vertically by
+    /// `squash` about its centre into an ellipse.
+    Radial {
+        /// Centre x.
+        cx: f64,
+        /// Centre y.
+        cy: f64,
+        /// Horizontal radius.
+        r: f64,
+        /// Ratio of the vertical to the horizontal radius.
+        squash: f64,
+    },
+}
+
+impl GradientGeometry {
+    /// The `[a b c d e f]` matrix that squashes an elliptical radial
+    /// gradient, or `None` when the geometry needs no transform.
+    pub(crate) fn transform(&self) -> Option<[f64; 6]> {
+        match *self {
+            GradientGeometry::Radial { cy, squash, .. } if (squash - 1.0).abs() > 1e-9 => {
+                Some([1.0, 0.0, 0.0, squash, 0.0, cy * (1.0 - squash)])
+            }
+            _ => None,
+        }
+    }
+}
+
+/// WCAG relative luminance of an sRGB colour, in `0.0..=1.0`.
+pub(crate) fn relative_luminance(color: Rgba<u8>) -> f64 {
+    let linear = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.040_45 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let [r, g, b, _] = color.0;
+    0.0722f64.mul_add(linear(b), 0.2126f64.mul_add(linear(r), 0.7152 * linear(g)))
+}
+
+/// `color` composited over `backdrop` by its alpha, as an opaque colour.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn over(color: Rgba<u8>, backdrop: Rgba<u8>) -> Rgba<u8> {
+    let alpha = f64::from(color.0[3]) / 255.0;
+    let mix = |c: u8, b: u8| (f64::from(c) * alpha + f64::from(b) * (1.0 - alpha)).round() as u8;
+    let [r, g, b, _] = color.0;
+    let [br, bg, bb, _] = backdrop.0;
+    Rgba([mix(r, br), mix(g, bg), mix(b, bb), 255])
+}
+
+/// WCAG contrast ratio between two colours.
+pub(crate) fn contrast_ratio(a: Rgba<u8>, b: Rgba<u8>) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// `#rrggbb` notation for SVG attributes.
+pub(crate) fn hex(color: Rgba<u8>) -> String {
+    let [r, g, b, _] = color.0;
+    format!("#{r:02X}{g:02X}{b:02X}")
+}
+
+/// SVG `fill` (plus `fill-opacity` when translucent) for `color`.
+pub(crate) fn svg_fill(color: Rgba<u8>) -> String {
+    let alpha = color.0[3];
+    if alpha == 255 {
+        format!("fill=\"{}\"", hex(color))
+    } else {
+        let opacity = f64::from(alpha) / 255.0;
+        format!("fill=\"{}\" fill-opacity=\"{opacity:.3}\"", hex(color))
+    }
+}
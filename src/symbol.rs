@@ -0,0 +1,662 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Encoded symbols and the choices that shape them.
+//!
+//! By default the data is split into its most compact mix of segment modes,
+//! placed in the smallest version that holds it, and masked with the pattern
+//! of lowest penalty. [`SymbolVersion`], [`SegmentMode`] and a mask number
+//! pin any of those down, for a consistent print layout or to match another
+//! encoder byte for byte. [`SymbolType`] trades the standard QR code for the
+//! smaller Micro QR and rectangular Micro QR symbols.
+
+use crate::segment::{self, SegmentSpan};
+use crate::structured::{self, HEADER_BITS};
+use crate::{decode, rmqr, CharacterSet, QRCode, QrcError, DEFAULT_QUIET_ZONE};
+use qrcode::bits::{encode_auto, Bits};
+use qrcode::canvas::{Canvas, MaskPattern};
+use qrcode::ec::construct_codewords;
+use qrcode::optimize::{total_encoded_len, Parser, Segment};
+use qrcode::types::{Color, EcLevel, Mode, Version};
+use std::ops::Index;
+
+/// Mask patterns in the order of their QR code numbers.
+const MASKS: [MaskPattern; 8] = [
+    MaskPattern::Checkerboard,
+    MaskPattern::HorizontalLines,
+    MaskPattern::VerticalLines,
+    MaskPattern::DiagonalLines,
+    MaskPattern::LargeCheckerboard,
+    MaskPattern::Fields,
+    MaskPattern::Diamonds,
+    MaskPattern::Meadow,
+];
+
+/// Mask patterns in the order of their Micro QR numbers.
+const MICRO_MASKS: [MaskPattern; 4] = [
+    MaskPattern::HorizontalLines,
+    MaskPattern::LargeCheckerboard,
+    MaskPattern::Diamonds,
+    MaskPattern::Meadow,
+];
+
+/// The family of symbol the data is encoded in.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{QRCode, SymbolType};
+///
+/// // M1 only detects errors, so at the default EcLevel::M the digits need M2.
+/// let micro = QRCode::from_string("12345".to_string()).with_symbol_type(SymbolType::Micro);
+/// assert_eq!(micro.to_qrcode().width(), 13);
+///
+/// let rmqr = QRCode::from_string("ABC-123".to_string()).with_symbol_type(SymbolType::Rmqr);
+/// let symbol = rmqr.to_qrcode();
+/// assert_eq!((symbol.width(), symbol.height()), (27, 11));
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SymbolType {
+    /// A standard QR code: versions 1–40, 21×21 to 177×177 modules.
+    #[default]
+    Qr,
+    /// A Micro QR code: versions M1–M4, 11×11 to 17×17 modules, with a single
+    /// finder pattern. M1 holds digits only and detects errors rather than
+    /// correcting them, so it is used only at `EcLevel::L`; M4 alone offers
+    /// `EcLevel::Q`, and no version offers `EcLevel::H`.
+    Micro,
+    /// A rectangular Micro QR code (rMQR, ISO/IEC 23941): 32 sizes from 7×43
+    /// to 17×139 modules, at `EcLevel::M` or `EcLevel::H`, with one fixed
+    /// mask. Versions are numbered 1–32 in the order of the standard, R7x43
+    /// first; [`SymbolVersion::rmqr`] looks one up by size. Automatic sizing
+    /// picks the smallest area, and with [`SegmentMode::Auto`] the data is
+    /// encoded as one segment in the most compact mode that holds all of it.
+    Rmqr,
+}
+
+impl SymbolType {
+    /// The quiet zone the specification asks for, in modules: 4 for QR codes
+    /// and 2 for Micro QR and rMQR.
+    #[must_use]
+    pub const fn quiet_zone(self) -> u32 {
+        match self {
+            SymbolType::Qr => DEFAULT_QUIET_ZONE,
+            SymbolType::Micro | SymbolType::Rmqr => 2,
+        }
+    }
+
+    /// The name used in error messages.
+    fn name(self) -> &'static str {
+        match self {
+            SymbolType::Qr => "QR code",
+            SymbolType::Micro => "Micro QR",
+            SymbolType::Rmqr => "rMQR",
+        }
+    }
+
+    /// The highest version number.
+    fn last_version(self) -> u8 {
+        match self {
+            SymbolType::Qr => 40,
+            SymbolType::Micro => 4,
+            SymbolType::Rmqr => 32,
+        }
+    }
+
+    /// The number of mask patterns.
+    fn masks(self) -> u8 {
+        match self {
+            SymbolType::Qr => 8,
+            SymbolType::Micro => 4,
+            SymbolType::Rmqr => 1,
+        }
+    }
+}
+
+/// Which symbol versions (sizes) the encoder may use.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{QRCode, SymbolVersion, Version};
+///
+/// // Keep every code in a batch the same size.
+/// let qr = QRCode::from_string("Hi".to_string()).with_version(SymbolVersion::Min(5));
+/// assert_eq!(qr.to_qrcode().version(), Version::Normal(5));
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SymbolVersion {
+    /// The smallest version that holds the data.
+    #[default]
+    Auto,
+    /// The smallest version, at least this one, that holds the data.
+    /// Versions run 1–40 for QR codes, 1–4 for Micro QR and 1–32 for rMQR.
+    Min(u8),
+    /// Exactly this version; encoding fails if the data does not fit.
+    Fixed(u8),
+}
+
+impl SymbolVersion {
+    /// The fixed rMQR version `height` modules high and `width` wide, such
+    /// as R11x43, or `None` if the standard has no such size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::SymbolVersion;
+    ///
+    /// assert_eq!(SymbolVersion::rmqr(7, 43), Some(SymbolVersion::Fixed(1)));
+    /// assert_eq!(SymbolVersion::rmqr(17, 139), Some(SymbolVersion::Fixed(32)));
+    /// assert_eq!(SymbolVersion::rmqr(7, 27), None);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn rmqr(height: usize, width: usize) -> Option<Self> {
+        rmqr::version_of(height, width).map(|version| SymbolVersion::Fixed(version as u8))
+    }
+}
+
+/// How the data is split into segments.
+///
+/// The forced modes encode all the data as one segment and reject data the
+/// mode cannot represent.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SegmentMode {
+    /// The most compact mix of modes for the data.
+    #[default]
+    Auto,
+    /// Digits `0`–`9`, at 10 bits per 3 characters.
+    Numeric,
+    /// Digits, upper-case letters, space and `$%*+-./:`, at 11 bits per 2
+    /// characters.
+    Alphanumeric,
+    /// Any bytes, at 8 bits each.
+    Byte,
+    /// Shift JIS double-byte characters, at 13 bits each.
+    Kanji,
+    /// The mix of modes that encodes the data in the fewest bits, found by
+    /// searching every split rather than by [`Auto`](Self::Auto)'s greedy
+    /// rules. It matters most for data that is mostly digits and upper-case
+    /// letters with a few other bytes, such as Wi-Fi and EMVCo payloads.
+    /// rMQR symbols, which hold a single segment, treat it as `Auto`.
+    Optimal,
+}
+
+impl SegmentMode {
+    /// The single mode all data is forced into, if any.
+    fn forced(self) -> Option<Mode> {
+        match self {
+            SegmentMode::Auto | SegmentMode::Optimal => None,
+            SegmentMode::Numeric => Some(Mode::Numeric),
+            SegmentMode::Alphanumeric => Some(Mode::Alphanumeric),
+            SegmentMode::Byte => Some(Mode::Byte),
+            SegmentMode::Kanji => Some(Mode::Kanji),
+        }
+    }
+}
+
+/// An encoded QR code: the grid of modules and the parameters chosen for it.
+///
+/// It mirrors the accessors of the `qrcode` crate's `QrCode`, and indexing
+/// with `(x, y)` gives the colour of a module. rMQR symbols are wider than
+/// they are high; every other type is square.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{Color, QRCode};
+///
+/// let symbol = QRCode::from_string("Hello".to_string()).with_mask(2).to_qrcode();
+/// assert_eq!(symbol.width(), 21);
+/// assert_eq!(symbol.mask(), 2);
+/// assert_eq!(symbol[(0, 0)], Color::Dark);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    /// Module colours, row by row.
+    modules: Vec<Color>,
+    /// Modules across, quiet zone excluded.
+    width: usize,
+    /// Modules down, quiet zone excluded.
+    height: usize,
+    /// The symbol version.
+    version: Version,
+    /// The error-correction level.
+    ec_level: EcLevel,
+    /// The mask pattern number.
+    mask: u8,
+    /// The symbol type.
+    kind: SymbolType,
+    /// The runs the data was split into.
+    segments: Vec<SegmentSpan>,
+    /// Bits the headers and segments take, terminator excluded.
+    data_bits: usize,
+    /// Bits the data codewords hold.
+    capacity_bits: usize,
+}
+
+impl Symbol {
+    /// The symbol type.
+    #[must_use]
+    pub fn symbol_type(&self) -> SymbolType {
+        self.kind
+    }
+
+    /// The symbol version: `Version::Normal` for QR codes and
+    /// `Version::Micro` for Micro QR. An rMQR size, which `Version` cannot
+    /// name, is reported as `Version::Normal` of its number, 1–32; read its
+    /// dimensions from [`width`](Self::width) and [`height`](Self::height).
+    #[must_use]
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The error-correction level.
+    #[must_use]
+    pub fn error_correction_level(&self) -> EcLevel {
+        self.ec_level
+    }
+
+    /// Modules across, quiet zone excluded.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Modules down, quiet zone excluded.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The mask pattern number: 0–7 for QR codes, 0–3 for Micro QR, and 0
+    /// for rMQR, which has a single pattern.
+    #[must_use]
+    pub fn mask(&self) -> u8 {
+        self.mask
+    }
+
+    /// The runs the data was split into and the mode of each, in order.
+    #[must_use]
+    pub fn segments(&self) -> &[SegmentSpan] {
+        &self.segments
+    }
+
+    /// Bits the encoded data takes: the segments with their mode indicators
+    /// and character counts, and any Structured Append header and ECI
+    /// designator, but not the terminator or padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use qrc::{QRCode, SegmentMode};
+    ///
+    /// let symbol = QRCode::from_string("01234567".to_string())
+    ///     .with_mode(SegmentMode::Optimal)
+    ///     .to_qrcode();
+    /// // Mode indicator, 10-bit count, then 10 + 10 + 7 bits of digits.
+    /// assert_eq!(symbol.data_bits(), 4 + 10 + 27);
+    /// assert_eq!(symbol.capacity_bits(), 16 * 8);
+    /// ```
+    #[must_use]
+    pub fn data_bits(&self) -> usize {
+        self.data_bits
+    }
+
+    /// Bits the data codewords of this version and level hold.
+    #[must_use]
+    pub fn capacity_bits(&self) -> usize {
+        self.capacity_bits
+    }
+
+    /// The module colours, row by row.
+    #[must_use]
+    pub fn to_colors(&self) -> Vec<Color> {
+        self.modules.clone()
+    }
+
+    /// Top-left corners of the 7×7 finder patterns: three on a QR code, one
+    /// on the Micro types.
+    pub(crate) fn finders(&self) -> impl Iterator<Item = (usize, usize)> {
+        let count = if self.kind == SymbolType::Qr { 3 } else { 1 };
+        [(0, 0), (self.width - 7, 0), (0, self.height - 7)]
+            .into_iter()
+            .take(count)
+    }
+}
+
+impl Index<(usize, usize)> for Symbol {
+    type Output = Color;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Color {
+        &self.modules[y * self.width + x]
+    }
+}
+
+/// Encodes the data of `code` under its symbol type, version, mode and mask
+/// choices, behind its Structured Append header, ECI designator and FNC1
+/// indicator if set.
+pub(crate) fn encode(code: &QRCode) -> Result<Symbol, QrcError> {
+    let QRCode {
+        ref data,
+        ec_level,
+        symbol_type: kind,
+        version,
+        mode,
+        mask,
+        structured_append: sequence,
+        character_set: charset,
+        gs1,
+        ..
+    } = *code;
+    let masks = kind.masks();
+    if let Some(mask) = mask.filter(|&mask| mask >= masks) {
+        let range = if masks == 1 {
+            "0".to_string()
+        } else {
+            format!("0-{}", masks - 1)
+        };
+        return Err(QrcError::InvalidOption(format!(
+            "{} mask pattern must be {range}, got {mask}",
+            kind.name()
+        )));
+    }
+    let last = kind.last_version();
+    let (first, last) = match version {
+        SymbolVersion::Auto => (1, last),
+        SymbolVersion::Min(v) => (v, last),
+        SymbolVersion::Fixed(v) => (v, v),
+    };
+    if !(1..=kind.last_version()).contains(&first) {
+        return Err(QrcError::InvalidOption(format!(
+            "{} version must be 1-{}, got {first}",
+            kind.name(),
+            kind.last_version()
+        )));
+    }
+    match (kind, ec_level) {
+        (SymbolType::Micro, EcLevel::H) => {
+            return Err(QrcError::InvalidOption(
+                "Micro QR codes do not support EcLevel::H".to_string(),
+            ));
+        }
+        (SymbolType::Rmqr, EcLevel::L | EcLevel::Q) => {
+            return Err(QrcError::InvalidOption(format!(
+                "rMQR codes support EcLevel::M and EcLevel::H, not EcLevel::{ec_level:?}"
+            )));
+        }
+        _ => {}
+    }
+    validate(data, mode)?;
+    if let Some(sequence) = sequence {
+        if kind != SymbolType::Qr {
+            return Err(QrcError::InvalidOption(format!(
+                "{} codes do not support Structured Append",
+                kind.name()
+            )));
+        }
+        sequence.validate()?;
+    }
+    if gs1 && kind != SymbolType::Qr {
+        return Err(QrcError::InvalidOption(format!(
+            "{} codes do not support GS1 data",
+            kind.name()
+        )));
+    }
+    if let Some(charset) = charset {
+        if kind != SymbolType::Qr {
+            return Err(QrcError::InvalidOption(format!(
+                "{} codes do not support ECI",
+                kind.name()
+            )));
+        }
+        if mode == SegmentMode::Kanji && charset != CharacterSet::ShiftJis {
+            return Err(QrcError::InvalidOption(format!(
+                "Kanji mode needs Shift_JIS data, not {}",
+                charset.name()
+            )));
+        }
+    }
+
+    if kind == SymbolType::Rmqr {
+        let mode = mode.forced().unwrap_or_else(|| {
+            [SegmentMode::Numeric, SegmentMode::Alphanumeric]
+                .into_iter()
+                .find(|&mode| validate(data, mode).is_ok())
+                .and_then(SegmentMode::forced)
+                .unwrap_or(Mode::Byte)
+        });
+        let versions = usize::from(first)..=usize::from(last);
+        let (number, codewords, data_bits) = rmqr::fit(data, ec_level, mode, versions)?;
+        let (height, width) = rmqr::dimensions(number);
+        return Ok(Symbol {
+            modules: rmqr::draw(number, ec_level, &codewords),
+            width,
+            height,
+            version: Version::Normal(i16::try_from(number).unwrap_or_default()),
+            ec_level,
+            mask: 0,
+            kind,
+            segments: vec![SegmentSpan::of(&Segment {
+                mode,
+                begin: 0,
+                end: data.len(),
+            })],
+            data_bits,
+            capacity_bits: codewords.len() * 8,
+        });
+    }
+
+    let automatic = version == SymbolVersion::Auto && mode == SegmentMode::Auto;
+    let plain = sequence.is_none() && charset.is_none() && !gs1;
+    let (version, codewords, segments, data_bits, capacity_bits) =
+        if kind == SymbolType::Qr && automatic && plain {
+            let bits = encode_auto(data, ec_level)
+                .map_err(|err| QrcError::from_qr(err, data, ec_level))?;
+            let version = bits.version();
+            let segments: Vec<Segment> = Parser::new(data).optimize(version).collect();
+            let capacity = Bits::new(version).max_len(ec_level).unwrap_or_default();
+            (
+                version,
+                bits.into_bytes(),
+                segments.iter().map(SegmentSpan::of).collect(),
+                total_encoded_len(&segments, version),
+                capacity,
+            )
+        } else {
+            let header = if sequence.is_some() { HEADER_BITS } else { 0 }
+                + charset.map_or(0, |charset| eci_len(charset.eci()))
+                + if gs1 { 4 } else { 0 };
+            // Kanji segments are read as Shift JIS whatever the ECI says, so
+            // other sets carry those bytes in byte mode.
+            let kanji = charset.map_or(true, |charset| charset == CharacterSet::ShiftJis);
+            let mut shortfall = (0, 0);
+            let mut found = None;
+            for v in first..=last {
+                let version = match kind {
+                    SymbolType::Micro => Version::Micro(i16::from(v)),
+                    _ => Version::Normal(i16::from(v)),
+                };
+                let segments: Vec<Segment> = match mode.forced() {
+                    Some(mode) => vec![Segment {
+                        mode,
+                        begin: 0,
+                        end: data.len(),
+                    }],
+                    None if mode == SegmentMode::Optimal => {
+                        match segment::optimal(data, version, kanji, gs1) {
+                            Some(segments) => segments,
+                            None => continue,
+                        }
+                    }
+                    None => Parser::new(data)
+                        .optimize(version)
+                        .map(|segment| match segment.mode {
+                            Mode::Kanji if !kanji => Segment {
+                                mode: Mode::Byte,
+                                ..segment
+                            },
+                            // Under FNC1 an alphanumeric `%` reads as a group
+                            // separator, so a literal one goes in byte mode.
+                            Mode::Alphanumeric
+                                if gs1 && data[segment.begin..segment.end].contains(&b'%') =>
+                            {
+                                Segment {
+                                    mode: Mode::Byte,
+                                    ..segment
+                                }
+                            }
+                            _ => segment,
+                        })
+                        .collect(),
+                };
+                let spans = segments.iter().map(SegmentSpan::of).collect();
+                let mut bits = Bits::new(version);
+                // Levels a Micro QR version lacks, and modes it cannot hold,
+                // rule the version out.
+                let Ok(available) = bits.max_len(ec_level) else {
+                    continue;
+                };
+                let needed = header + total_encoded_len(&segments, version);
+                if needed > available {
+                    shortfall = (needed, available);
+                    continue;
+                }
+                let eci = charset.map_or(Ok(()), |charset| bits.push_eci_designator(charset.eci()));
+                let fnc1 = if gs1 {
+                    bits.push_fnc1_first_position()
+                } else {
+                    Ok(())
+                };
+                if eci
+                    .and(fnc1)
+                    .and_then(|()| bits.push_segments(data, segments.into_iter()))
+                    .is_err()
+                {
+                    continue;
+                }
+                let used = bits.len() + if sequence.is_some() { HEADER_BITS } else { 0 };
+                let codewords = match sequence {
+                    Some(sequence) => {
+                        let len = bits.len();
+                        structured::codewords(sequence, &bits.into_bytes(), len, available / 8)
+                    }
+                    None if bits.push_terminator(ec_level).is_ok() => bits.into_bytes(),
+                    None => continue,
+                };
+                found = Some((version, codewords, spans, used, available));
+                break;
+            }
+            match (found, shortfall) {
+                (Some(found), _) => found,
+                // Every version was ruled out by its level or modes, not size.
+                (None, (0, 0)) => {
+                    let versions = if first == last {
+                        format!("version {first}")
+                    } else {
+                        format!("versions {first}-{last}")
+                    };
+                    return Err(QrcError::InvalidOption(format!(
+                        "{} {versions} cannot hold {mode:?} data at EcLevel::{ec_level:?}",
+                        kind.name()
+                    )));
+                }
+                (None, (needed, available)) => {
+                    return Err(QrcError::CapacityExceeded { needed, available })
+                }
+            }
+        };
+
+    let (data, ec) = construct_codewords(&codewords, version, ec_level)
+        .map_err(|err| QrcError::InvalidOption(err.to_string()))?;
+    let mut canvas = Canvas::new(version, ec_level);
+    canvas.draw_all_functional_patterns();
+    canvas.draw_data(&data, &ec);
+    let patterns: &[MaskPattern] = match kind {
+        SymbolType::Micro => &MICRO_MASKS,
+        _ => &MASKS,
+    };
+    let width = usize::try_from(version.width()).unwrap_or_default();
+    let (mask, modules) = match mask {
+        Some(mask) => {
+            let mut masked = canvas;
+            masked.apply_mask(patterns[usize::from(mask)]);
+            (mask, masked.into_colors())
+        }
+        None => {
+            let best = canvas.apply_best_mask().into_colors();
+            (applied_mask(&best, width, kind), best)
+        }
+    };
+    Ok(Symbol {
+        modules,
+        width,
+        height: width,
+        version,
+        ec_level,
+        mask,
+        kind,
+        segments,
+        data_bits,
+        capacity_bits,
+    })
+}
+
+/// The mask pattern number written in the format information of the
+/// `width`-module symbol `modules`. `Canvas::apply_best_mask` does not say
+/// which pattern it chose, so it is read back from what it drew.
+fn applied_mask(modules: &[Color], width: usize, kind: SymbolType) -> u8 {
+    let (coords, xor, bits) = match kind {
+        SymbolType::Micro => (
+            (1..=8)
+                .map(|x| (x, 8))
+                .chain((1..8).rev().map(|y| (8, y)))
+                .collect(),
+            0x4445,
+            0b11,
+        ),
+        _ => {
+            let [main, _] = decode::format_modules(width);
+            (main, 0x5412, 0b111)
+        }
+    };
+    let read = coords.into_iter().fold(0u32, |acc, (x, y)| {
+        acc << 1 | u32::from(modules[y * width + x] == Color::Dark)
+    });
+    u8::try_from((read ^ xor) >> 10 & bits).unwrap_or(0)
+}
+
+/// Bits an ECI segment announcing `designator` takes: the mode indicator and
+/// one to three bytes.
+fn eci_len(designator: u32) -> usize {
+    4 + match designator {
+        0..=127 => 8,
+        128..=16_383 => 16,
+        _ => 24,
+    }
+}
+
+/// Rejects data that a forced `mode` cannot represent.
+fn validate(data: &[u8], mode: SegmentMode) -> Result<(), QrcError> {
+    let valid = match mode {
+        SegmentMode::Auto | SegmentMode::Optimal | SegmentMode::Byte => true,
+        SegmentMode::Numeric => data.iter().all(u8::is_ascii_digit),
+        SegmentMode::Alphanumeric => data.iter().all(|&b| segment::is_alphanumeric(b)),
+        SegmentMode::Kanji => {
+            data.len() % 2 == 0
+                && data
+                    .chunks_exact(2)
+                    .all(|pair| segment::is_kanji([pair[0], pair[1]]))
+        }
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(QrcError::InvalidOption(format!(
+            "the data cannot be encoded in {mode:?} mode"
+        )))
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Text renderings for terminals, SSH banners and logs.
+//!
+//! The half-block charset packs two module rows into each line using `▀`,
+//! `▄` and `█`, which keeps modules roughly square in a typical 1:2
+//! character cell. The ASCII charset uses two characters per module instead.
+
+use image::Rgba;
+use std::fmt::Write as _;
+
+/// Characters used by [`QRCode::to_terminal`](crate::QRCode::to_terminal).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TerminalCharset {
+    /// Unicode half blocks, two modules per character cell (default).
+    #[default]
+    HalfBlock,
+    /// `##` and two spaces per module, one line per module row, for
+    /// terminals and fonts without block elements.
+    Ascii,
+}
+
+/// Options for [`QRCode::to_terminal`](crate::QRCode::to_terminal).
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{QRCode, TerminalCharset, TerminalOptions};
+///
+/// let qr = QRCode::from_string("Hello".to_string()).with_quiet_zone(1);
+/// let text = qr.to_terminal(&TerminalOptions::default().with_charset(TerminalCharset::Ascii));
+/// assert_eq!(text.lines().count(), 23);
+/// assert!(text.lines().nth(1).unwrap().starts_with("  ##############"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TerminalOptions {
+    /// Which characters to draw with.
+    pub charset: TerminalCharset,
+    /// Wrap each line in 24-bit ANSI colour escapes using the
+    /// [`QrStyle`](crate::QrStyle) colours, so the code scans whatever the
+    /// terminal theme.
+    pub ansi: bool,
+    /// Draw the light modules instead of the dark ones, for light-on-dark
+    /// terminals without [`ansi`](Self::ansi) colours. Ignored when `ansi` is
+    /// set, since the colours are then explicit.
+    pub invert: bool,
+}
+
+impl TerminalOptions {
+    /// Sets [`charset`](Self::charset) (builder pattern).
+    #[must_use]
+    pub fn with_charset(mut self, charset: TerminalCharset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Sets [`ansi`](Self::ansi) (builder pattern).
+    #[must_use]
+    pub fn with_ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Sets [`invert`](Self::invert) (builder pattern).
+    #[must_use]
+    pub fn with_invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+}
+
+/// Renders a `width`×`height` module grid where `is_dark(x, y)` reports each
+/// module. ANSI escapes paint glyphs in `dark` over the `light` background,
+/// or over the terminal's own background when `light` is `None`.
+pub(crate) fn render(
+    (width, height): (usize, usize),
+    is_dark: impl Fn(usize, usize) -> bool,
+    options: &TerminalOptions,
+    dark: Rgba<u8>,
+    light: Option<Rgba<u8>>,
+) -> String {
+    let invert = options.invert && !options.ansi;
+    let drawn = |x: usize, y: usize| y < height && is_dark(x, y) != invert;
+    let mut out = String::new();
+    let rows = match options.charset {
+        TerminalCharset::HalfBlock => height.div_ceil(2),
+        TerminalCharset::Ascii => height,
+    };
+    for row in 0..rows {
+        if options.ansi {
+            let [r, g, b, _] = dark.0;
+            let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+            if let Some(Rgba([r, g, b, _])) = light {
+                let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+            }
+        }
+        for x in 0..width {
+            match options.charset {
+                TerminalCharset::HalfBlock => {
+                    out.push(match (drawn(x, 2 * row), drawn(x, 2 * row + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    });
+                }
+                TerminalCharset::Ascii => {
+                    out.push_str(if drawn(x, row) { "##" } else { "  " });
+                }
+            }
+        }
+        if options.ansi {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,198 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Physical lengths and print sizing.
+//!
+//! Printers place pixels on a fixed grid, so a code only keeps every module
+//! the same size when each one spans a whole number of device dots. A
+//! [`PrintSize`] states what matters — the space available or the smallest
+//! acceptable module (the X-dimension) — and the printer resolution;
+//! [`QRCode::print_layout`](crate::QRCode::print_layout) turns it into a
+//! [`PrintLayout`] of whole dots per module.
+
+use crate::QrcError;
+
+/// Points per inch, the unit of PDF and PostScript user space.
+pub(crate) const POINTS_PER_INCH: f64 = 72.0;
+/// Millimetres per inch.
+const MM_PER_INCH: f64 = 25.4;
+
+/// A physical length.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::Length;
+///
+/// assert_eq!(Length::Inches(1.0).to_points(), 72.0);
+/// assert!((Length::Millimetres(25.4).to_inches() - 1.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Length {
+    /// Millimetres.
+    Millimetres(f64),
+    /// Inches.
+    Inches(f64),
+    /// PostScript points (1/72 inch).
+    Points(f64),
+}
+
+impl Length {
+    /// The length in inches.
+    #[must_use]
+    pub fn to_inches(self) -> f64 {
+        match self {
+            Length::Millimetres(mm) => mm / MM_PER_INCH,
+            Length::Inches(inches) => inches,
+            Length::Points(points) => points / POINTS_PER_INCH,
+        }
+    }
+
+    /// The length in millimetres.
+    #[must_use]
+    pub fn to_millimetres(self) -> f64 {
+        self.to_inches() * MM_PER_INCH
+    }
+
+    /// The length in PostScript points.
+    #[must_use]
+    pub fn to_points(self) -> f64 {
+        self.to_inches() * POINTS_PER_INCH
+    }
+
+    /// The length in points, rejecting zero, negative, non-finite and
+    /// larger-than-`max` values.
+    pub(crate) fn checked_points(self, what: &str, max: f64) -> Result<f64, QrcError> {
+        let points = self.to_points();
+        if points.is_finite() && points > 0.0 && points <= max {
+            Ok(points)
+        } else {
+            let limit = if max.is_finite() {
+                format!(" and at most {max} pt")
+            } else {
+                String::new()
+            };
+            Err(QrcError::InvalidOption(format!(
+                "{what} must be positive{limit}, got {self:?}"
+            )))
+        }
+    }
+}
+
+/// What a [`PrintSize`] holds fixed.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum PrintTarget {
+    /// The largest code, quiet zone included, that fits in this side length.
+    Fit(Length),
+    /// The smallest code whose modules are at least this wide.
+    MinModule(Length),
+}
+
+/// A physical size request for a printer of a given resolution.
+///
+/// # Examples
+///
+/// ```
+/// use qrc::{Length, PrintSize, QRCode};
+///
+/// // Modules of at least 0.33 mm on a 300 dpi label printer.
+/// let size = PrintSize::min_module(Length::Millimetres(0.33), 300);
+/// let layout = QRCode::from_string("Hello".to_string()).print_layout(&size).unwrap();
+/// assert_eq!(layout.module_px, 4);
+/// assert_eq!(layout.width_px, 29 * 4);
+/// assert!(layout.module_size.to_millimetres() >= 0.33);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct PrintSize {
+    /// The size constraint.
+    pub target: PrintTarget,
+    /// Printer resolution, in dots per inch.
+    pub dpi: u32,
+}
+
+impl PrintSize {
+    /// The largest code that fits in `side` at `dpi`.
+    #[must_use]
+    pub fn fit(side: Length, dpi: u32) -> Self {
+        PrintSize {
+            target: PrintTarget::Fit(side),
+            dpi,
+        }
+    }
+
+    /// The smallest code whose modules are at least `module` wide at `dpi`.
+    #[must_use]
+    pub fn min_module(module: Length, dpi: u32) -> Self {
+        PrintSize {
+            target: PrintTarget::MinModule(module),
+            dpi,
+        }
+    }
+
+    /// Whole dots per module for a symbol `modules` wide, quiet zone
+    /// included.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn layout(&self, modules: u32) -> Result<PrintLayout, QrcError> {
+        if self.dpi == 0 {
+            return Err(QrcError::InvalidOption(
+                "DPI must be at least 1".to_string(),
+            ));
+        }
+        let dpi = f64::from(self.dpi);
+        let module_px = match self.target {
+            PrintTarget::Fit(side) => {
+                let side = side.checked_points("print size", f64::INFINITY)? / POINTS_PER_INCH;
+                let px = (side * dpi / f64::from(modules) + 1e-9).floor();
+                if px < 1.0 {
+                    return Err(QrcError::InvalidOption(format!(
+                        "{modules} modules do not fit in {side:.3} in at {} dpi",
+                        self.dpi
+                    )));
+                }
+                px
+            }
+            PrintTarget::MinModule(module) => {
+                let module = module.checked_points("module size", f64::INFINITY)? / POINTS_PER_INCH;
+                (module * dpi - 1e-9).ceil().max(1.0)
+            }
+        };
+        let width = module_px * f64::from(modules);
+        if width > f64::from(u32::MAX) {
+            return Err(QrcError::InvalidOption(format!(
+                "{width} px is too large an image"
+            )));
+        }
+        let (module_px, width_px) = (module_px as u32, width as u32);
+        let inches = |px: u32| Length::Inches(f64::from(px) / dpi);
+        Ok(PrintLayout {
+            dpi: self.dpi,
+            modules,
+            module_px,
+            width_px,
+            module_size: inches(module_px),
+            size: inches(width_px),
+        })
+    }
+}
+
+/// The integer pixel grid chosen for a [`PrintSize`].
+///
+/// Pass [`width_px`](Self::width_px) to the raster renderers (with
+/// [`RasterScaling::Exact`](crate::RasterScaling::Exact) it maps exactly onto
+/// whole modules), or [`size`](Self::size) to the vector ones, so every
+/// output has the same physical dimensions.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct PrintLayout {
+    /// Printer resolution, in dots per inch.
+    pub dpi: u32,
+    /// Symbol width plus both quiet zones, in modules.
+    pub modules: u32,
+    /// Dots per module.
+    pub module_px: u32,
+    /// Side of the image, in dots.
+    pub width_px: u32,
+    /// Resulting module width (X-dimension).
+    pub module_size: Length,
+    /// Resulting side of the code, quiet zone included.
+    pub size: Length,
+}
@@ -0,0 +1,362 @@
+// Copyright © 2022-2026 QR Code Library (QRC). All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Resolution-independent outlines shared by the vector writers.
+//!
+//! Module shapes, finder patterns and connected pieces are all traced into a
+//! [`Path`] of absolute moves, lines and circular arcs, grouped into painted
+//! [`Layer`]s. The SVG writer turns a path into compact relative path data;
+//! the PDF writer walks the same segments with arcs converted to cubic
+//! Béziers.
+
+use crate::Gradient;
+use image::Rgba;
+use std::f64::consts::FRAC_PI_2;
+use std::fmt::Write as _;
+
+/// How a [`Layer`] is painted.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Fill {
+    /// A flat colour.
+    Solid(Rgba<u8>),
+    /// A gradient spanning the box at (`x`, `y`) with width and height
+    /// `size`.
+    /// Layers sharing a `name` share one gradient definition.
+    Gradient {
+        /// Identifier of the gradient definition.
+        name: String,
+        /// The gradient.
+        gradient: Gradient,
+        /// Left edge of the painted box.
+        x: f64,
+        /// Top edge of the painted box.
+        y: f64,
+        /// Width and height of the painted box.
+        size: (f64, f64),
+    },
+}
+
+/// One filled outline of the rendered symbol.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Layer {
+    /// The outline.
+    pub(crate) path: Path,
+    /// Its paint.
+    pub(crate) fill: Fill,
+    /// Fill with the even-odd rule (finder frames) instead of nonzero.
+    pub(crate) even_odd: bool,
+}
+
+/// A drawing command with arcs replaced by cubic Béziers, as used by PDF and
+/// PostScript.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Op {
+    /// Starts a new subpath.
+    Move(f64, f64),
+    /// Straight line.
+    Line(f64, f64),
+    /// Cubic Bézier: two control points, then the end point.
+    Curve([f64; 6]),
+    /// Closes the current subpath.
+    Close,
+}
+
+/// One drawing command, in absolute output coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Segment {
+    /// Starts a new subpath at (`x`, `y`).
+    MoveTo(f64, f64),
+    /// Straight line to (`x`, `y`).
+    LineTo(f64, f64),
+    /// Circular arc of radius `r` to (`x`, `y`), with SVG's large-arc and
+    /// sweep flags (`sweep` is clockwise on screen).
+    ArcTo {
+        /// Radius.
+        r: f64,
+        /// Take the longer of the two possible arcs.
+        large: bool,
+        /// Travel clockwise (on screen, y pointing down).
+        sweep: bool,
+        /// End point x.
+        x: f64,
+        /// End point y.
+        y: f64,
+    },
+    /// Closes the current subpath.
+    Close,
+}
+
+/// A sequence of subpaths.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Path {
+    /// The drawing commands, in order.
+    pub(crate) segments: Vec<Segment>,
+}
+
+impl Path {
+    /// Whether nothing has been drawn.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Starts a new subpath.
+    pub(crate) fn move_to(&mut self, x: f64, y: f64) {
+        self.segments.push(Segment::MoveTo(x, y));
+    }
+
+    /// Adds a straight line.
+    pub(crate) fn line_to(&mut self, x: f64, y: f64) {
+        self.segments.push(Segment::LineTo(x, y));
+    }
+
+    /// Adds a circular arc; a zero radius degenerates to a line.
+    pub(crate) fn arc_to(&mut self, r: f64, large: bool, sweep: bool, x: f64, y: f64) {
+        if r > 0.0 {
+            self.segments.push(Segment::ArcTo {
+                r,
+                large,
+                sweep,
+                x,
+                y,
+            });
+        } else {
+            self.line_to(x, y);
+        }
+    }
+
+    /// Closes the current subpath.
+    pub(crate) fn close(&mut self) {
+        self.segments.push(Segment::Close);
+    }
+
+    /// Appends a clockwise rectangle at (`x`, `y`) of size `w`×`h` with corner
+    /// radii `r` (top-left, top-right, bottom-right, bottom-left).
+    pub(crate) fn rounded_rect(&mut self, x: f64, y: f64, w: f64, h: f64, r: [f64; 4]) {
+        let [tl, tr, br, bl] = r;
+        self.move_to(x + tl, y);
+        self.line_to(x + w - tr, y);
+        if tr > 0.0 {
+            self.arc_to(tr, false, true, x + w, y + tr);
+        }
+        self.line_to(x + w, y + h - br);
+        if br > 0.0 {
+            self.arc_to(br, false, true, x + w - br, y + h);
+        }
+        self.line_to(x + bl, y + h);
+        if bl > 0.0 {
+            self.arc_to(bl, false, true, x, y + h - bl);
+        }
+        if tl > 0.0 {
+            self.line_to(x, y + tl);
+            self.arc_to(tl, false, true, x + tl, y);
+        }
+        self.close();
+    }
+
+    /// Appends a clockwise circle centred at (`cx`, `cy`).
+    pub(crate) fn circle(&mut self, cx: f64, cy: f64, r: f64) {
+        self.move_to(cx - r, cy);
+        self.arc_to(r, true, true, cx + r, cy);
+        self.arc_to(r, true, true, cx - r, cy);
+        self.close();
+    }
+
+    /// SVG path data using relative commands, with coordinates rounded to
+    /// `precision` decimals. Offsets are taken between rounded points, so
+    /// rounding never accumulates along a subpath.
+    pub(crate) fn to_svg(&self, precision: u8) -> String {
+        let round = |v: f64| round(v, precision);
+        let num = |v: f64| number(v, precision);
+        let mut d = String::new();
+        let (mut cx, mut cy) = (0.0, 0.0);
+        let (mut sx, mut sy) = (0.0, 0.0);
+        for segment in &self.segments {
+            match *segment {
+                Segment::MoveTo(x, y) => {
+                    (cx, cy) = (round(x), round(y));
+                    (sx, sy) = (cx, cy);
+                    let _ = write!(d, "M{} {}", num(cx), num(cy));
+                }
+                Segment::LineTo(x, y) => {
+                    let (x, y) = (round(x), round(y));
+                    let (dx, dy) = (x - cx, y - cy);
+                    if dy == 0.0 {
+                        let _ = write!(d, "h{}", num(dx));
+                    } else if dx == 0.0 {
+                        let _ = write!(d, "v{}", num(dy));
+                    } else {
+                        let _ = write!(d, "l{} {}", num(dx), num(dy));
+                    }
+                    (cx, cy) = (x, y);
+                }
+                Segment::ArcTo {
+                    r,
+                    large,
+                    sweep,
+                    x,
+                    y,
+                } => {
+                    let (x, y) = (round(x), round(y));
+                    let r = num(r);
+                    let _ = write!(
+                        d,
+                        "a{r} {r} 0 {} {} {} {}",
+                        u8::from(large),
+                        u8::from(sweep),
+                        num(x - cx),
+                        num(y - cy)
+                    );
+                    (cx, cy) = (x, y);
+                }
+                Segment::Close => {
+                    d.push('z');
+                    (cx, cy) = (sx, sy);
+                }
+            }
+        }
+        d
+    }
+}
+
+impl Path {
+    /// The path with every arc approximated by cubic Béziers of at most a
+    /// quarter turn each.
+    pub(crate) fn to_cubics(&self) -> Vec<Op> {
+        let mut out = Vec::with_capacity(self.segments.len());
+        let (mut cx, mut cy) = (0.0, 0.0);
+        let (mut sx, mut sy) = (0.0, 0.0);
+        for segment in &self.segments {
+            match *segment {
+                Segment::MoveTo(x, y) => {
+                    (cx, cy, sx, sy) = (x, y, x, y);
+                    out.push(Op::Move(x, y));
+                }
+                Segment::LineTo(x, y) => {
+                    (cx, cy) = (x, y);
+                    out.push(Op::Line(x, y));
+                }
+                Segment::ArcTo {
+                    r,
+                    large,
+                    sweep,
+                    x,
+                    y,
+                } => {
+                    arc_to_cubics(&mut out, (cx, cy), r, large, sweep, (x, y));
+                    (cx, cy) = (x, y);
+                }
+                Segment::Close => {
+                    (cx, cy) = (sx, sy);
+                    out.push(Op::Close);
+                }
+            }
+        }
+        out
+    }
+
+    /// Postfix path operators (`x y m`, `x y l`, `x1 y1 x2 y2 x y c`, `h` in
+    /// PDF) with the operator names given in `ops` as move, line, curve and
+    /// close.
+    pub(crate) fn to_postfix(&self, ops: [&str; 4], precision: u8) -> String {
+        let [move_to, line_to, curve_to, close] = ops;
+        let num = |v: f64| number(v, precision);
+        let mut out = String::new();
+        for op in self.to_cubics() {
+            match op {
+                Op::Move(x, y) => {
+                    let _ = writeln!(out, "{} {} {move_to}", num(x), num(y));
+                }
+                Op::Line(x, y) => {
+                    let _ = writeln!(out, "{} {} {line_to}", num(x), num(y));
+                }
+                Op::Curve(c) => {
+                    let c = c.map(num);
+                    let _ = writeln!(
+                        out,
+                        "{} {} {} {} {} {} {curve_to}",
+                        c[0], c[1], c[2], c[3], c[4], c[5]
+                    );
+                }
+                Op::Close => {
+                    let _ = writeln!(out, "{close}");
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Appends the SVG-style circular arc from `from` to `to` as cubic Béziers
+/// (endpoint-to-centre conversion, SVG implementation notes F.6.5).
+fn arc_to_cubics(
+    out: &mut Vec<Op>,
+    from: (f64, f64),
+    r: f64,
+    large: bool,
+    sweep: bool,
+    to: (f64, f64),
+) {
+    let (hx, hy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+    let half = hx.hypot(hy);
+    if half == 0.0 {
+        return;
+    }
+    // Radii too small to span the endpoints are scaled up, as in SVG.
+    let r = r.max(half);
+    let mut k = ((r * r - half * half) / (half * half)).max(0.0).sqrt();
+    if large == sweep {
+        k = -k;
+    }
+    let cx = k * hy + (from.0 + to.0) / 2.0;
+    let cy = -k * hx + (from.1 + to.1) / 2.0;
+    let start = (from.1 - cy).atan2(from.0 - cx);
+    let mut delta = (to.1 - cy).atan2(to.0 - cx) - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * std::f64::consts::PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * std::f64::consts::PI;
+    }
+    let pieces = (delta.abs() / FRAC_PI_2 - 1e-9).ceil().max(1.0);
+    let step = delta / pieces;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan() * r;
+    let mut angle = start;
+    let mut remaining = pieces;
+    while remaining > 0.0 {
+        let next = angle + step;
+        let (sa, ca) = angle.sin_cos();
+        let (sb, cb) = next.sin_cos();
+        let end = if remaining > 1.0 {
+            (cx + r * cb, cy + r * sb)
+        } else {
+            to
+        };
+        out.push(Op::Curve([
+            cx + r * ca - handle * sa,
+            cy + r * sa + handle * ca,
+            cx + r * cb + handle * sb,
+            cy + r * sb - handle * cb,
+            end.0,
+            end.1,
+        ]));
+        angle = next;
+        remaining -= 1.0;
+    }
+}
+
+/// Rounds `value` to `precision` decimals.
+fn round(value: f64, precision: u8) -> f64 {
+    let scale = 10f64.powi(i32::from(precision));
+    (value * scale).round() / scale
+}
+
+/// Formats `value` with at most `precision` decimals and no trailing zeros.
+pub(crate) fn number(value: f64, precision: u8) -> String {
+    let mut s = format!("{:.*}", usize::from(precision), value);
+    if s.contains('.') {
+        s.truncate(s.trim_end_matches('0').trim_end_matches('.').len());
+    }
+    if s == "-0" {
+        s.remove(0);
+    }
+    s
+}
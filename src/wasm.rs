@@ -6,7 +6,8 @@
 
 use wasm_bindgen::prelude::*;
 
-use crate::{EcLevel, ModuleShape, QRCode};
+use crate::{EcLevel, EyeShape, EyeStyle, ModuleShape, QRCode, QrStyle};
+use image::Rgba;
 
 /// A QR code generator for use from JavaScript / WASM.
 #[wasm_bindgen]
@@ -41,22 +42,61 @@ impl WasmQRCode {
 
     /// Sets the module shape.
     ///
-    /// Valid values: `"square"`, `"rounded"`, `"circle"`, `"diamond"`.
+    /// Valid values: `"square"`, `"rounded"`, `"circle"`, `"diamond"`,
+    /// `"liquid"`, `"hbars"`, `"vbars"`, `"dots"`.
     #[wasm_bindgen(js_name = "setShape")]
     pub fn set_shape(&mut self, shape: &str) {
         let s = match shape {
             "rounded" => ModuleShape::RoundedSquare,
             "circle" => ModuleShape::Circle,
             "diamond" => ModuleShape::Diamond,
+            "liquid" => ModuleShape::Liquid,
+            "hbars" => ModuleShape::HorizontalBars,
+            "vbars" => ModuleShape::VerticalBars,
+            "dots" => ModuleShape::ConnectedDots,
             _ => ModuleShape::Square,
         };
         self.inner.shape = s;
     }
 
-    /// Returns the QR code as an SVG string.
+    /// Sets the finder-pattern frame and centre shapes.
+    ///
+    /// Valid values: `"square"`, `"rounded"`, `"circle"`, `"leaf"`; pass
+    /// `"module"` as the frame to draw the eyes with the module shape again.
+    #[wasm_bindgen(js_name = "setEyeShape")]
+    pub fn set_eye_shape(&mut self, frame: &str, ball: &str) {
+        let parse = |shape: &str| match shape {
+            "rounded" => EyeShape::RoundedSquare,
+            "circle" => EyeShape::Circle,
+            "leaf" => EyeShape::Leaf,
+            _ => EyeShape::Square,
+        };
+        self.inner.eye_style = match frame {
+            "module" => None,
+            _ => Some(EyeStyle::new(parse(frame), parse(ball))),
+        };
+    }
+
+    /// Sets the quiet-zone width, in modules.
+    #[wasm_bindgen(js_name = "setQuietZone")]
+    pub fn set_quiet_zone(&mut self, modules: u32) {
+        self.inner.quiet_zone = modules;
+    }
+
+    /// Sets the dark and light colours from `#rrggbb` strings and whether the
+    /// background is transparent. Unparseable colours leave the style
+    /// unchanged.
+    #[wasm_bindgen(js_name = "setColors")]
+    pub fn set_colors(&mut self, dark: &str, light: &str, transparent: bool) {
+        if let (Some(dark), Some(light)) = (parse_hex(dark), parse_hex(light)) {
+            self.inner.style = QrStyle::new(dark, light).with_transparent_background(transparent);
+        }
+    }
+
+    /// Returns the QR code as an SVG string (empty if the data does not fit).
     #[wasm_bindgen(js_name = "toSvg")]
     pub fn to_svg(&self, width: u32) -> String {
-        self.inner.to_svg(width)
+        self.inner.try_to_svg(width).unwrap_or_default()
     }
 
     /// Returns PNG-encoded bytes.
@@ -71,3 +111,13 @@ impl WasmQRCode {
         self.inner.to_jpg(width).unwrap_or_default()
     }
 }
+
+/// Parses `#rrggbb` (the leading `#` is optional) into an opaque colour.
+fn parse_hex(hex: &str) -> Option<Rgba<u8>> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(Rgba([channel(0)?, channel(2)?, channel(4)?, 255]))
+}
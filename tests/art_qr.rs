@@ -1,7 +1,8 @@
 //! Offline art-QR primitives: ControlNet control image + image blend.
 
 use image::{ImageBuffer, Rgba, RgbaImage};
-use qrc::{BlendOptions, EcLevel, QRCode};
+use qrc::decode::decode;
+use qrc::{BlendOptions, EcLevel, QRCode, QrcError};
 
 #[test]
 fn control_image_is_square_and_at_least_requested_size() {
@@ -42,3 +43,113 @@ fn blend_accepts_empty_background() {
     let art = QRCode::from_string("x".to_string()).blend_image(&empty, &BlendOptions::default());
     assert!(art.width() > 0);
 }
+
+#[test]
+fn blended_overlaid_and_watermarked_codes_still_decode() {
+    let url = "https://example.com";
+    let qr = QRCode::from_string(url.to_string()).with_ec_level(EcLevel::H);
+    let bg = ImageBuffer::from_fn(64, 64, |x, y| {
+        Rgba([(x * 4) as u8, (y * 4) as u8, 128, 255])
+    });
+    let decoded = decode(&qr.blend_image(&bg, &BlendOptions::default())).unwrap();
+    assert_eq!(decoded.text(), Some(url));
+
+    let logo = ImageBuffer::from_pixel(60, 60, Rgba([200, 0, 0, 255]));
+    let decoded = decode(&qr.overlay_image(&logo)).unwrap();
+    assert_eq!(decoded.text(), Some(url));
+    // The logo covers modules that error correction has to repair.
+    assert!(decoded.corrected > 0);
+
+    let mut marked = qr.to_image(300);
+    QRCode::add_image_watermark(
+        &mut marked,
+        &ImageBuffer::from_pixel(40, 40, Rgba([0, 0, 255, 96])),
+    );
+    assert_eq!(decode(&marked).unwrap().text(), Some(url));
+}
+
+/// Black and white stripes: the hardest background for a blend to hide.
+fn stripes() -> RgbaImage {
+    RgbaImage::from_fn(200, 200, |x, y| {
+        if (x / 7 + y / 5) % 2 == 0 {
+            Rgba([0, 0, 0, 255])
+        } else {
+            Rgba([255, 255, 255, 255])
+        }
+    })
+}
+
+#[test]
+fn clean_and_default_blended_codes_pass_scannability() {
+    let qr = QRCode::from_string("https://example.com/scannability".to_string());
+    let report = qr.scannability(&qr.to_image(300)).unwrap();
+    assert!(report.passed && report.format_readable && report.finders_intact);
+    assert_eq!((report.misread, report.risky), (0, 0));
+    assert!((report.score() - 1.0).abs() < f64::EPSILON);
+
+    let art = qr.blend_image(&stripes(), &BlendOptions::default());
+    let report = qr.scannability(&art).unwrap();
+    assert!(report.passed);
+    assert_eq!(report.misread, 0);
+}
+
+#[test]
+fn weak_blend_fails_scannability() {
+    let qr = QRCode::from_string("https://example.com/scannability".to_string());
+    let opts = BlendOptions {
+        strength: 0.1,
+        dot_ratio: 0.1,
+        ..BlendOptions::default()
+    };
+    let report = qr.scannability(&qr.blend_image(&stripes(), &opts)).unwrap();
+    assert!(!report.passed);
+    assert!(report.misread > 0 && report.risky > 0);
+    assert!(report.block_errors.iter().any(|&e| e > report.correctable));
+    assert!(report.score().abs() < f64::EPSILON);
+}
+
+#[test]
+fn scannability_weighs_damage_against_the_ec_level() {
+    let hole = |level| {
+        let qr = QRCode::from_string("https://example.com/scannability".to_string())
+            .with_ec_level(level);
+        let mut img = qr.to_image(330);
+        let w = img.width();
+        for y in w * 3 / 8..w * 5 / 8 {
+            for x in w * 3 / 8..w * 5 / 8 {
+                img.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+        qr.scannability(&img).unwrap()
+    };
+    let low = hole(EcLevel::L);
+    assert!(!low.passed);
+    let high = hole(EcLevel::H);
+    assert!(high.passed);
+    assert!(high.misread > 0 && high.score() < 1.0);
+    assert_eq!(high.block_errors.len(), 4);
+
+    // Damage shows up red in the heatmap, at the centre of the grid.
+    let heatmap = high.heatmap(5);
+    let side = high.size() as u32 * 5;
+    assert_eq!(heatmap.dimensions(), (side, side));
+    assert!(heatmap.pixels().any(|p| p.0 == [220, 30, 30, 255]));
+    assert_eq!(heatmap.get_pixel(0, 0).0, [235, 235, 235, 255]);
+    assert!(matches!(
+        high.try_heatmap(u32::MAX),
+        Err(QrcError::InvalidOption(_))
+    ));
+}
+
+#[test]
+fn scannability_finds_a_code_placed_inside_a_larger_image() {
+    let qr = QRCode::from_string("https://example.com/scannability".to_string());
+    let mut canvas = RgbaImage::from_pixel(500, 420, Rgba([255, 255, 255, 255]));
+    image::imageops::overlay(&mut canvas, &qr.to_image(300), 120, 60);
+    let report = qr.scannability(&canvas).unwrap();
+    assert!(report.passed);
+    assert_eq!(report.misread, 0);
+
+    let empty = RgbaImage::new(0, 0);
+    assert!(qr.scannability(&empty).is_err());
+}
@@ -5,7 +5,7 @@
 
 use image::{ImageBuffer, Rgba, RgbaImage};
 use qrc::payload::vcard::BusinessCard;
-use qrc::{BlendOptions, EcLevel, ModuleShape, QRCode};
+use qrc::{BlendOptions, EcLevel, ModuleShape, QRCode, QrcError};
 use std::collections::HashMap;
 
 const URL: &str = "https://example.com/coverage";
@@ -37,6 +37,66 @@ fn try_to_qrcode_reports_capacity_error() {
     assert!(QRCode::from_string(URL.to_string()).try_to_qrcode().is_ok());
 }
 
+#[test]
+#[allow(deprecated)]
+fn try_variants_return_errors_instead_of_panicking() {
+    let qr = QRCode::from_string("Z".repeat(8000));
+    let logo: RgbaImage = ImageBuffer::from_pixel(4, 4, Rgba([0, 0, 0, 255]));
+
+    assert!(qr.try_to_png(64).is_err());
+    assert!(qr.try_to_image(64).is_err());
+    assert!(qr.try_to_svg(64).is_err());
+    assert!(qr.try_colorize(Rgba([255, 0, 0, 255])).is_err());
+    assert!(qr.try_resize(64, 64).is_err());
+    assert!(qr.try_overlay_image(&logo).is_err());
+    assert!(qr.try_to_control_image(256).is_err());
+    assert!(qr.try_blend_image(&logo, &BlendOptions::default()).is_err());
+    assert!(qr.to_png_bytes(64).is_err());
+    assert!(qr.to_gif(64).is_err());
+    assert!(qr.to_jpg(64).is_err());
+    assert!(QRCode::combine_qr_codes(&[qr]).is_err());
+}
+
+#[test]
+fn capacity_error_reports_needed_and_available_bits() {
+    let Err(err) = QRCode::from_bytes(vec![0u8; 3000])
+        .with_ec_level(EcLevel::H)
+        .try_to_qrcode()
+    else {
+        panic!("3000 bytes cannot fit at EcLevel::H");
+    };
+    match err {
+        QrcError::CapacityExceeded { needed, available } => {
+            assert!(needed > available);
+            assert_eq!(available, 10208); // version 40-H data capacity
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+    assert!(err.to_string().contains("10208"));
+}
+
+#[test]
+fn error_display_and_source() {
+    use std::error::Error;
+
+    let opt = QRCode::from_string(URL.to_string())
+        .set_encoding_format("latin-1")
+        .unwrap_err();
+    assert!(matches!(opt, QrcError::InvalidOption(_)));
+    assert!(opt.to_string().contains("latin-1"));
+    assert!(opt.source().is_none());
+
+    let encode = QRCode::from_string(URL.to_string())
+        .to_png_bytes(0)
+        .unwrap_err();
+    assert!(matches!(encode, QrcError::ImageEncode(_)));
+    assert!(encode.source().is_some());
+    assert!(encode.to_string().starts_with("image encoding failed"));
+
+    let payload = QrcError::InvalidPayload("bad IBAN".to_string());
+    assert_eq!(payload.to_string(), "invalid payload: bad IBAN");
+}
+
 // --- Raster / vector outputs ----------------------------------------------
 
 #[test]
@@ -140,6 +200,7 @@ fn overlay_watermark_and_art() {
 // --- Batch / combine / dynamic / multilanguage / compress ------------------
 
 #[test]
+#[allow(deprecated)]
 fn batch_combine_dynamic_multilanguage_compress() {
     let codes = QRCode::batch_generate_qr_codes(vec!["a".into(), "b".into()]);
     assert_eq!(codes.len(), 2);
@@ -167,7 +228,7 @@ fn batch_combine_dynamic_multilanguage_compress() {
 fn encoding_format_ok_and_error() {
     let qr = QRCode::from_string(URL.to_string());
     let updated = qr.set_encoding_format("utf-8").unwrap();
-    assert_eq!(updated.get_encoding_format(), "utf-8");
+    assert_eq!(updated.get_encoding_format(), "UTF-8");
     assert!(qr.set_encoding_format("latin-1").is_err());
 }
 
@@ -212,10 +273,18 @@ fn wasm_bindings_cover_every_branch() {
     for lvl in ["L", "M", "Q", "H", "x"] {
         qr.set_ec_level(lvl); // "x" hits the default arm
     }
-    for shape in ["square", "rounded", "circle", "diamond", "x"] {
+    for shape in [
+        "square", "rounded", "circle", "diamond", "liquid", "hbars", "vbars", "dots", "x",
+    ] {
         qr.set_shape(shape); // "x" hits the default arm
     }
-    assert!(qr.to_svg(128).contains("<svg"));
+    qr.set_quiet_zone(2);
+    for (frame, ball) in [("rounded", "circle"), ("leaf", "x"), ("module", "")] {
+        qr.set_eye_shape(frame, ball); // "x" hits the default arm
+    }
+    qr.set_colors("zz", "#FFFFFF", false); // ignored
+    qr.set_colors("#003366", "fffff0", true);
+    assert!(qr.to_svg(128).contains("fill=\"#003366\""));
     assert!(!qr.to_png_bytes(128).is_empty());
     assert!(!qr.to_jpg(128).is_empty());
     // width 0 -> inner Err -> the binding's `unwrap_or_default` returns empty.
@@ -0,0 +1,155 @@
+//! Reading rendered codes back with the built-in decoder.
+
+use image::{imageops, ImageBuffer, Rgba, RgbaImage};
+use qrc::decode::decode;
+use qrc::{EcLevel, EyeShape, EyeStyle, ModuleShape, QRCode, QrcError};
+
+const URL: &str = "https://example.com/menu?table=12";
+
+/// Decodes `img` and returns the payload as text.
+fn text(img: &RgbaImage) -> String {
+    decode(img).unwrap().text().unwrap().to_string()
+}
+
+/// Paints a white square of `side` pixels centred on `img`.
+fn blank_centre(img: &mut RgbaImage, side: u32) {
+    let (x0, y0) = ((img.width() - side) / 2, (img.height() - side) / 2);
+    for y in y0..y0 + side {
+        for x in x0..x0 + side {
+            img.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+        }
+    }
+}
+
+// --- Metadata ---------------------------------------------------------------
+
+#[test]
+fn reports_version_level_and_payload() {
+    for (data, level, version) in [
+        ("Hello", EcLevel::L, 1),
+        (URL, EcLevel::M, 3),
+        ("0123456789", EcLevel::Q, 1),
+        ("HELLO WORLD $%*+-./:", EcLevel::H, 2),
+    ] {
+        let qr = QRCode::from_string(data.to_string()).with_ec_level(level);
+        let decoded = decode(&qr.to_image(300)).unwrap();
+        assert_eq!(decoded.text(), Some(data));
+        assert_eq!((decoded.ec_level, decoded.version), (level, version));
+        assert!(decoded.mask < 8);
+        assert_eq!(decoded.corrected, 0);
+    }
+}
+
+#[test]
+fn reads_version_information_of_large_symbols() {
+    // Version 7+ carries version information; multiple blocks interleave.
+    let data = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+    let qr = QRCode::from_string(data.clone()).with_ec_level(EcLevel::Q);
+    let decoded = decode(&qr.to_image(800)).unwrap();
+    assert!(decoded.version > 7);
+    assert_eq!(decoded.text(), Some(data.as_str()));
+}
+
+#[test]
+fn returns_kanji_as_shift_jis() {
+    // "点茗" in Shift JIS, which the encoder packs in Kanji mode.
+    let sjis = vec![0x93, 0x5F, 0xE4, 0xAA];
+    let decoded = decode(&QRCode::new(sjis.clone()).to_image(200)).unwrap();
+    assert_eq!(decoded.data, sjis);
+}
+
+// --- Geometry ---------------------------------------------------------------
+
+#[test]
+fn handles_rotation_and_mirroring() {
+    let img = QRCode::from_string(URL.to_string()).to_image(300);
+    for turned in [
+        imageops::rotate90(&img),
+        imageops::rotate180(&img),
+        imageops::rotate270(&img),
+        imageops::flip_horizontal(&img),
+    ] {
+        assert_eq!(text(&turned), URL);
+    }
+}
+
+#[test]
+fn handles_perspective() {
+    let img = QRCode::from_string(URL.to_string()).to_image(300);
+    // A keystone homography: the bottom edge is about a quarter narrower,
+    // as if photographed from above.
+    let warped = ImageBuffer::from_fn(300, 300, |x, y| {
+        let depth = 1.0 - 0.0008 * f64::from(y);
+        let sx = 150.0 + (f64::from(x) - 150.0) / depth;
+        let sy = f64::from(y) * 0.76 / depth;
+        if (0.0..300.0).contains(&sx) && (0.0..300.0).contains(&sy) {
+            *img.get_pixel(sx as u32, sy as u32)
+        } else {
+            Rgba([255, 255, 255, 255])
+        }
+    });
+    assert_eq!(text(&warped), URL);
+}
+
+#[test]
+fn handles_small_modules_and_no_quiet_zone() {
+    let qr = QRCode::from_string(URL.to_string());
+    assert_eq!(text(&qr.to_image_scaled(1)), URL);
+    assert_eq!(text(&qr.clone().with_quiet_zone(0).to_image(250)), URL);
+}
+
+#[test]
+fn reads_every_module_and_eye_shape() {
+    let qr = QRCode::from_string(URL.to_string());
+    for shape in [
+        ModuleShape::Circle,
+        ModuleShape::Diamond,
+        ModuleShape::Liquid,
+        ModuleShape::HorizontalBars,
+        ModuleShape::VerticalBars,
+        ModuleShape::ConnectedDots,
+    ] {
+        assert_eq!(
+            text(&qr.clone().with_shape(shape).to_image(400)),
+            URL,
+            "{shape:?}"
+        );
+    }
+    for eye in [EyeShape::Circle, EyeShape::RoundedSquare, EyeShape::Leaf] {
+        let styled = qr.clone().with_eye_style(EyeStyle::new(eye, eye));
+        assert_eq!(text(&styled.to_image(400)), URL, "{eye:?}");
+    }
+}
+
+// --- Error correction -------------------------------------------------------
+
+#[test]
+fn corrects_damage_within_the_level_budget() {
+    let mut strong = QRCode::from_string(URL.to_string())
+        .with_ec_level(EcLevel::H)
+        .to_image_scaled(10);
+    let mut weak = QRCode::from_string(URL.to_string())
+        .with_ec_level(EcLevel::L)
+        .to_image_scaled(10);
+    blank_centre(&mut strong, 70);
+    blank_centre(&mut weak, 70);
+
+    let decoded = decode(&strong).unwrap();
+    assert_eq!(decoded.text(), Some(URL));
+    assert!(decoded.corrected > 0);
+    match decode(&weak) {
+        Err(QrcError::Decode(msg)) => assert!(msg.contains("errors"), "{msg}"),
+        other => panic!("expected a correction failure, got {other:?}"),
+    }
+}
+
+#[test]
+fn rejects_images_without_a_code() {
+    let blank: RgbaImage = ImageBuffer::from_pixel(120, 120, Rgba([255, 255, 255, 255]));
+    let empty: RgbaImage = ImageBuffer::new(0, 0);
+    for img in [blank, empty] {
+        let err = decode(&img).unwrap_err();
+        assert!(matches!(err, QrcError::Decode(_)));
+        assert_eq!(err.to_string(), "decoding failed: no QR code found");
+    }
+}
@@ -1,9 +1,15 @@
-//! Structured payload builders: Wi-Fi, MeCard and EMVCo merchant payments.
+//! Structured payload builders: Wi-Fi, MeCard, EMVCo merchant payments, EPC
+//! credit transfers, GS1 product data and Swiss QR-bills.
 
 use qrc::payload::emvco::{MerchantAccount, MerchantPayment};
+use qrc::payload::epc::{is_valid_bic, is_valid_iban, CreditTransfer, MAX_BYTES};
+use qrc::payload::gs1::{check_digit, DigitalLink, ElementString, GROUP_SEPARATOR};
 use qrc::payload::mecard::MeCard;
+use qrc::payload::swiss_qr_bill::{
+    is_qr_iban, qrr_check_digit, Address, Currency, QrBill, Reference, CROSS_SIZE_MM, QR_SIZE_MM,
+};
 use qrc::payload::wifi::{WifiNetwork, WifiSecurity};
-use qrc::QRCode;
+use qrc::{CharacterSet, EcLevel, QRCode, QrcError, SegmentMode, SymbolType};
 
 /// Asserts that `payload` encodes to a valid QR code (correct version selected,
 /// no panic) — i.e. the builder output is within QR capacity and encodable.
@@ -124,3 +130,375 @@ fn emvco_static_payment_and_tag_clamping() {
     let s = MerchantPayment::new(high, "840", "US", "M", "C").to_emvco();
     assert!(s.contains("51050001g")); // clamped tag 51, inner = 00||01||g
 }
+
+// --- EPC -------------------------------------------------------------------
+
+#[test]
+fn epc_credit_transfer_lines() {
+    let transfer = CreditTransfer::new("Red Cross Belgium", "be72 0000 0000 1616")
+        .bic("BPOTBEB1")
+        .amount_cents(1000)
+        .purpose("CHAR")
+        .text("Urgency fund");
+    assert_eq!(
+        transfer.to_epc_string().unwrap(),
+        "BCD\n002\n1\nSCT\nBPOTBEB1\nRed Cross Belgium\nBE72000000001616\nEUR10.00\nCHAR\n\nUrgency fund"
+    );
+
+    // Without a BIC or amount, and with a structured reference and a note.
+    let s = CreditTransfer::new("Acme GmbH", "DE89370400440532013000")
+        .amount_cents(1_234_567)
+        .reference("RF18539007547034")
+        .information("Invoice 42")
+        .to_epc_string()
+        .unwrap();
+    assert_eq!(
+        s,
+        "BCD\n002\n1\nSCT\n\nAcme GmbH\nDE89370400440532013000\nEUR12345.67\n\nRF18539007547034\n\nInvoice 42"
+    );
+    let s = CreditTransfer::new("Acme GmbH", "DE89370400440532013000")
+        .to_epc_string()
+        .unwrap();
+    assert!(s.ends_with("\nDE89370400440532013000"));
+
+    assert!(is_valid_iban("GB82 WEST 1234 5698 7654 32"));
+    assert!(!is_valid_iban("GB82 WEST 1234 5698 7654 3"));
+    assert!(!is_valid_iban("DE8937040044053201300!"));
+    assert!(is_valid_bic("DEUTDEFF500"));
+    assert!(!is_valid_bic("DEUTDEF"));
+}
+
+#[test]
+fn epc_rejects_invalid_transfers() {
+    let base = || CreditTransfer::new("Acme", "DE89370400440532013000");
+    let invalid =
+        |transfer: CreditTransfer| matches!(transfer.to_bytes(), Err(QrcError::InvalidPayload(_)));
+    assert!(!invalid(base()));
+    assert!(invalid(CreditTransfer::new(
+        "Acme",
+        "DE88370400440532013000"
+    )));
+    assert!(invalid(base().bic("DEUT1EFF")));
+    assert!(invalid(
+        base().reference("RF18539007547034").text("Invoice")
+    ));
+    assert!(invalid(base().reference("RF19539007547034")));
+    assert!(invalid(base().amount_cents(0)));
+    assert!(invalid(base().amount_cents(100_000_000_000)));
+    assert!(!invalid(base().amount_cents(99_999_999_999)));
+    assert!(invalid(base().purpose("char")));
+    assert!(invalid(base().text("x".repeat(141))));
+    assert!(invalid(base().information("two\nlines")));
+    assert!(invalid(CreditTransfer::new(" ", "DE89370400440532013000")));
+    assert!(invalid(base().charset(CharacterSet::ShiftJis)));
+    assert!(invalid(base().charset(CharacterSet::Iso8859_1).text("5 €")));
+
+    // Every field is within its own limit, but two-byte characters take
+    // the payload past the byte limit.
+    let long = CreditTransfer::new("é".repeat(70), "DE89370400440532013000")
+        .text("é".repeat(100))
+        .information("x".repeat(2));
+    assert!(long.to_epc_string().is_ok());
+    assert!(invalid(long));
+    assert_eq!(MAX_BYTES, 331);
+}
+
+#[test]
+fn epc_qr_code_uses_level_m_and_the_chosen_charset() {
+    let transfer = CreditTransfer::new("Café Müller", "DE89370400440532013000")
+        .amount_cents(450)
+        .charset(CharacterSet::Iso8859_15)
+        .text("Rechnung 7 - 5 €");
+    let bytes = transfer.to_bytes().unwrap();
+    assert_eq!(&bytes[..10], b"BCD\n002\n8\n");
+    assert!(bytes.contains(&0xA4)); // € in ISO-8859-15
+
+    let qr = transfer.to_qr_code().unwrap();
+    assert_eq!(qr.ec_level, EcLevel::M);
+    assert_eq!(qr.character_set, None);
+    let decoded = qrc::decode::decode(&qr.to_image(300)).unwrap();
+    assert_eq!(decoded.ec_level, EcLevel::M);
+    assert_eq!(decoded.eci, None);
+    assert_eq!(decoded.data, bytes);
+}
+
+// --- GS1 -------------------------------------------------------------------
+
+#[test]
+fn gs1_element_string_puts_fixed_fields_first() {
+    let product = ElementString::new()
+        .batch("ABC123")
+        .gtin("9506000134352")
+        .serial("XYZ")
+        .expiry("261231");
+    assert_eq!(
+        product.to_gs1_string().unwrap(),
+        format!(
+            "0109506000134352{}10ABC123{GROUP_SEPARATOR}21XYZ",
+            "17261231"
+        )
+    );
+    assert_eq!(
+        product.to_hri().unwrap(),
+        "(01)09506000134352(17)261231(10)ABC123(21)XYZ"
+    );
+    assert_eq!(product.get("21"), Some("XYZ"));
+    assert_eq!(check_digit("629104150021"), Some(3));
+
+    // Eight-digit GTINs and other AIs from the table.
+    let s = ElementString::new()
+        .gtin("96385074")
+        .element("37", "24")
+        .best_before("240229")
+        .to_gs1_string()
+        .unwrap();
+    assert_eq!(s, concat!("0100000096385074", "15240229", "3724"));
+}
+
+#[test]
+fn gs1_rejects_invalid_elements() {
+    let invalid = |product: ElementString| {
+        matches!(product.to_gs1_string(), Err(QrcError::InvalidPayload(_)))
+    };
+    assert!(invalid(ElementString::new()));
+    assert!(invalid(ElementString::new().gtin("09506000134353")));
+    assert!(invalid(ElementString::new().gtin("0950600013435")));
+    assert!(invalid(ElementString::new().expiry("261301")));
+    assert!(invalid(ElementString::new().expiry("250229")));
+    assert!(invalid(ElementString::new().expiry("2612")));
+    assert!(invalid(ElementString::new().batch("lot #1")));
+    assert!(invalid(ElementString::new().serial("S".repeat(21))));
+    assert!(invalid(ElementString::new().element("99", "x")));
+    assert!(invalid(ElementString::new().batch("A").batch("B")));
+    assert!(invalid(ElementString::new().element("30", "12a")));
+    // A day of 00 means the end of the month.
+    assert!(!invalid(ElementString::new().expiry("261200")));
+}
+
+#[test]
+fn gs1_qr_code_round_trips_in_fnc1_mode() {
+    let product = ElementString::new()
+        .gtin("09506000134352")
+        .batch("LOT%42")
+        .serial("ABCDEF123456");
+    let expected = product.to_gs1_string().unwrap().into_bytes();
+    for mode in [SegmentMode::Auto, SegmentMode::Optimal] {
+        let qr = product.to_qr_code().unwrap().with_mode(mode);
+        assert!(qr.gs1);
+        let decoded = qrc::decode::decode(&qr.to_image(300)).unwrap();
+        assert!(decoded.gs1);
+        assert_eq!(decoded.data, expected);
+    }
+
+    // A plain code carries no FNC1 indicator, and Micro QR cannot.
+    let plain = QRCode::from_string("0109506000134352".to_string());
+    assert!(!qrc::decode::decode(&plain.to_image(300)).unwrap().gs1);
+    let micro = product
+        .to_qr_code()
+        .unwrap()
+        .with_symbol_type(SymbolType::Micro);
+    assert!(matches!(
+        micro.try_to_qrcode(),
+        Err(QrcError::InvalidOption(_))
+    ));
+}
+
+#[test]
+fn gs1_digital_link_paths_and_queries() {
+    let product = ElementString::new()
+        .expiry("261231")
+        .serial("A/1&2")
+        .element("22", "2A")
+        .batch("B1")
+        .gtin("9506000134352");
+    let link = DigitalLink::new(product);
+    assert_eq!(
+        link.to_uri().unwrap(),
+        "https://id.gs1.org/01/09506000134352/22/2A/10/B1/21/A%2F1%262?17=261231"
+    );
+    let qr = link.to_qr_code().unwrap();
+    assert!(!qr.gs1);
+    encodes(&link.to_uri().unwrap());
+
+    let place = DigitalLink::new(ElementString::new().element("414", "9506000134369"))
+        .domain("https://brand.example/");
+    assert_eq!(
+        place.to_uri().unwrap(),
+        "https://brand.example/414/9506000134369"
+    );
+
+    let orphan = DigitalLink::new(ElementString::new().batch("B1"));
+    assert!(matches!(orphan.to_uri(), Err(QrcError::InvalidPayload(_))));
+}
+
+// --- Swiss QR-bill ---------------------------------------------------------
+
+fn schneider() -> Address {
+    Address::new("Robert Schneider AG", "2501", "Biel", "CH")
+        .street("Rue du Lac")
+        .building_number("1268")
+}
+
+fn qr_bill() -> QrBill {
+    QrBill::new("CH44 3199 9123 0008 8901 2", schneider(), Currency::Chf)
+        .amount_cents(194_975)
+        .reference(Reference::Qrr(
+            "21 00000 00003 13947 14300 09017".to_string(),
+        ))
+}
+
+#[test]
+fn swiss_qr_bill_payload_lines() {
+    let debtor = Address::new("Pia-Maria Rutschmann-Schnyder", "9400", "Rorschach", "CH")
+        .street("Grosse Marktgasse")
+        .building_number("28");
+    let s = qr_bill()
+        .debtor(debtor)
+        .message("Auftrag vom 15.06.2020")
+        .bill_information("//S1/10/10201409/11/200701")
+        .to_qr_string()
+        .unwrap();
+    let lines: Vec<&str> = s.split('\n').collect();
+    assert_eq!(lines.len(), 32);
+    assert_eq!(&lines[..4], ["SPC", "0200", "1", "CH4431999123000889012"]);
+    assert_eq!(
+        &lines[4..11],
+        [
+            "S",
+            "Robert Schneider AG",
+            "Rue du Lac",
+            "1268",
+            "2501",
+            "Biel",
+            "CH"
+        ]
+    );
+    assert!(lines[11..18].iter().all(|line| line.is_empty()));
+    assert_eq!(&lines[18..20], ["1949.75", "CHF"]);
+    assert_eq!(lines[20], "S");
+    assert_eq!(lines[21], "Pia-Maria Rutschmann-Schnyder");
+    assert_eq!(
+        &lines[27..],
+        [
+            "QRR",
+            "210000000003139471430009017",
+            "Auftrag vom 15.06.2020",
+            "EPD",
+            "//S1/10/10201409/11/200701"
+        ]
+    );
+
+    // An open amount in euro with a creditor reference and no debtor.
+    let s = QrBill::new("CH93 0076 2011 6238 5295 7", schneider(), Currency::Eur)
+        .reference(Reference::Scor("RF18 5390 0754 7034".to_string()))
+        .to_qr_string()
+        .unwrap();
+    assert!(s.contains("\n\nEUR\n\n\n\n\n\n\n\nSCOR\nRF18539007547034\n\nEPD"));
+    assert!(s.ends_with("EPD"));
+
+    assert_eq!(qrr_check_digit("21000000000313947143000901"), Some(7));
+    assert!(is_qr_iban("CH4431999123000889012"));
+    assert!(!is_qr_iban("CH9300762011623852957"));
+}
+
+#[test]
+fn swiss_qr_bill_rejects_invalid_bills() {
+    let invalid = |bill: QrBill| matches!(bill.to_qr_string(), Err(QrcError::InvalidPayload(_)));
+    let plain = || QrBill::new("CH9300762011623852957", schneider(), Currency::Chf);
+    assert!(!invalid(qr_bill()));
+    assert!(!invalid(plain()));
+    // References must suit the kind of IBAN.
+    assert!(invalid(qr_bill().reference(Reference::None)));
+    assert!(invalid(
+        qr_bill().reference(Reference::Scor("RF18539007547034".to_string()))
+    ));
+    assert!(invalid(plain().reference(Reference::Qrr(
+        "210000000003139471430009017".to_string()
+    ))));
+    // Check digits.
+    assert!(invalid(qr_bill().reference(Reference::Qrr(
+        "210000000003139471430009018".to_string()
+    ))));
+    assert!(invalid(
+        plain().reference(Reference::Scor("RF19539007547034".to_string()))
+    ));
+    assert!(invalid(QrBill::new(
+        "CH9300762011623852958",
+        schneider(),
+        Currency::Chf
+    )));
+    assert!(invalid(QrBill::new(
+        "DE89370400440532013000",
+        schneider(),
+        Currency::Chf
+    )));
+    // Amounts, addresses and characters.
+    assert!(invalid(plain().amount_cents(0)));
+    assert!(invalid(plain().amount_cents(100_000_000_000)));
+    assert!(invalid(QrBill::new(
+        "CH9300762011623852957",
+        Address::new("Acme", "8000", "Zürich", "ch"),
+        Currency::Chf
+    )));
+    assert!(invalid(QrBill::new(
+        "CH9300762011623852957",
+        Address::new("Acme", "", "Zürich", "CH"),
+        Currency::Chf
+    )));
+    assert!(invalid(plain().debtor(Address::new(
+        "Acme",
+        "8000",
+        "Z".repeat(36),
+        "CH"
+    ))));
+    assert!(invalid(plain().message("Merci ✓")));
+    assert!(invalid(plain().message("two\nlines")));
+    assert!(!invalid(plain().message("Façade Ștefan 5 €")));
+}
+
+#[test]
+fn swiss_qr_bill_rejects_multibyte_iban_and_reference() {
+    let invalid = |bill: QrBill| matches!(bill.to_qr_string(), Err(QrcError::InvalidPayload(_)));
+    // 21 bytes, but the first character straddles the country-code slice.
+    let iban = format!("€{}", "1".repeat(18));
+    assert_eq!(iban.len(), 21);
+    assert!(invalid(QrBill::new(iban, schneider(), Currency::Chf)));
+    // 27 bytes, but the check-digit split falls inside the last character.
+    let reference = format!("{}é", "1".repeat(25));
+    assert_eq!(reference.len(), 27);
+    assert!(invalid(qr_bill().reference(Reference::Qrr(reference))));
+}
+
+#[test]
+fn swiss_qr_bill_renders_the_cross_at_size() {
+    let bill = qr_bill();
+    let qr = bill.to_qr_code().unwrap();
+    assert_eq!(qr.ec_level, EcLevel::M);
+    let modules = qr.to_qrcode().width() as f64;
+
+    let dpi = 300;
+    let px_per_mm = f64::from(dpi) / 25.4;
+    let img = bill.to_image(dpi).unwrap();
+    let module_px = (QR_SIZE_MM * px_per_mm / modules).round();
+    assert_eq!(f64::from(img.width()), module_px * (modules + 8.0));
+    // Centre of the cross, inside its black square, and its white border.
+    let c = img.width() / 2;
+    let at = |mm: f64| *img.get_pixel(c + (mm * px_per_mm) as u32, c);
+    assert_eq!(at(0.0), image::Rgba([255, 255, 255, 255]));
+    assert_eq!(at(2.5), image::Rgba([0, 0, 0, 255]));
+    assert_eq!(
+        at(CROSS_SIZE_MM / 2.0 - 0.2),
+        image::Rgba([255, 255, 255, 255])
+    );
+    let decoded = qrc::decode::decode(&img).unwrap();
+    assert_eq!(decoded.text(), Some(bill.to_qr_string().unwrap().as_str()));
+
+    let svg = bill.to_svg().unwrap();
+    let width = QR_SIZE_MM * (modules + 8.0) / modules;
+    assert!(svg.contains(&format!(
+        "width=\"{}mm\"",
+        (width * 1000.0).round() / 1000.0
+    )));
+    assert!(svg.contains("fill=\"#000\"/>"));
+    assert!(svg.ends_with("</g></svg>"));
+}
@@ -2,8 +2,11 @@
 mod tests {
     use image::{Rgba, RgbaImage};
     use qrc::{
-        add_image_watermark, qr_code, qr_code_to, qr_code_with_ec, set_encoding_format, EcLevel,
-        ModuleShape, QRCode,
+        add_image_watermark, qr_code, qr_code_to, qr_code_with_ec, set_encoding_format,
+        CharacterSet, Color, EcLevel, EyeShape, EyeStyle, Gradient, GradientKind, Length,
+        LogoOptions, ModuleShape, OutputFormat, PngOptions, PrintSize, QRCode, QrStyle, QrcError,
+        RasterScaling, SegmentMode, Sequence, SvgOptions, SymbolType, SymbolVersion,
+        TerminalCharset, TerminalOptions, Version, DEFAULT_QUIET_ZONE, MAX_QUIET_ZONE,
     };
 
     const URL: &str = "https://minifunctions.com/";
@@ -97,7 +100,7 @@ mod tests {
 
     #[test]
     fn test_colorize() {
-        let qrcode = QRCode::new(vec![0, 1, 2, 3]);
+        let qrcode = QRCode::new(vec![0, 1, 2, 3]).with_quiet_zone(0);
         let red_qrcode = qrcode.colorize(Rgba([255, 0, 0, 255]));
 
         let image: RgbaImage = red_qrcode;
@@ -156,7 +159,13 @@ mod tests {
     fn test_set_encoding_format() {
         let qrcode = QRCode::new(b"some data".to_vec());
         let qr_with_format = set_encoding_format!(qrcode, "utf-8").unwrap();
-        assert_eq!(qr_with_format.get_encoding_format(), "utf-8");
+        assert_eq!(qr_with_format.get_encoding_format(), "UTF-8");
+        // Aliases read back under the set's canonical name.
+        let latin = QRCode::from_string("café".to_string())
+            .set_encoding_format("LATIN1")
+            .unwrap();
+        assert_eq!(latin.get_encoding_format(), "ISO-8859-1");
+        assert_eq!(latin.get_encoding_format(), CharacterSet::Iso8859_1.name());
     }
 
     // ── EC level tests ──────────────────────────────────────────────────
@@ -208,6 +217,32 @@ mod tests {
         assert!(qr.try_to_qrcode().is_err());
     }
 
+    #[test]
+    fn test_huge_rasters_are_rejected() {
+        fn invalid<T>(result: Result<T, QrcError>) -> bool {
+            matches!(result, Err(QrcError::InvalidOption(_)))
+        }
+        let qr = QRCode::from_string("Hello".to_string());
+        let max = u32::MAX;
+        assert!(invalid(qr.try_to_png(max)));
+        assert!(invalid(qr.try_to_image(max)));
+        assert!(invalid(qr.try_to_image_scaled(max)));
+        assert!(invalid(qr.try_resize(max, max)));
+        assert!(invalid(qr.try_resize(max, 1).and(qr.try_resize(1, max))));
+        assert!(invalid(qr.try_to_control_image(max)));
+        assert!(invalid(qr.encode(max, OutputFormat::Bmp)));
+        assert!(invalid(
+            qr.to_png_bytes_with_options(max, &PngOptions::default())
+        ));
+        let opts = qrc::BlendOptions {
+            module_size: max,
+            ..qrc::BlendOptions::default()
+        };
+        assert!(invalid(qr.try_blend_image(&RgbaImage::new(1, 1), &opts)));
+        // The cap sits well above everyday sizes.
+        assert!(qr.try_to_png(1024).is_ok());
+    }
+
     // ── Multilanguage tests ─────────────────────────────────────────────
 
     #[test]
@@ -262,6 +297,1926 @@ mod tests {
         assert_eq!(&png[..4], &[0x89, 0x50, 0x4E, 0x47]);
     }
 
+    // ── Quiet zone tests ────────────────────────────────────────────────
+
+    #[test]
+    fn test_default_quiet_zone() {
+        assert_eq!(QRCode::new(b"test".to_vec()).quiet_zone, 4);
+    }
+
+    #[test]
+    fn test_quiet_zone_is_light_in_every_raster() {
+        let qr = QRCode::from_string(URL.to_string()).with_quiet_zone(4);
+        let n = qr.to_qrcode().width() as u32;
+        let white = Rgba([255, 255, 255, 255]);
+
+        // 1 px per module: the border is exactly `quiet_zone` pixels wide.
+        let native = qr.colorize(Rgba([0, 0, 0, 255]));
+        assert_eq!(native.dimensions(), (n + 8, n + 8));
+        for i in 0..n + 8 {
+            for border in [0, 1, 2, 3, n + 4, n + 5, n + 6, n + 7] {
+                assert_eq!(*native.get_pixel(i, border), white);
+                assert_eq!(*native.get_pixel(border, i), white);
+            }
+        }
+        // The finder pattern starts right after the quiet zone.
+        assert_eq!(*native.get_pixel(4, 4), Rgba([0, 0, 0, 255]));
+
+        // Scaled renderers leave the same proportion of the edge blank.
+        let scale = 4;
+        let png = qr.to_png((n + 8) * scale);
+        let resized = qr.resize((n + 8) * scale, (n + 8) * scale);
+        for img in [&png, &resized] {
+            assert_eq!(*img.get_pixel(4 * scale - 1, 4 * scale - 1), white);
+            assert_eq!(*img.get_pixel(4 * scale, 4 * scale), Rgba([0, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn test_quiet_zone_scales_overlay_and_art() {
+        let logo = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 0]));
+        let qr = QRCode::from_string(URL.to_string());
+        let n = qr.to_qrcode().width() as u32;
+        let wide = qr.clone().with_quiet_zone(6);
+
+        assert_eq!(qr.overlay_image(&logo).width(), (n + 8) * 10);
+        assert_eq!(wide.overlay_image(&logo).width(), (n + 12) * 10);
+
+        let opts = qrc::BlendOptions {
+            module_size: 3,
+            ..qrc::BlendOptions::default()
+        };
+        let art = wide.blend_image(&logo, &opts);
+        assert_eq!(art.width(), (n + 12) * 3);
+
+        let control = QRCode::from_string(URL.to_string())
+            .with_quiet_zone(0)
+            .to_control_image(n);
+        assert_eq!(*control.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_svg_quiet_zone_offsets_modules() {
+        let qr = QRCode::from_string("quiet".to_string());
+        let n = qr.to_qrcode().width();
+        // The first dark run (top-left finder) starts after the quiet zone.
+        let width = (n + 8) as u32;
+        let svg = qr.to_svg(width);
+        assert!(svg.contains("d=\"M4 4h7v1h-7z"));
+        let flush = qr.with_quiet_zone(0).to_svg(n as u32);
+        assert!(flush.contains("d=\"M0 0h7v1h-7z"));
+    }
+
+    #[test]
+    fn test_oversized_quiet_zone_is_rejected() {
+        fn invalid<T>(result: Result<T, QrcError>) -> bool {
+            matches!(result, Err(QrcError::InvalidOption(_)))
+        }
+        let logo = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 0]));
+        let qr = QRCode::from_string("quiet".to_string()).with_quiet_zone(MAX_QUIET_ZONE);
+        assert!(qr.try_to_svg(0).is_ok());
+        let qr = qr.with_quiet_zone(u32::MAX / 2);
+        assert!(invalid(qr.try_to_control_image(64)));
+        assert!(invalid(qr.try_overlay_image(&logo)));
+        assert!(invalid(qr.try_to_svg(64)));
+        assert!(invalid(qr.try_to_image(64)));
+        assert!(invalid(qr.try_to_unicode()));
+        assert!(invalid(qr.try_image_size(64)));
+        assert!(invalid(
+            qr.try_blend_image(&logo, &qrc::BlendOptions::default())
+        ));
+    }
+
+    // ── Integer scaling tests ───────────────────────────────────────────
+
+    /// Lengths of the alternating light/dark runs along row `y`.
+    fn row_runs(img: &RgbaImage, y: u32) -> Vec<u32> {
+        let mut runs = vec![];
+        let mut last = *img.get_pixel(0, y);
+        let mut len = 0;
+        for x in 0..img.width() {
+            let p = *img.get_pixel(x, y);
+            if p == last {
+                len += 1;
+            } else {
+                runs.push(len);
+                last = p;
+                len = 1;
+            }
+        }
+        runs.push(len);
+        runs
+    }
+
+    #[test]
+    fn test_image_scaled_has_uniform_modules() {
+        let qr = QRCode::from_string("Hello".to_string());
+        let img = qr.to_image_scaled(3);
+        assert_eq!(img.dimensions(), (87, 87));
+        // Through the finder row every run is a whole number of modules.
+        let runs = row_runs(&img, 4 * 3 + 1);
+        assert!(runs.iter().all(|r| r % 3 == 0), "{runs:?}");
+        assert!(qr.try_to_image_scaled(0).is_err());
+        assert!(matches!(
+            qr.try_to_image_scaled(u32::MAX / 4),
+            Err(QrcError::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_exact_scaling_reports_output_size() {
+        let qr = QRCode::from_string("Hello".to_string()).with_scaling(RasterScaling::Exact);
+        assert_eq!(qr.try_image_size(100).unwrap(), 87);
+        assert_eq!(qr.to_png(100).dimensions(), (87, 87));
+        // Widths smaller than the module count still get one pixel per module.
+        assert_eq!(qr.try_image_size(10).unwrap(), 29);
+        let stretched = qr.clone().with_scaling(RasterScaling::Stretch);
+        assert_eq!(stretched.try_image_size(100).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_aligned_scaling_rejects_huge_rasters() {
+        for scaling in [RasterScaling::Pad, RasterScaling::Exact] {
+            let qr = QRCode::from_string("Hello".to_string()).with_scaling(scaling);
+            assert!(matches!(
+                qr.try_to_png(u32::MAX),
+                Err(QrcError::InvalidOption(_))
+            ));
+            assert!(matches!(
+                qr.encode(u32::MAX, OutputFormat::Bmp),
+                Err(QrcError::InvalidOption(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_pad_scaling_centres_symbol() {
+        let qr = QRCode::from_string("Hello".to_string())
+            .with_quiet_zone(0)
+            .with_scaling(RasterScaling::Pad);
+        let img = qr.to_png(100);
+        assert_eq!(img.dimensions(), (100, 100));
+        // 21 modules at 4 px = 84 px, leaving 8 px of padding on each side.
+        let white = Rgba([255, 255, 255, 255]);
+        assert_eq!(*img.get_pixel(7, 7), white);
+        assert_eq!(*img.get_pixel(8, 8), Rgba([0, 0, 0, 255]));
+        assert_eq!(*img.get_pixel(91, 91), white);
+        let runs = row_runs(&img, 9);
+        assert_eq!(runs.first(), Some(&8));
+        assert!(runs[1..runs.len() - 1].iter().all(|r| r % 4 == 0));
+    }
+
+    // ── Style tests ─────────────────────────────────────────────────────
+
+    const NAVY: Rgba<u8> = Rgba([0, 51, 102, 255]);
+    const CREAM: Rgba<u8> = Rgba([255, 253, 240, 255]);
+
+    #[test]
+    fn test_style_applies_to_every_raster() {
+        let qr = QRCode::from_string("style".to_string())
+            .with_style(QrStyle::new(NAVY, CREAM))
+            .with_scaling(RasterScaling::Exact);
+        let n = qr.to_qrcode().width() as u32 + 8;
+        let logo = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 0]));
+        let images = [
+            qr.to_png(n * 4),
+            qr.to_image_scaled(4),
+            qr.resize(n * 4, n * 4),
+            qr.overlay_image(&logo),
+            qr.to_control_image(n * 4),
+        ];
+        for img in &images {
+            let scale = img.width() / n;
+            assert_eq!(*img.get_pixel(0, 0), CREAM);
+            assert_eq!(*img.get_pixel(4 * scale, 4 * scale), NAVY);
+        }
+        // `colorize` overrides only the dark colour.
+        let red = qr.colorize(Rgba([200, 0, 0, 255]));
+        assert_eq!(*red.get_pixel(0, 0), CREAM);
+        assert_eq!(*red.get_pixel(4, 4), Rgba([200, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_style_applies_to_svg() {
+        let qr = QRCode::from_string("style".to_string()).with_style(QrStyle::new(NAVY, CREAM));
+        let svg = qr.to_svg(128);
+        assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#FFFDF0\"/>"));
+        assert!(svg.contains("<path fill=\"#003366\""));
+
+        let circles = qr.clone().with_shape(ModuleShape::Circle).to_svg(128);
+        assert!(circles.contains("<path fill=\"#003366\" d=\"M"));
+
+        let clear = qr.with_style(QrStyle::new(NAVY, CREAM).with_transparent_background(true));
+        assert!(!clear.to_svg(128).contains("100%"));
+    }
+
+    #[test]
+    fn test_transparent_background() {
+        let style = QrStyle::default().with_transparent_background(true);
+        let qr = QRCode::from_string("style".to_string()).with_style(style);
+        let img = qr.to_png(116);
+        assert_eq!(img.get_pixel(0, 0)[3], 0);
+        assert_eq!(*img.get_pixel(16, 16), Rgba([0, 0, 0, 255]));
+        // JPEG has no alpha channel: the light colour shows through.
+        assert!(!qr.to_jpg(116).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_style_contrast_check() {
+        assert!((QrStyle::default().contrast_ratio() - 21.0).abs() < 1e-9);
+        assert!(QrStyle::new(NAVY, CREAM).validate().is_ok());
+
+        let grey = QrStyle::new(Rgba([170, 170, 170, 255]), Rgba([255, 255, 255, 255]));
+        assert!(grey.validate().is_err());
+        let inverted = QrStyle::new(CREAM, NAVY);
+        assert!(inverted.validate().is_err());
+
+        let qr = QRCode::from_string("style".to_string()).with_style(grey);
+        assert!(qr.try_to_png(64).is_err());
+        assert!(qr.try_to_svg(64).is_err());
+        assert!(qr.to_png_bytes(64).is_err());
+        // `colorize` checks the colour it is given against the light colour.
+        let plain = QRCode::from_string("style".to_string());
+        assert!(plain.try_colorize(Rgba([250, 250, 250, 255])).is_err());
+    }
+
+    #[test]
+    fn test_style_contrast_counts_alpha() {
+        let white = Rgba([255, 255, 255, 255]);
+        let invisible = QrStyle::new(Rgba([0, 0, 0, 0]), white);
+        assert!((invisible.contrast_ratio() - 1.0).abs() < 1e-9);
+        assert!(invisible.validate().is_err());
+        let faint = QrStyle::new(Rgba([0, 0, 0, 64]), white);
+        assert!(faint.validate().is_err());
+        assert!(QrStyle::new(Rgba([0, 0, 0, 230]), white).validate().is_ok());
+
+        let clear = Rgba([0, 0, 0, 0]);
+        assert!(QrStyle::default()
+            .with_eye_colors(clear, clear)
+            .validate()
+            .is_err());
+        let gradient = Gradient::linear(0, Rgba([0, 0, 0, 255]), clear);
+        assert!(QrStyle::default()
+            .with_gradient(gradient)
+            .validate()
+            .is_err());
+        let qr = QRCode::from_string("alpha".to_string()).with_style(invisible);
+        assert!(qr.try_to_png(64).is_err());
+    }
+
+    // ── Gradient tests ──────────────────────────────────────────────────
+
+    const PLUM: Rgba<u8> = Rgba([102, 0, 51, 255]);
+
+    /// Colours equal within a few levels of rounding from pixel-centre sampling.
+    fn near(a: Rgba<u8>, b: Rgba<u8>) -> bool {
+        a.0.iter().zip(b.0).all(|(x, y)| x.abs_diff(y) <= 4)
+    }
+
+    #[test]
+    fn test_linear_gradient_raster() {
+        let style = QrStyle::default().with_gradient(Gradient::linear(0, NAVY, PLUM));
+        let qr = QRCode::from_string("gradient".to_string())
+            .with_quiet_zone(0)
+            .with_style(style);
+        let n = qr.to_qrcode().width() as u32;
+        let img = qr.to_image_scaled(10);
+        // Top-left finder is near the start of the ramp, top-right near the end.
+        assert!(near(*img.get_pixel(0, 0), NAVY));
+        assert!(near(*img.get_pixel(n * 10 - 1, 0), PLUM));
+        let mid = *img.get_pixel(n * 10 / 2, n * 10 / 2);
+        assert!(mid == Rgba([255, 255, 255, 255]) || (mid != NAVY && mid != PLUM));
+    }
+
+    #[test]
+    fn test_radial_and_multi_stop_gradient() {
+        let gradient = Gradient::radial(NAVY, PLUM).with_stop(50, Rgba([0, 0, 0, 255]));
+        assert_eq!(gradient.kind, GradientKind::Radial);
+        let offsets: Vec<u8> = gradient.stops.iter().map(|s| s.offset).collect();
+        assert_eq!(offsets, [0, 50, 100]);
+
+        let qr = QRCode::from_string("gradient".to_string())
+            .with_quiet_zone(0)
+            .with_style(QrStyle::default().with_gradient(gradient));
+        let img = qr.to_image_scaled(10);
+        // The corners are the outer stop.
+        assert!(near(*img.get_pixel(0, 0), PLUM));
+    }
+
+    #[test]
+    fn test_eye_gradient_spans_each_finder() {
+        let style = QrStyle::default().with_eye_gradient(Gradient::linear(90, NAVY, PLUM));
+        let qr = QRCode::from_string("gradient".to_string())
+            .with_quiet_zone(0)
+            .with_style(style);
+        let n = qr.to_qrcode().width() as u32;
+        let img = qr.to_image_scaled(10);
+        for (ex, ey) in [(0, 0), (n - 7, 0), (0, n - 7)] {
+            assert!(near(*img.get_pixel(ex * 10, ey * 10), NAVY));
+            assert!(near(*img.get_pixel(ex * 10, ey * 10 + 69), PLUM));
+        }
+        // Data modules keep the flat dark colour.
+        let data_dark = (0..n * 10)
+            .flat_map(|y| (0..n * 10).map(move |x| (x, y)))
+            .filter(|&(x, y)| (9..n - 9).contains(&(x / 10)) && (9..n - 9).contains(&(y / 10)))
+            .map(|(x, y)| *img.get_pixel(x, y))
+            .find(|p| *p != Rgba([255, 255, 255, 255]));
+        assert_eq!(data_dark, Some(Rgba([0, 0, 0, 255])));
+    }
+
+    #[test]
+    fn test_gradient_svg_defs() {
+        let style = QrStyle::default()
+            .with_gradient(Gradient::linear(90, NAVY, PLUM))
+            .with_eye_gradient(Gradient::radial(PLUM, NAVY));
+        let svg = QRCode::from_string("gradient".to_string())
+            .with_style(style)
+            .to_svg(290);
+        assert!(svg.contains("<defs><linearGradient id=\"qrc-fill\""));
+        assert!(svg.contains("<stop offset=\"0%\" stop-color=\"#003366\"/>"));
+        assert!(svg.contains("fill=\"url(#qrc-fill)\""));
+        for eye in 0..3 {
+            assert!(svg.contains(&format!("<radialGradient id=\"qrc-eye-{eye}\"")));
+            assert!(svg.contains(&format!("fill=\"url(#qrc-eye-{eye})\"")));
+        }
+        // 90° runs top to bottom across the symbol (10 px modules, 4 quiet).
+        assert!(svg.contains("x1=\"145\" y1=\"40\" x2=\"145\" y2=\"250\""));
+    }
+
+    #[test]
+    fn test_gradient_spans_rectangular_symbols() {
+        let rmqr = |gradient| {
+            QRCode::from_string("ABC".to_string())
+                .with_symbol_type(SymbolType::Rmqr)
+                .with_version(SymbolVersion::rmqr(7, 43).unwrap())
+                .with_quiet_zone(0)
+                .with_style(QrStyle::default().with_gradient(gradient))
+        };
+        // 90° runs top to bottom over the 7 rows, as in the raster.
+        let qr = rmqr(Gradient::linear(90, NAVY, PLUM));
+        assert!(qr
+            .to_svg(43)
+            .contains("x1=\"21.5\" y1=\"0\" x2=\"21.5\" y2=\"7\""));
+        let img = qr.to_image_scaled(10);
+        assert!(near(*img.get_pixel(0, 0), NAVY));
+        assert!(near(*img.get_pixel(0, 69), PLUM));
+
+        // Radial rings squash into ellipses in every vector format.
+        let qr = rmqr(Gradient::radial(NAVY, PLUM));
+        assert!(qr
+            .to_svg(43)
+            .contains("r=\"30.406\" gradientTransform=\"matrix(1 0 0 0.163 0 2.93)\""));
+        assert!(qr
+            .to_eps(Length::Points(43.0))
+            .contains("[1 0 0 0.1628 0 2.9302] concat"));
+        let square = QRCode::from_string("ABC".to_string())
+            .with_style(QrStyle::default().with_gradient(Gradient::radial(NAVY, PLUM)));
+        assert!(!square.to_svg(100).contains("gradientTransform"));
+    }
+
+    #[test]
+    fn test_gradient_luminance_check() {
+        let white = Rgba([255, 255, 255, 255]);
+        let pale = Rgba([200, 200, 200, 255]);
+        // A light stop is fine as long as the darkest stop contrasts enough.
+        let ok = QrStyle::default().with_gradient(Gradient::linear(0, NAVY, pale));
+        assert!(ok.validate().is_ok());
+        let faint = QrStyle::default().with_gradient(Gradient::linear(0, pale, pale));
+        assert!(faint.validate().is_err());
+        let invisible = QrStyle::default().with_gradient(Gradient::linear(0, NAVY, white));
+        assert!(invisible.validate().is_err());
+        let mut empty = Gradient::linear(0, NAVY, PLUM);
+        empty.stops.clear();
+        assert!(QrStyle::default()
+            .with_eye_gradient(empty)
+            .validate()
+            .is_err());
+        let overshoot = Gradient::linear(0, NAVY, PLUM).with_stop(150, NAVY);
+        assert!(QrStyle::default()
+            .with_gradient(overshoot)
+            .validate()
+            .is_err());
+
+        let qr = QRCode::from_string("gradient".to_string()).with_style(faint);
+        assert!(qr.try_to_svg(64).is_err());
+    }
+
+    // ── Eye style tests ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_eye_shapes_raster() {
+        let white = Rgba([255, 255, 255, 255]);
+        let black = Rgba([0, 0, 0, 255]);
+        let qr = QRCode::from_string("eyes".to_string())
+            .with_quiet_zone(0)
+            .with_eye_style(EyeStyle::new(EyeShape::Circle, EyeShape::Circle));
+        let n = qr.to_qrcode().width() as u32;
+        let img = qr.to_image_scaled(10);
+        for (ex, ey) in [(0, 0), (n - 7, 0), (0, n - 7)] {
+            let (x0, y0) = (ex * 10, ey * 10);
+            // Round frames leave the box corners empty…
+            assert_eq!(*img.get_pixel(x0, y0), white);
+            // …but cover the middle of each edge and the centre.
+            assert_eq!(*img.get_pixel(x0 + 35, y0 + 2), black);
+            assert_eq!(*img.get_pixel(x0 + 35, y0 + 35), black);
+            assert_eq!(*img.get_pixel(x0 + 35, y0 + 15), white);
+        }
+
+        // Leaf frames keep two sharp corners, mirrored per eye.
+        let leaf = QRCode::from_string("eyes".to_string())
+            .with_quiet_zone(0)
+            .with_eye_style(EyeStyle::new(EyeShape::Leaf, EyeShape::Square))
+            .to_image_scaled(10);
+        assert_eq!(*leaf.get_pixel(0, 0), white);
+        assert_eq!(*leaf.get_pixel(69, 0), black);
+        assert_eq!(*leaf.get_pixel((n - 7) * 10, 0), black);
+        assert_eq!(*leaf.get_pixel(n * 10 - 1, 0), white);
+    }
+
+    #[test]
+    fn test_eye_shapes_ignore_module_shape() {
+        let qr = QRCode::from_string("eyes".to_string())
+            .with_quiet_zone(0)
+            .with_shape(ModuleShape::Circle)
+            .with_eye_style(EyeStyle::default());
+        let img = qr.to_image_scaled(10);
+        // Square eyes are solid to the corner even with circular modules.
+        assert_eq!(*img.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+        let mut circles = qr.clone();
+        circles.eye_style = None;
+        assert_eq!(
+            *circles.to_image_scaled(10).get_pixel(0, 0),
+            Rgba([255, 255, 255, 255])
+        );
+    }
+
+    #[test]
+    fn test_eye_colors() {
+        let style = QrStyle::default().with_eye_colors(PLUM, NAVY);
+        let qr = QRCode::from_string("eyes".to_string())
+            .with_quiet_zone(0)
+            .with_style(style.clone());
+        let img = qr.to_image_scaled(10);
+        assert_eq!(*img.get_pixel(5, 5), PLUM);
+        assert_eq!(*img.get_pixel(35, 35), NAVY);
+
+        let shaped = qr.with_eye_style(EyeStyle::new(EyeShape::RoundedSquare, EyeShape::Circle));
+        let img = shaped.to_image_scaled(10);
+        assert_eq!(*img.get_pixel(35, 5), PLUM);
+        assert_eq!(*img.get_pixel(35, 35), NAVY);
+
+        let bad = style.with_eye_colors(Rgba([240, 240, 240, 255]), NAVY);
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_eye_styles_svg() {
+        let qr = QRCode::from_string("eyes".to_string())
+            .with_style(QrStyle::default().with_eye_colors(PLUM, NAVY))
+            .with_eye_style(EyeStyle::new(EyeShape::Circle, EyeShape::RoundedSquare));
+        let svg = qr.to_svg(290);
+        // Three frames drawn as even-odd rings, three balls as rounded squares.
+        assert_eq!(
+            svg.matches("fill-rule=\"evenodd\" fill=\"#660033\"")
+                .count(),
+            3
+        );
+        assert_eq!(svg.matches("fill=\"#003366\" d=\"M").count(), 3);
+        assert!(svg.contains("a35 35 0 0 1"));
+
+        // Eye colours without eye shapes split the finder modules into layers.
+        let cells = QRCode::from_string("eyes".to_string())
+            .with_style(QrStyle::default().with_eye_colors(PLUM, NAVY))
+            .to_svg(290);
+        assert_eq!(cells.matches("fill=\"#660033\"").count(), 3);
+        assert!(!cells.contains("evenodd"));
+    }
+
+    // ── Connected shape tests ───────────────────────────────────────────
+
+    /// Renders "connected" edge-to-edge at 10 px per module.
+    fn connected(shape: ModuleShape) -> RgbaImage {
+        QRCode::from_string("connected".to_string())
+            .with_quiet_zone(0)
+            .with_shape(shape)
+            .to_image_scaled(10)
+    }
+
+    #[test]
+    fn test_liquid_shape_rounds_exterior_corners_only() {
+        let white = Rgba([255, 255, 255, 255]);
+        let black = Rgba([0, 0, 0, 255]);
+        let img = connected(ModuleShape::Liquid);
+        // The finder's outer corner is rounded…
+        assert_eq!(*img.get_pixel(0, 0), white);
+        // …its straight edges are not…
+        assert_eq!(*img.get_pixel(35, 0), black);
+        assert_eq!(*img.get_pixel(10, 0), black);
+        // …and the inside corner of the ring gets a fillet.
+        assert_eq!(*img.get_pixel(10, 10), black);
+        assert_eq!(*img.get_pixel(15, 15), white);
+    }
+
+    #[test]
+    fn test_bar_shapes_merge_runs() {
+        let white = Rgba([255, 255, 255, 255]);
+        let black = Rgba([0, 0, 0, 255]);
+        let horizontal = connected(ModuleShape::HorizontalBars);
+        // A gap separates rows; the run is continuous across module edges.
+        assert_eq!(*horizontal.get_pixel(35, 0), white);
+        assert_eq!(*horizontal.get_pixel(30, 5), black);
+        // Rounded end cap.
+        assert_eq!(*horizontal.get_pixel(0, 1), white);
+
+        let vertical = connected(ModuleShape::VerticalBars);
+        assert_eq!(*vertical.get_pixel(0, 35), white);
+        assert_eq!(*vertical.get_pixel(5, 30), black);
+    }
+
+    #[test]
+    fn test_connected_dots_bridge_neighbours() {
+        let img = connected(ModuleShape::ConnectedDots);
+        // Bridge between the first two finder modules, at the module edge…
+        assert_eq!(*img.get_pixel(10, 5), Rgba([0, 0, 0, 255]));
+        // …but the dots stay round above it.
+        assert_eq!(*img.get_pixel(10, 1), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_connected_shapes_svg_single_path() {
+        for shape in [
+            ModuleShape::Liquid,
+            ModuleShape::HorizontalBars,
+            ModuleShape::VerticalBars,
+            ModuleShape::ConnectedDots,
+        ] {
+            let svg = QRCode::from_string("connected".to_string())
+                .with_shape(shape)
+                .to_svg(290);
+            assert_eq!(svg.matches("<path").count(), 1, "{shape:?}");
+            assert!(!svg.contains("<circle") && !svg.contains("<rect x="));
+            assert!(svg.contains("a4 4 0 0 1") || svg.contains("a5 5 0 0 1"));
+        }
+        // Liquid fillets are drawn as concave arcs.
+        let liquid = QRCode::from_string("connected".to_string())
+            .with_shape(ModuleShape::Liquid)
+            .to_svg(290);
+        assert!(liquid.contains("a5 5 0 0 0"));
+    }
+
+    // ── Compact SVG ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_svg_every_shape_single_path() {
+        for shape in [
+            ModuleShape::Square,
+            ModuleShape::RoundedSquare,
+            ModuleShape::Circle,
+            ModuleShape::Diamond,
+        ] {
+            let svg = QRCode::from_string("compact".to_string())
+                .with_shape(shape)
+                .to_svg(256);
+            assert_eq!(svg.matches("<path").count(), 1, "{shape:?}");
+            assert!(!svg.contains("<circle") && !svg.contains("<polygon"));
+            assert!(!svg.contains("<rect x="));
+        }
+    }
+
+    #[test]
+    fn test_svg_view_box_only() {
+        let qr = QRCode::from_string("compact".to_string()).with_shape(ModuleShape::Circle);
+        let svg = qr.to_svg_with_options(999, &SvgOptions::default().with_view_box_only(true));
+        assert!(svg.contains("viewBox=\"0 0 29 29\">"));
+        assert!(!svg.contains("width=\"999\""));
+        // Module units: the first circle starts at the quiet-zone edge.
+        assert!(svg.contains("d=\"M4 4.5a0.5 0.5 0 1 1 1 0"));
+
+        let sized = qr.to_svg(290);
+        assert!(sized.contains("width=\"290\" height=\"290\" viewBox=\"0 0 290 290\""));
+    }
+
+    #[test]
+    fn test_svg_precision() {
+        let qr = QRCode::from_string("compact".to_string());
+        let coarse = qr.to_svg_with_options(100, &SvgOptions::default().with_precision(0));
+        assert!(coarse.contains("d=\"M14 14h"));
+        let d = coarse
+            .split("d=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap();
+        assert!(!d.contains('.'));
+        let fine = qr.to_svg_with_options(100, &SvgOptions::default().with_precision(2));
+        assert!(fine.contains("d=\"M13.79 13.79h"));
+        let default = qr.to_svg(100);
+        assert!(default.contains("d=\"M13.793 13.793h"));
+    }
+
+    // ── PDF ─────────────────────────────────────────────────────────────
+
+    /// Byte offset of the first `needle` in `pdf`.
+    fn find(pdf: &[u8], needle: &str) -> usize {
+        pdf.windows(needle.len())
+            .position(|w| w == needle.as_bytes())
+            .unwrap()
+    }
+
+    /// The decompressed page content of a PDF written by `to_pdf`.
+    fn pdf_content(pdf: &[u8]) -> String {
+        let start = find(pdf, "stream\n") + "stream\n".len();
+        let end = find(pdf, "\nendstream");
+        let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&pdf[start..end]).unwrap();
+        String::from_utf8(raw).unwrap()
+    }
+
+    #[test]
+    fn test_pdf_structure() {
+        let pdf = QRCode::from_string("print".to_string()).to_pdf(Length::Inches(1.0));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/MediaBox [0 0 72 72]"));
+
+        // Every xref entry points at its object header.
+        let tail = String::from_utf8_lossy(&pdf[find(&pdf, "xref\n")..]).into_owned();
+        let xref: usize = tail.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert!(pdf[xref..].starts_with(b"xref\n0 6\n"));
+        for (i, line) in tail.lines().skip(3).take(5).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_pdf_respects_quiet_zone_and_style() {
+        let qr = QRCode::from_string("print".to_string())
+            .with_quiet_zone(2)
+            .with_style(QrStyle::new(NAVY, CREAM));
+        let content = pdf_content(&qr.to_pdf(Length::Millimetres(25.4)));
+        // 21 modules plus 2×2 of quiet zone across 72 pt, y axis flipped.
+        assert!(content.starts_with("q\n2.88 0 0 -2.88 0 72 cm\n"));
+        assert!(content.contains("1 0.9922 0.9412 rg\n0 0 25 25 re\nf\n"));
+        assert!(content.contains("0 0.2 0.4 rg\n2 2 m\n"));
+
+        let clear = qr.with_style(QrStyle::new(NAVY, CREAM).with_transparent_background(true));
+        assert!(!pdf_content(&clear.to_pdf(Length::Points(100.0))).contains(" re\n"));
+    }
+
+    #[test]
+    fn test_pdf_shapes_eyes_and_gradients() {
+        let circles = QRCode::from_string("print".to_string()).with_shape(ModuleShape::Circle);
+        assert!(pdf_content(&circles.to_pdf(Length::Points(100.0))).contains(" c\n"));
+
+        let eyes = QRCode::from_string("print".to_string())
+            .with_eye_style(EyeStyle::new(EyeShape::Circle, EyeShape::Square));
+        assert_eq!(
+            pdf_content(&eyes.to_pdf(Length::Points(100.0)))
+                .matches("f*\n")
+                .count(),
+            3
+        );
+
+        let gradient = QRCode::from_string("print".to_string()).with_style(
+            QrStyle::default().with_gradient(Gradient::linear(0, NAVY, PLUM).with_stop(50, NAVY)),
+        );
+        let pdf = gradient.to_pdf(Length::Points(100.0));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Shading << /qrc-fill << /ShadingType 2"));
+        assert!(text.contains("/Bounds [0.5]"));
+        assert!(pdf_content(&pdf).contains("W n\n/qrc-fill sh\n"));
+    }
+
+    #[test]
+    fn test_pdf_rejects_bad_sizes() {
+        let qr = QRCode::from_string("print".to_string());
+        for size in [
+            Length::Millimetres(0.0),
+            Length::Inches(-1.0),
+            Length::Inches(300.0),
+        ] {
+            assert!(matches!(
+                qr.try_to_pdf(size),
+                Err(QrcError::InvalidOption(_))
+            ));
+        }
+        assert!((Length::Inches(2.0).to_millimetres() - 50.8).abs() < 1e-9);
+    }
+
+    // ── EPS ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_eps_header_and_bounding_box() {
+        let eps = QRCode::from_string("print".to_string()).to_eps(Length::Millimetres(30.0));
+        assert!(eps.starts_with("%!PS-Adobe-3.0 EPSF-3.0\n"));
+        assert!(eps.contains("%%BoundingBox: 0 0 86 86\n"));
+        assert!(eps.contains("%%HiResBoundingBox: 0 0 85.0394 85.0394\n"));
+        assert!(eps.contains("%%LanguageLevel: 2\n"));
+        assert!(eps.ends_with("showpage\n%%Trailer\n%%EOF\n"));
+        assert_eq!(
+            eps.matches("gsave").count(),
+            eps.matches("grestore").count()
+        );
+    }
+
+    #[test]
+    fn test_eps_cmyk_colours() {
+        let qr = QRCode::from_string("print".to_string()).with_style(QrStyle::new(NAVY, CREAM));
+        let eps = qr.to_eps(Length::Inches(1.0));
+        assert!(eps.contains("1 0.5 0 0.6 setcmykcolor\nnewpath\n"));
+        assert!(eps.contains("0 0.0078 0.0588 0 setcmykcolor\n0 0 29 29 rectfill"));
+        assert!(!eps.contains("setrgbcolor"));
+    }
+
+    #[test]
+    fn test_eps_every_shape_is_vector() {
+        for shape in [
+            ModuleShape::Square,
+            ModuleShape::RoundedSquare,
+            ModuleShape::Circle,
+            ModuleShape::Diamond,
+            ModuleShape::Liquid,
+            ModuleShape::HorizontalBars,
+            ModuleShape::VerticalBars,
+            ModuleShape::ConnectedDots,
+        ] {
+            let eps = QRCode::from_string("print".to_string())
+                .with_shape(shape)
+                .to_eps(Length::Inches(1.0));
+            assert_eq!(eps.matches("\nfill\n").count(), 1, "{shape:?}");
+            let curved = eps.contains(" c\n");
+            assert_eq!(
+                curved,
+                !matches!(shape, ModuleShape::Square | ModuleShape::Diamond),
+                "{shape:?}"
+            );
+        }
+
+        let styled = QRCode::from_string("print".to_string())
+            .with_eye_style(EyeStyle::new(EyeShape::Leaf, EyeShape::Circle))
+            .with_style(QrStyle::default().with_gradient(Gradient::radial(NAVY, PLUM)))
+            .to_eps(Length::Inches(1.0));
+        assert!(styled.contains("%%LanguageLevel: 3\n"));
+        assert!(styled.contains("eoclip\n<< /ShadingType 3 /ColorSpace /DeviceCMYK"));
+        assert_eq!(styled.matches("shfill").count(), 7);
+    }
+
+    // ── Terminal ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_unicode_half_blocks() {
+        let qr = QRCode::from_string("term".to_string()).with_quiet_zone(1);
+        let text = qr.to_unicode();
+        let lines: Vec<&str> = text.lines().collect();
+        // 23 module rows packed two per line.
+        assert_eq!(lines.len(), 12);
+        assert!(lines.iter().all(|line| line.chars().count() == 23));
+        // Quiet row above the finder's top edge, then its side bars.
+        assert!(lines[0].starts_with(" ▄▄▄▄▄▄▄ "));
+        assert!(lines[1].starts_with(" █ ▄▄▄ █ "));
+        // The last line holds only the bottom quiet row.
+        assert!(lines[11].chars().all(|c| c == ' '));
+    }
+
+    #[test]
+    fn test_terminal_invert_and_ascii() {
+        let qr = QRCode::from_string("term".to_string()).with_quiet_zone(1);
+        let inverted = qr.to_terminal(&TerminalOptions::default().with_invert(true));
+        assert!(inverted.lines().next().unwrap().starts_with("█▀▀▀▀▀▀▀█"));
+
+        let ascii =
+            qr.to_terminal(&TerminalOptions::default().with_charset(TerminalCharset::Ascii));
+        assert!(ascii.is_ascii());
+        assert_eq!(ascii.lines().count(), 23);
+        assert!(ascii.lines().all(|line| line.len() == 46));
+        let row = |n: usize| ascii.lines().nth(n).unwrap()[..16].to_string();
+        assert_eq!(row(2), "  ##          ##");
+        assert_eq!(row(2), row(6));
+    }
+
+    #[test]
+    fn test_terminal_ansi_colours() {
+        let qr = QRCode::from_string("term".to_string()).with_style(QrStyle::new(NAVY, CREAM));
+        let options = TerminalOptions::default().with_ansi(true).with_invert(true);
+        let text = qr.to_terminal(&options);
+        let first = text.lines().next().unwrap();
+        assert!(first.starts_with("\x1b[38;2;0;51;102m\x1b[48;2;255;253;240m"));
+        assert!(first.ends_with("\x1b[0m"));
+        // Explicit colours ignore `invert`: the quiet zone stays blank.
+        assert!(first.contains("        "));
+
+        let clear = qr.with_style(QrStyle::new(NAVY, CREAM).with_transparent_background(true));
+        assert!(!clear.to_terminal(&options).contains("\x1b[48;2"));
+    }
+
+    // ── Encoders ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_encode_magic_bytes() {
+        let qr = QRCode::from_string("encode".to_string());
+        let cases = [
+            (OutputFormat::Png, &b"\x89PNG"[..]),
+            (OutputFormat::Jpeg { quality: 90 }, &b"\xFF\xD8\xFF"[..]),
+            (OutputFormat::Gif, &b"GIF8"[..]),
+            (OutputFormat::Bmp, &b"BM"[..]),
+            (
+                OutputFormat::Ico {
+                    extra_sizes: vec![],
+                },
+                &b"\0\0\x01\0\x01\0"[..],
+            ),
+        ];
+        for (format, magic) in cases {
+            let bytes = qr.encode(128, format.clone()).unwrap();
+            assert!(bytes.starts_with(magic), "{format:?}");
+        }
+        assert_eq!(
+            qr.to_png_bytes(128).unwrap(),
+            qr.encode(128, OutputFormat::Png).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_encode_multi_size_icon() {
+        let qr = QRCode::from_string("encode".to_string());
+        let icon = qr
+            .encode(
+                48,
+                OutputFormat::Ico {
+                    extra_sizes: vec![16, 32, 48, 256],
+                },
+            )
+            .unwrap();
+        assert_eq!(u16::from_le_bytes([icon[4], icon[5]]), 4);
+        // Directory entries store the width, with 256 written as 0.
+        let widths: Vec<u8> = (0..4).map(|i| icon[6 + 16 * i]).collect();
+        assert_eq!(widths, [16, 32, 48, 0]);
+
+        for bad in [0, 257] {
+            let err = qr.encode(
+                48,
+                OutputFormat::Ico {
+                    extra_sizes: vec![bad],
+                },
+            );
+            assert!(matches!(err, Err(QrcError::InvalidOption(_))));
+        }
+        assert!(qr
+            .encode(
+                300,
+                OutputFormat::Ico {
+                    extra_sizes: vec![]
+                }
+            )
+            .is_err());
+    }
+
+    #[cfg(feature = "webp")]
+    #[test]
+    fn test_encode_webp_lossless() {
+        let bytes = QRCode::from_string("encode".to_string())
+            .encode(128, OutputFormat::WebP)
+            .unwrap();
+        assert!(bytes.starts_with(b"RIFF") && &bytes[8..16] == b"WEBPVP8L");
+    }
+
+    #[cfg(feature = "tiff")]
+    #[test]
+    fn test_encode_tiff() {
+        let bytes = QRCode::from_string("encode".to_string())
+            .encode(128, OutputFormat::Tiff)
+            .unwrap();
+        assert!(bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*"));
+        assert_eq!(OutputFormat::Tiff.extension(), "tiff");
+    }
+
+    // ── Optimised PNG ───────────────────────────────────────────────────
+
+    /// The data of the first `kind` chunk in `png`.
+    fn png_chunk<'a>(png: &'a [u8], kind: &[u8]) -> Option<&'a [u8]> {
+        let mut at = 8;
+        while at + 8 <= png.len() {
+            let len = u32::from_be_bytes(png[at..at + 4].try_into().unwrap()) as usize;
+            if &png[at + 4..at + 8] == kind {
+                return Some(&png[at + 8..at + 8 + len]);
+            }
+            at += 12 + len;
+        }
+        None
+    }
+
+    #[test]
+    fn test_png_picks_smallest_colour_type() {
+        let qr = QRCode::from_string("optimise".to_string());
+        let mono = qr.to_png_bytes(290).unwrap();
+        // Bit depth 1, greyscale.
+        assert_eq!(&png_chunk(&mono, b"IHDR").unwrap()[8..10], &[1, 0]);
+        let decoded = image::load_from_memory(&mono).unwrap().to_rgba8();
+        assert_eq!(decoded, qr.to_image(290));
+
+        let styled = qr.clone().with_style(QrStyle::new(NAVY, CREAM));
+        let indexed = styled.to_png_bytes(290).unwrap();
+        assert_eq!(&png_chunk(&indexed, b"IHDR").unwrap()[8..10], &[1, 3]);
+        assert_eq!(png_chunk(&indexed, b"PLTE").unwrap().len(), 6);
+        let decoded = image::load_from_memory(&indexed).unwrap().to_rgba8();
+        assert_eq!(decoded, styled.to_image(290));
+
+        let clear = qr
+            .clone()
+            .with_style(QrStyle::default().with_transparent_background(true));
+        assert!(png_chunk(&clear.to_png_bytes(64).unwrap(), b"tRNS").is_some());
+
+        let gradient =
+            qr.with_style(QrStyle::default().with_gradient(Gradient::linear(45, NAVY, PLUM)));
+        let png = gradient.to_png_bytes(290).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+        assert_eq!(decoded, gradient.to_image(290));
+    }
+
+    #[test]
+    fn test_png_smaller_than_rgba() {
+        let qr = QRCode::from_string(URL.to_string());
+        let mut rgba = std::io::Cursor::new(Vec::new());
+        qr.to_image(512)
+            .write_to(&mut rgba, image::ImageFormat::Png)
+            .unwrap();
+        let optimised = qr.to_png_bytes(512).unwrap();
+        assert!(
+            optimised.len() * 2 < rgba.get_ref().len(),
+            "{} bytes",
+            optimised.len()
+        );
+    }
+
+    #[test]
+    fn test_png_metadata_chunks() {
+        let qr = QRCode::from_string("https://example.com".to_string());
+        let options = PngOptions::default()
+            .with_dpi(300)
+            .with_payload(true)
+            .with_software(true)
+            .with_text("Title", "Café menu")
+            .with_text("Description", "メニュー");
+        let png = qr.to_png_bytes_with_options(128, &options).unwrap();
+        let phys = png_chunk(&png, b"pHYs").unwrap();
+        assert_eq!(phys, [0, 0, 0x2E, 0x23, 0, 0, 0x2E, 0x23, 1]);
+        assert!(png
+            .windows(27)
+            .any(|w| w == b"Payload\0https://example.com"));
+        assert!(png.windows(9).any(|w| w == b"Software\0"));
+        assert!(png.windows(11).any(|w| w == b"Title\0Caf\xE9 "));
+        let itxt = png_chunk(&png, b"iTXt").unwrap();
+        assert!(itxt.starts_with(b"Description\0\0\0\0\0"));
+        assert!(itxt.ends_with("メニュー".as_bytes()));
+        // Metadata does not disturb decoding.
+        assert!(image::load_from_memory(&png).is_ok());
+
+        let bad = PngOptions::default().with_text("", "empty keyword");
+        assert!(matches!(
+            qr.to_png_bytes_with_options(64, &bad),
+            Err(QrcError::InvalidOption(_))
+        ));
+        let binary = QRCode::new(vec![0xFF, 0xFE]);
+        let payload = PngOptions::default().with_payload(true);
+        assert!(binary.to_png_bytes_with_options(64, &payload).is_err());
+    }
+
+    // ── Print sizing ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_print_layout_whole_dots_per_module() {
+        let qr = QRCode::from_string("Hello".to_string());
+        // 1 inch at 300 dpi holds 300 / 29 = 10.3 dots per module.
+        let fit = qr
+            .print_layout(&PrintSize::fit(Length::Inches(1.0), 300))
+            .unwrap();
+        assert_eq!((fit.modules, fit.module_px, fit.width_px), (29, 10, 290));
+        assert!((fit.size.to_inches() - 290.0 / 300.0).abs() < 1e-9);
+        assert!(fit.size.to_inches() <= 1.0);
+
+        // 0.5 mm at 203 dpi is 3.996 dots, rounded up to 4.
+        let min = qr
+            .print_layout(&PrintSize::min_module(Length::Millimetres(0.5), 203))
+            .unwrap();
+        assert_eq!((min.module_px, min.width_px), (4, 116));
+        assert!(min.module_size.to_millimetres() >= 0.5);
+        // An exact fit is not bumped up by rounding error.
+        let exact = qr
+            .print_layout(&PrintSize::min_module(Length::Inches(0.01), 300))
+            .unwrap();
+        assert_eq!(exact.module_px, 3);
+    }
+
+    #[test]
+    fn test_print_layout_rejects_impossible_sizes() {
+        let qr = QRCode::from_string("Hello".to_string());
+        for size in [
+            PrintSize::fit(Length::Millimetres(2.0), 300),
+            PrintSize::fit(Length::Inches(1.0), 0),
+            PrintSize::fit(Length::Inches(-1.0), 300),
+            PrintSize::min_module(Length::Millimetres(0.0), 300),
+            PrintSize::min_module(Length::Inches(f64::NAN), 300),
+            // 10 km at 600 dpi fits u32 but not the raster limit.
+            PrintSize::fit(Length::Millimetres(1e7), 600),
+            PrintSize::min_module(Length::Inches(100.0), 2400),
+        ] {
+            assert!(
+                matches!(qr.print_layout(&size), Err(QrcError::InvalidOption(_))),
+                "{size:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_for_print_records_dpi() {
+        let qr = QRCode::from_string("Hello".to_string());
+        let size = PrintSize::min_module(Length::Millimetres(0.33), 300);
+        let png = qr.encode_for_print(&size, OutputFormat::Png).unwrap();
+        // 300 dpi is 11811 pixels per metre.
+        let phys = png_chunk(&png, b"pHYs").unwrap();
+        assert_eq!(phys, [0, 0, 0x2E, 0x23, 0, 0, 0x2E, 0x23, 1]);
+        let img = image::load_from_memory(&png).unwrap();
+        assert_eq!((img.width(), img.height()), (116, 116));
+
+        let jpeg = qr
+            .encode_for_print(&size, OutputFormat::Jpeg { quality: 90 })
+            .unwrap();
+        // JFIF APP0: version, units = dots per inch, then X and Y density.
+        let at = jpeg.windows(5).position(|w| w == b"JFIF\0").unwrap() + 7;
+        assert_eq!(&jpeg[at..at + 5], &[1, 1, 44, 1, 44]);
+
+        // Icons have fixed pixel sizes and no resolution to record.
+        let ico = qr.encode_for_print(
+            &size,
+            OutputFormat::Ico {
+                extra_sizes: vec![],
+            },
+        );
+        assert!(matches!(ico, Err(QrcError::InvalidOption(_))));
+    }
+
+    #[test]
+    fn test_print_layout_drives_vector_size() {
+        let qr = QRCode::from_string("Hello".to_string());
+        let layout = qr
+            .print_layout(&PrintSize::fit(Length::Millimetres(25.4), 100))
+            .unwrap();
+        // 100 dots / 29 modules = 3 dots per module, 87 dots = 0.87 in.
+        assert_eq!(layout.width_px, 87);
+        let pdf = qr.to_pdf(layout.size);
+        assert!(find(&pdf, "/MediaBox [0 0 62.64 62.64]") > 0);
+        let svg = qr.to_svg_with_options(0, &SvgOptions::default().with_size(layout.size));
+        assert!(svg.contains("width=\"22.098mm\" height=\"22.098mm\" viewBox=\"0 0 29 29\""));
+    }
+
+    // ── Version, mask and mode ──────────────────────────────────────────
+
+    #[test]
+    fn test_fixed_and_min_versions() {
+        let fixed = QRCode::from_string(URL.to_string()).with_version(SymbolVersion::Fixed(10));
+        let symbol = fixed.to_qrcode();
+        assert_eq!(symbol.version(), Version::Normal(10));
+        assert_eq!(symbol.width(), 57);
+        assert_eq!(
+            qrc::decode::decode(&fixed.to_image(600)).unwrap().text(),
+            Some(URL)
+        );
+
+        // A minimum only raises small symbols; larger data still grows.
+        let min = SymbolVersion::Min(4);
+        assert_eq!(
+            QRCode::from_string("Hi".to_string())
+                .with_version(min)
+                .to_qrcode()
+                .version(),
+            Version::Normal(4)
+        );
+        let long = QRCode::from_string("x".repeat(200)).with_version(min);
+        assert!(long.to_qrcode().width() > 33);
+    }
+
+    #[test]
+    fn test_fixed_version_reports_overflow() {
+        let qr = QRCode::from_string("x".repeat(100)).with_version(SymbolVersion::Fixed(3));
+        let Err(QrcError::CapacityExceeded { needed, available }) = qr.try_to_qrcode() else {
+            panic!("100 bytes cannot fit in version 3");
+        };
+        assert!(needed > available);
+        // Version 3 at level M holds 44 data codewords.
+        assert_eq!(available, 44 * 8);
+        assert!(qr.try_to_png(256).is_err());
+
+        for version in [SymbolVersion::Fixed(0), SymbolVersion::Min(41)] {
+            let qr = QRCode::from_string("Hi".to_string()).with_version(version);
+            assert!(matches!(
+                qr.try_to_qrcode(),
+                Err(QrcError::InvalidOption(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_every_mask_pattern_round_trips() {
+        for mask in 0..8 {
+            let qr = QRCode::from_string(URL.to_string()).with_mask(mask);
+            assert_eq!(qr.to_qrcode().mask(), mask);
+            let decoded = qrc::decode::decode(&qr.to_image(300)).unwrap();
+            assert_eq!((decoded.mask, decoded.text()), (mask, Some(URL)));
+        }
+        let masks: Vec<_> = (0..8)
+            .map(|mask| {
+                QRCode::from_string(URL.to_string())
+                    .with_mask(mask)
+                    .to_qrcode()
+            })
+            .collect();
+        assert!(masks.windows(2).all(|pair| pair[0] != pair[1]));
+        let qr = QRCode::from_string(URL.to_string()).with_mask(8);
+        assert!(matches!(
+            qr.try_to_qrcode(),
+            Err(QrcError::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_forced_segment_modes() {
+        let digits = "0123456789".repeat(8);
+        let size = |mode| {
+            QRCode::from_string(digits.clone())
+                .with_mode(mode)
+                .to_qrcode()
+                .width()
+        };
+        assert_eq!(size(SegmentMode::Numeric), size(SegmentMode::Auto));
+        assert!(size(SegmentMode::Byte) > size(SegmentMode::Numeric));
+
+        for (data, mode) in [
+            (digits.as_bytes().to_vec(), SegmentMode::Numeric),
+            (b"HELLO WORLD $%*+-./:".to_vec(), SegmentMode::Alphanumeric),
+            (b"123".to_vec(), SegmentMode::Byte),
+            (b"\x93\x5f\xe4\xaa".to_vec(), SegmentMode::Kanji),
+        ] {
+            let qr = QRCode::new(data.clone()).with_mode(mode);
+            let decoded = qrc::decode::decode(&qr.to_image(300)).unwrap();
+            assert_eq!(decoded.data, data, "{mode:?}");
+        }
+
+        for (data, mode) in [
+            ("12a", SegmentMode::Numeric),
+            ("hello", SegmentMode::Alphanumeric),
+            ("odd", SegmentMode::Kanji),
+        ] {
+            let qr = QRCode::from_string(data.to_string()).with_mode(mode);
+            let err = qr.try_to_qrcode().unwrap_err();
+            assert!(err.to_string().contains(&format!("{mode:?}")), "{err}");
+        }
+    }
+
+    // ── Logos ───────────────────────────────────────────────────────────
+
+    fn logo() -> RgbaImage {
+        RgbaImage::from_fn(80, 60, |x, _| {
+            if x < 40 {
+                Rgba([200, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        })
+    }
+
+    #[test]
+    fn test_max_logo_size_follows_ec_level_and_budget() {
+        let qr = QRCode::from_string(URL.to_string());
+        let opts = LogoOptions::default();
+        let sizes: Vec<f64> = [EcLevel::M, EcLevel::H]
+            .into_iter()
+            .map(|level| {
+                qr.clone()
+                    .with_ec_level(level)
+                    .max_logo_size(&opts)
+                    .unwrap()
+            })
+            .collect();
+        assert!(sizes[1] > sizes[0]);
+        let generous = qr
+            .clone()
+            .with_ec_level(EcLevel::H)
+            .max_logo_size(&opts.with_budget(1.0))
+            .unwrap();
+        assert!(generous > sizes[1]);
+
+        // Version 1 at level L keeps 3 of its 7 EC codewords for misdecode
+        // protection, leaving no room at all.
+        let tiny = QRCode::from_string("hi".to_string()).with_ec_level(EcLevel::L);
+        let err = tiny.max_logo_size(&opts).unwrap_err();
+        assert!(err.to_string().contains("no logo fits"), "{err}");
+    }
+
+    #[test]
+    fn test_logo_is_centred_and_still_decodes() {
+        let qr = QRCode::from_string(URL.to_string()).with_ec_level(EcLevel::H);
+        let img = qr.to_image_with_logo(&logo(), &LogoOptions::default());
+        let n = qr.to_qrcode().width() as u32;
+        assert_eq!(img.dimensions(), ((n + 8) * 10, (n + 8) * 10));
+        let centre = img.width() / 2;
+        assert!(img
+            .enumerate_pixels()
+            .filter(|&(x, y, _)| x.abs_diff(centre) < 20 && y.abs_diff(centre) < 20)
+            .any(|(_, _, p)| p.0 == [200, 0, 0, 255]));
+
+        let decoded = qrc::decode::decode(&img).unwrap();
+        assert_eq!(decoded.text(), Some(URL));
+        let report = qr.scannability(&img).unwrap();
+        assert!(report.passed);
+        assert!(report
+            .block_errors
+            .iter()
+            .all(|&e| e * 2 <= report.correctable));
+    }
+
+    #[test]
+    fn test_logo_keeps_alignment_patterns_and_shape() {
+        let opts = LogoOptions::default().with_budget(1.0).with_module_px(6);
+        let qr = |version: u8| {
+            QRCode::from_string("x".repeat(40))
+                .with_ec_level(EcLevel::H)
+                .with_version(SymbolVersion::Fixed(version))
+                .with_shape(ModuleShape::Circle)
+        };
+        // Version 7 has an alignment pattern at (22, 22), the very centre.
+        let err = qr(7).try_to_image_with_logo(&logo(), &opts).unwrap_err();
+        assert!(err.to_string().contains("no logo fits"), "{err}");
+
+        // Version 14 puts them at 6, 26, 46 and 66: the logo sits between.
+        let plain = qr(14).to_image_scaled(6);
+        let img = qr(14).to_image_with_logo(&logo(), &opts);
+        assert!(img.pixels().any(|p| p.0 == [200, 0, 0, 255]));
+        for cy in [26, 46, 66] {
+            for cx in [26, 46, 66] {
+                for y in (cy + 4 - 2) * 6..(cy + 4 + 3) * 6 {
+                    for x in (cx + 4 - 2) * 6..(cx + 4 + 3) * 6 {
+                        assert_eq!(img.get_pixel(x, y), plain.get_pixel(x, y), "({cx}, {cy})");
+                    }
+                }
+            }
+        }
+        // The middle of each alignment pattern is dark, drawn as a circle:
+        // its centre is the dark colour but its corner is not.
+        let middle = (46 + 4) * 6;
+        assert_eq!(img.get_pixel(middle + 3, middle + 3).0, [0, 0, 0, 255]);
+        assert_eq!(img.get_pixel(middle, middle).0, [255, 255, 255, 255]);
+        assert!(qrc::decode::decode(&img).is_ok());
+    }
+
+    #[test]
+    fn test_logo_rejects_oversized_and_invalid_requests() {
+        let qr = QRCode::from_string(URL.to_string()).with_ec_level(EcLevel::H);
+        let err = qr
+            .try_to_image_with_logo(&logo(), &LogoOptions::default().with_size(0.6))
+            .unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+        assert!(matches!(err, QrcError::InvalidOption(_)));
+
+        let max = qr.max_logo_size(&LogoOptions::default()).unwrap();
+        assert!(qr
+            .try_to_image_with_logo(&logo(), &LogoOptions::default().with_size(max))
+            .is_ok());
+
+        for opts in [
+            LogoOptions::default().with_budget(0.0),
+            LogoOptions::default().with_size(1.5),
+            LogoOptions::default().with_module_px(0),
+            LogoOptions::default().with_module_px(u32::MAX / 4),
+        ] {
+            assert!(qr.try_to_image_with_logo(&logo(), &opts).is_err());
+        }
+        let empty = RgbaImage::new(0, 0);
+        assert!(qr
+            .try_to_image_with_logo(&empty, &LogoOptions::default())
+            .is_err());
+    }
+
+    // ── Micro QR and rMQR ───────────────────────────────────────────────
+
+    #[test]
+    fn test_micro_versions_and_levels() {
+        let micro = |data: &str, version: u8| {
+            QRCode::from_string(data.to_string())
+                .with_symbol_type(SymbolType::Micro)
+                .with_ec_level(EcLevel::L)
+                .with_version(SymbolVersion::Fixed(version))
+        };
+        for version in 1..=4 {
+            let symbol = micro("123", version).to_qrcode();
+            assert_eq!(symbol.symbol_type(), SymbolType::Micro);
+            assert_eq!(symbol.version(), Version::Micro(i16::from(version)));
+            assert_eq!(symbol.width(), 9 + 2 * usize::from(version));
+            assert_eq!(symbol.height(), symbol.width());
+        }
+        // M1 only has an error-detection level; H is not defined at all.
+        let m1 = micro("123", 1).with_ec_level(EcLevel::M);
+        let err = m1.try_to_qrcode().unwrap_err();
+        assert!(matches!(err, QrcError::InvalidOption(_)), "{err:?}");
+        // M1 has no byte mode, so no amount of room would help.
+        let bytes = micro("a", 1).with_mode(SegmentMode::Byte);
+        let err = bytes.try_to_qrcode().unwrap_err();
+        assert!(err.to_string().contains("version 1"), "{err}");
+        let high = QRCode::from_string("123".to_string())
+            .with_symbol_type(SymbolType::Micro)
+            .with_ec_level(EcLevel::H);
+        let err = high.try_to_qrcode().unwrap_err();
+        assert!(err.to_string().contains("EcLevel::H"), "{err}");
+        assert!(micro("x".repeat(40).as_str(), 4).try_to_qrcode().is_err());
+        assert!(micro("123", 5).try_to_qrcode().is_err());
+    }
+
+    #[test]
+    fn test_micro_masks_and_quiet_zone() {
+        let qr = QRCode::from_string("MICRO".to_string()).with_symbol_type(SymbolType::Micro);
+        assert_eq!(qr.quiet_zone, 2);
+        // An explicit quiet zone survives the symbol type, in either order.
+        let before = QRCode::from_string("MICRO".to_string())
+            .with_quiet_zone(6)
+            .with_symbol_type(SymbolType::Micro);
+        let after = QRCode::from_string("MICRO".to_string())
+            .with_symbol_type(SymbolType::Micro)
+            .with_quiet_zone(6);
+        assert_eq!((before.quiet_zone, after.quiet_zone), (6, 6));
+        let back = qr.clone().with_symbol_type(SymbolType::Qr);
+        assert_eq!(back.quiet_zone, DEFAULT_QUIET_ZONE);
+        let symbols: Vec<_> = (0..4)
+            .map(|mask| {
+                let symbol = qr.clone().with_mask(mask).to_qrcode();
+                assert_eq!(symbol.mask(), mask);
+                symbol
+            })
+            .collect();
+        assert!(symbols.windows(2).all(|pair| pair[0] != pair[1]));
+        let err = qr.with_mask(4).try_to_qrcode().unwrap_err();
+        assert!(err.to_string().contains("Micro QR mask"), "{err}");
+
+        // One finder in the top-left corner, timing along the top edge.
+        let symbol = QRCode::from_string("MICRO".to_string())
+            .with_symbol_type(SymbolType::Micro)
+            .to_qrcode();
+        let n = symbol.width();
+        let colors = symbol.to_colors();
+        assert!((0..n).all(|x| (colors[x] == Color::Dark) == (x < 7 || x % 2 == 0)));
+    }
+
+    #[test]
+    fn test_rmqr_sizes_and_capacity() {
+        let heights = [7, 9, 11, 13, 15, 17];
+        let widths = [43, 59, 77, 99, 139, 27];
+        let mut sizes = 0;
+        for height in heights {
+            for width in widths {
+                let Some(version) = SymbolVersion::rmqr(height, width) else {
+                    continue;
+                };
+                sizes += 1;
+                let symbol = QRCode::from_string("1".to_string())
+                    .with_symbol_type(SymbolType::Rmqr)
+                    .with_version(version)
+                    .to_qrcode();
+                assert_eq!((symbol.width(), symbol.height()), (width, height));
+                assert_eq!(symbol.to_colors().len(), width * height);
+            }
+        }
+        assert_eq!(sizes, 32);
+        assert_eq!(SymbolVersion::rmqr(7, 27), None);
+        assert_eq!(SymbolVersion::rmqr(12, 43), None);
+
+        // R7x43 at level M holds six data codewords: five bytes.
+        let r7x43 = |data: &str| {
+            QRCode::from_string(data.to_string())
+                .with_symbol_type(SymbolType::Rmqr)
+                .with_mode(SegmentMode::Byte)
+                .with_version(SymbolVersion::rmqr(7, 43).unwrap())
+                .try_to_qrcode()
+        };
+        assert!(r7x43("abcde").is_ok());
+        let Err(QrcError::CapacityExceeded { needed, available }) = r7x43("abcdef") else {
+            panic!("six bytes cannot fit in R7x43");
+        };
+        assert_eq!((needed, available), (54, 48));
+
+        // Without a fixed size the smallest area wins: R11x27 over R7x43.
+        let auto = QRCode::from_string("HELLO".to_string())
+            .with_symbol_type(SymbolType::Rmqr)
+            .to_qrcode();
+        assert_eq!((auto.width(), auto.height()), (27, 11));
+        assert_eq!(auto.version(), Version::Normal(11));
+    }
+
+    #[test]
+    fn test_rmqr_rejects_unsupported_options() {
+        let qr = QRCode::from_string("RMQR".to_string()).with_symbol_type(SymbolType::Rmqr);
+        for level in [EcLevel::L, EcLevel::Q] {
+            let err = qr.clone().with_ec_level(level).try_to_qrcode().unwrap_err();
+            assert!(
+                err.to_string().contains("EcLevel::M and EcLevel::H"),
+                "{err}"
+            );
+        }
+        assert!(qr.clone().with_ec_level(EcLevel::H).try_to_qrcode().is_ok());
+        assert_eq!(qr.clone().with_mask(0).to_qrcode().mask(), 0);
+        assert!(qr.clone().with_mask(1).try_to_qrcode().is_err());
+        assert!(qr
+            .clone()
+            .with_version(SymbolVersion::Fixed(33))
+            .try_to_qrcode()
+            .is_err());
+
+        let img = qr.to_image(200);
+        assert!(qr.max_logo_size(&LogoOptions::default()).is_err());
+        assert!(qr.scannability(&img).is_err());
+        let micro = QRCode::from_string("1".to_string()).with_symbol_type(SymbolType::Micro);
+        assert!(micro.scannability(&micro.to_image(200)).is_err());
+    }
+
+    #[test]
+    fn test_rmqr_format_information_copies_agree() {
+        // Both copies carry the same 18-bit BCH word under different masks.
+        let symbol = QRCode::from_string("FORMAT".to_string())
+            .with_symbol_type(SymbolType::Rmqr)
+            .with_ec_level(EcLevel::H)
+            .with_version(SymbolVersion::rmqr(11, 59).unwrap())
+            .to_qrcode();
+        let (w, h) = (symbol.width(), symbol.height());
+        let colors = symbol.to_colors();
+        let bit = |x: usize, y: usize| u32::from(colors[y * w + x] == Color::Dark);
+        let (mut finder, mut sub) = (0, 0);
+        for n in 0..18 {
+            finder |= bit(8 + n / 5, 1 + n % 5) << n;
+            let (x, y) = if n < 15 {
+                (w - 8 + n / 5, h - 6 + n % 5)
+            } else {
+                (w - 20 + n, h - 6)
+            };
+            sub |= bit(x, y) << n;
+        }
+        let (finder, sub) = (
+            finder ^ 0b01_1111_1010_1011_0010,
+            sub ^ 0b10_0000_1010_0111_1011,
+        );
+        assert_eq!(finder, sub);
+        // Level H and R11x59, version 13, over a 12-bit BCH remainder.
+        assert_eq!(finder >> 12, 0b10_0000 | 12);
+    }
+
+    /// Renders `symbol` as one string per row, `#` for dark modules.
+    fn module_rows(symbol: &qrc::Symbol) -> Vec<String> {
+        let colors = symbol.to_colors();
+        colors
+            .chunks(symbol.width())
+            .map(|row| {
+                row.iter()
+                    .map(|&c| if c == Color::Dark { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rmqr_known_answers() {
+        // Reference matrices from a separate ISO/IEC 23941 implementation
+        // (own tables, BCH, Reed-Solomon, placement and mask), not derived
+        // from this encoder.
+        let rmqr = |data: &str, mode, (height, width)| {
+            QRCode::from_string(data.to_string())
+                .with_symbol_type(SymbolType::Rmqr)
+                .with_ec_level(EcLevel::M)
+                .with_mode(mode)
+                .with_version(SymbolVersion::rmqr(height, width).unwrap())
+                .to_qrcode()
+        };
+        // R7x43 at level M holds 12 digits, the capacity the standard lists.
+        let r7x43 = [
+            "#######.#.#.#.#.#.#.###.#.#.#.#.#.#.#.#.###",
+            "#.....#..#.#.######.#.#.....#...##.##...#.#",
+            "#.###.#.#.###...#..####.##.#.##.#.#########",
+            "#.###.#..##..##.###........#######....#...#",
+            "#.###.#...#.##...######..##.#####.##..#.#.#",
+            "#.....#.####.########.#.##.#..#.#.###.#...#",
+            "#######.#.#.#.#.#.#.###.#.#.#.#.#.#.#.#####",
+        ];
+        let symbol = rmqr("123456789012", SegmentMode::Numeric, (7, 43));
+        assert_eq!(module_rows(&symbol), r7x43);
+        assert!(QRCode::from_string("1234567890123".to_string())
+            .with_symbol_type(SymbolType::Rmqr)
+            .with_version(SymbolVersion::rmqr(7, 43).unwrap())
+            .try_to_qrcode()
+            .is_err());
+
+        // Two blocks, two alignment patterns and the bottom-left corner mark.
+        let r13x59 = [
+            "#######.#.#.#.#.#.###.#.#.#.#.#.#.#.#.###.#.#.#.#.#.#.#.###",
+            "#.....#.##.#...#..#.###...#.#.###.###.#.#...#..######.##..#",
+            "#.###.#.......##.#####...######.#.#######.##.###.###.##..##",
+            "#.###.#.###.##..##....#####..#....##.....##.##.....####....",
+            "#.###.#.........#..#....####...#.#..#.#####..##.....#####.#",
+            "#.....#...#.##..##..#.##.#.##.###..###..#####.#.###..#.....",
+            "#######.##.#.#.##..#...###.####.#...#..###.#..###.##..#...#",
+            "........##..#.##.....#.#.##.##....###.#....#.#.#.#..#..###.",
+            "######...#.####..####.#.######.#.##.##.#.#######.#..#.#####",
+            ".#.##.##.#.#.###....#....#.#.#..#....##..###.##...##..#...#",
+            "#..#...##..###.#.####..#...#...#.#.#..####...##.####.##.#.#",
+            "#..#....#..##..#..#.#...#..#######.#..#.##.######.#...#...#",
+            "###.#.#.#.#.#.#.#.###.#.#.#.#.#.#.#.#.###.#.#.#.#.#.#.#####",
+        ];
+        let symbol = rmqr("RMQR ISO/IEC 23941", SegmentMode::Alphanumeric, (13, 59));
+        assert_eq!(module_rows(&symbol), r13x59);
+    }
+
+    #[test]
+    fn test_rectangular_renderers_keep_proportions() {
+        let qr = QRCode::from_string("ABC".to_string())
+            .with_symbol_type(SymbolType::Rmqr)
+            .with_version(SymbolVersion::rmqr(7, 43).unwrap());
+        // 43 × 7 modules plus a two-module quiet zone: 47 × 11.
+        assert_eq!(qr.to_png(470).dimensions(), (470, 110));
+        assert_eq!(qr.to_image_scaled(2).dimensions(), (94, 22));
+        assert_eq!(qr.colorize(NAVY).dimensions(), (47, 11));
+        assert_eq!(qr.resize(94, 22).dimensions(), (94, 22));
+        assert!(qr.to_svg(470).contains("width=\"470\" height=\"110\""));
+
+        let pdf = qr.to_pdf(Length::Points(470.0));
+        assert!(find(&pdf, "/MediaBox [0 0 470 110]") > 0);
+        let text = qr.to_terminal(&TerminalOptions::default());
+        assert_eq!(text.lines().count(), 6);
+        assert!(text.lines().all(|line| line.chars().count() == 47));
+
+        let micro = QRCode::from_string("1".to_string()).with_symbol_type(SymbolType::Micro);
+        // M1 only offers level L, so level M needs M2: 13 modules.
+        assert_eq!(micro.to_image_scaled(1).dimensions(), (17, 17));
+    }
+
+    // ── Structured Append ───────────────────────────────────────────────
+
+    #[test]
+    fn test_split_into_round_trips_in_any_order() {
+        let message = "Structured Append carries one message across linked symbols.";
+        let parts = QRCode::from_string(message.to_string()).split_into(4);
+        assert_eq!(parts.codes().len(), 4);
+        let parity = message.bytes().fold(0, |p, b| p ^ b);
+        assert_eq!(parts.parity(), parity);
+
+        let mut decoded: Vec<_> = parts
+            .codes()
+            .iter()
+            .rev()
+            .map(|code| qrc::decode::decode(&code.to_image(300)).unwrap())
+            .collect();
+        decoded.sort_by_key(|d| d.structured_append.unwrap().position);
+        for (i, d) in (0..).zip(&decoded) {
+            let header = d.structured_append.unwrap();
+            assert_eq!(header, Sequence::new(i, 4, message.as_bytes()));
+        }
+        let joined: Vec<u8> = decoded.iter().flat_map(|d| d.data.clone()).collect();
+        assert_eq!(joined, message.as_bytes());
+        // A plain code carries no header.
+        let plain = qrc::decode::decode(&QRCode::from_string(URL.to_string()).to_image(300));
+        assert_eq!(plain.unwrap().structured_append, None);
+    }
+
+    #[test]
+    fn test_structured_append_uses_fewest_symbols() {
+        let qr = QRCode::from_string("y".repeat(100)).with_version(SymbolVersion::Fixed(2));
+        let parts = qr.to_structured_append();
+        // Version 2-M holds 28 data codewords: 20 header bits, 12 mode and
+        // count bits, then up to 24 bytes.
+        assert_eq!(parts.codes().len(), 5);
+        assert!(parts
+            .codes()
+            .iter()
+            .all(|code| code.to_qrcode().version() == Version::Normal(2)));
+        let sizes: Vec<usize> = parts.codes().iter().map(|c| c.data.len()).collect();
+        assert_eq!(sizes, [20; 5]);
+
+        let short = QRCode::from_string("short".to_string()).to_structured_append();
+        assert_eq!(short.codes().len(), 1);
+        assert_eq!(short.codes()[0].structured_append.unwrap().total, 1);
+
+        let huge = QRCode::from_string("z".repeat(500)).with_version(SymbolVersion::Fixed(1));
+        assert!(matches!(
+            huge.try_to_structured_append(),
+            Err(QrcError::CapacityExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_structured_append_header_costs_twenty_bits() {
+        let header = Sequence::new(0, 2, b"x");
+        let qr = QRCode::from_string("x".repeat(14)).with_version(SymbolVersion::Fixed(1));
+        assert!(qr.try_to_qrcode().is_ok());
+        let Err(QrcError::CapacityExceeded { needed, available }) =
+            qr.with_structured_append(header).try_to_qrcode()
+        else {
+            panic!("14 bytes and a header cannot fit in version 1");
+        };
+        assert_eq!((needed, available), (144, 128));
+    }
+
+    #[test]
+    fn test_structured_append_rejects_invalid_splits() {
+        let qr = QRCode::from_string("abc".to_string());
+        for count in [0, 4, 17] {
+            assert!(matches!(
+                qr.try_split_into(count),
+                Err(QrcError::InvalidOption(_))
+            ));
+        }
+        let micro = qr.clone().with_symbol_type(SymbolType::Micro);
+        assert!(micro.try_split_into(2).is_err());
+        let header = Sequence::new(3, 3, b"abc");
+        assert!(qr
+            .clone()
+            .with_structured_append(header)
+            .try_to_qrcode()
+            .is_err());
+
+        // Kanji characters stay whole.
+        let kanji = QRCode::new(b"\x93\x5f\xe4\xaa\x93\x5f".to_vec()).with_mode(SegmentMode::Kanji);
+        let sizes: Vec<usize> = kanji
+            .split_into(2)
+            .codes()
+            .iter()
+            .map(|c| c.data.len())
+            .collect();
+        assert_eq!(sizes, [4, 2]);
+    }
+
+    #[test]
+    fn test_structured_append_sheet_layout() {
+        let parts = QRCode::from_string("ABCDEFGHIJ".repeat(4))
+            .with_style(QrStyle::new(NAVY, CREAM))
+            .split_into(5);
+        let side = (21 + 8) * 2;
+        let sheet = parts.to_sheet(2, 3);
+        assert_eq!(sheet.dimensions(), (side * 3, side * 2));
+        // The empty sixth cell is background; the fifth holds a finder.
+        assert_eq!(sheet.get_pixel(side * 2 + 9, side + 9).0, CREAM.0);
+        assert_eq!(sheet.get_pixel(side + 9, side + 9).0, NAVY.0);
+        assert_eq!(parts.to_sheet(2, 10).dimensions(), (side * 5, side));
+        assert!(parts.try_to_sheet(2, 0).is_err());
+        assert!(parts.try_to_sheet(0, 1).is_err());
+        assert!(matches!(
+            parts.try_to_sheet(u32::MAX / 4, 1),
+            Err(QrcError::InvalidOption(_))
+        ));
+        // Each 11 600 px part fits the raster limit; five in a row do not.
+        assert!(matches!(
+            parts.try_to_sheet(400, 5),
+            Err(QrcError::InvalidOption(_))
+        ));
+    }
+
+    // ── ECI ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_eci_round_trips_every_family() {
+        for (label, text) in [
+            ("ISO-8859-1", "Grüße aus Köln"),
+            ("latin2", "Zażółć gęślą jaźń"),
+            ("cyrillic", "Съешь же ещё"),
+            ("greek", "Γειά σου κόσμε"),
+            ("iso_8859-8", "שלום עולם"),
+            ("iso8859-11", "สวัสดีครับ"),
+            ("iso-8859-15", "Prix : 5 €"),
+            ("Shift_JIS", "日本語のテキスト ｶﾀｶﾅ"),
+            ("utf-16be", "Snow ☃ and 🎉"),
+            ("utf-8", "naïve ✓"),
+        ] {
+            let charset = CharacterSet::from_label(label).unwrap();
+            let qr = set_encoding_format!(QRCode::from_string(text.to_string()), label).unwrap();
+            assert_eq!(qr.get_encoding_format(), charset.name());
+            assert_eq!(qr.character_set, Some(charset));
+            let decoded = qrc::decode::decode(&qr.to_image(400)).unwrap();
+            assert_eq!(decoded.eci, Some(charset.eci()), "{label}");
+            assert_eq!(decoded.decoded_text().as_deref(), Some(text), "{label}");
+        }
+        // Without a character set no designator is written.
+        let plain = qrc::decode::decode(&QRCode::from_string(URL.to_string()).to_image(300));
+        assert_eq!(plain.unwrap().eci, None);
+    }
+
+    #[test]
+    fn test_eci_designator_and_kanji_costs() {
+        // The designator takes a 4-bit mode indicator and one byte.
+        let qr = QRCode::from_string("x".repeat(14))
+            .with_version(SymbolVersion::Fixed(1))
+            .set_encoding_format("utf-8")
+            .unwrap();
+        let Err(QrcError::CapacityExceeded { needed, available }) = qr.try_to_qrcode() else {
+            panic!("14 bytes and a designator cannot fit in version 1");
+        };
+        assert_eq!((needed, available), (136, 128));
+
+        // Shift JIS text packs each kanji into 13 bits.
+        let text = "漢字".repeat(10);
+        let width = |label: &str| {
+            QRCode::from_string(text.clone())
+                .set_encoding_format(label)
+                .unwrap()
+                .to_qrcode()
+                .width()
+        };
+        assert!(width("sjis") < width("utf-8"));
+
+        // UTF-16 bytes that look like Shift JIS stay in byte mode: seven
+        // characters need 24 + 7 × 16 bits, more than version 1 holds.
+        let utf16 = QRCode::from_string("語".repeat(7))
+            .with_version(SymbolVersion::Fixed(1))
+            .set_character_set(CharacterSet::Utf16Be)
+            .unwrap();
+        let Err(QrcError::CapacityExceeded { needed, .. }) = utf16.try_to_qrcode() else {
+            panic!("byte mode cannot fit seven UTF-16 characters in version 1");
+        };
+        assert_eq!(needed, 136);
+    }
+
+    #[test]
+    fn test_eci_rejections_and_retranscoding() {
+        let qr = QRCode::from_string("Prix 5 €".to_string());
+        for label in ["latin-1", "klingon", "iso-8859-12", "iso-8859-0"] {
+            let err = qr.set_encoding_format(label).unwrap_err();
+            assert!(err.to_string().contains(label), "{err}");
+        }
+        let err = qr.set_encoding_format("latin1").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'€' cannot be encoded in ISO-8859-1"));
+
+        // The data is read in its current set before it is transcoded again.
+        let latin9 = qr.set_character_set(CharacterSet::Iso8859_15).unwrap();
+        assert_eq!(latin9.data, b"Prix 5 \xa4");
+        let utf16 = latin9.set_character_set(CharacterSet::Utf16Be).unwrap();
+        assert_eq!(
+            CharacterSet::Utf16Be.decode(&utf16.data).as_deref(),
+            Some("Prix 5 €")
+        );
+        let raw = QRCode::new(vec![0xFF, 0xFE]);
+        assert!(raw.set_character_set(CharacterSet::Iso8859_1).is_err());
+
+        let micro = QRCode::from_string("abc".to_string())
+            .with_symbol_type(SymbolType::Micro)
+            .set_encoding_format("utf-8")
+            .unwrap();
+        assert!(micro.try_to_qrcode().is_err());
+        let kanji = latin9.with_mode(SegmentMode::Kanji);
+        assert!(kanji.try_to_qrcode().is_err());
+
+        assert_eq!(
+            CharacterSet::ShiftJis.decode(b"\x93\xfa"),
+            Some("日".to_string())
+        );
+        assert_eq!(CharacterSet::ShiftJis.decode(b"\x93"), None);
+        assert_eq!(CharacterSet::Iso8859_3.decode(b"\xa5"), None);
+        assert_eq!(CharacterSet::from_eci(20), Some(CharacterSet::ShiftJis));
+        assert_eq!(CharacterSet::from_eci(14), None);
+    }
+
+    #[test]
+    fn test_eci_with_structured_append() {
+        let qr = QRCode::from_string("Καλημέρα κόσμε, ".repeat(4))
+            .set_encoding_format("greek")
+            .unwrap();
+        let parts = qr.split_into(2);
+        let mut joined = Vec::new();
+        for code in parts.codes() {
+            let decoded = qrc::decode::decode(&code.to_image(300)).unwrap();
+            assert_eq!(decoded.eci, Some(9));
+            assert!(decoded.structured_append.is_some());
+            joined.extend(decoded.data);
+        }
+        assert_eq!(joined, qr.data);
+    }
+
+    // ── Segment optimiser ───────────────────────────────────────────────
+
+    const EMVCO: &str =
+        "00020101021129370016A000000677010111011300668123456785802TH53037646304ABCD";
+
+    #[test]
+    fn test_optimal_segments_never_cost_more() {
+        let auto = QRCode::from_string(EMVCO.to_string());
+        let optimal = auto.clone().with_mode(SegmentMode::Optimal);
+        let (a, o) = (auto.to_qrcode(), optimal.to_qrcode());
+        assert!(o.data_bits() < a.data_bits());
+        assert!(o.width() <= a.width());
+        assert_eq!(o.capacity_bits(), a.capacity_bits());
+
+        let modes: Vec<SegmentMode> = o.segments().iter().map(|span| span.mode).collect();
+        assert_eq!(
+            modes,
+            [
+                SegmentMode::Numeric,
+                SegmentMode::Alphanumeric,
+                SegmentMode::Numeric,
+                SegmentMode::Alphanumeric
+            ]
+        );
+        let decoded = qrc::decode::decode(&optimal.to_image(400)).unwrap();
+        assert_eq!(decoded.text(), Some(EMVCO));
+
+        let wifi = "WIFI:T:WPA;S:Cafe;P:CORRECTHORSE1234;;";
+        let auto = QRCode::from_string(wifi.to_string()).to_qrcode();
+        let optimal = QRCode::from_string(wifi.to_string())
+            .with_mode(SegmentMode::Optimal)
+            .to_qrcode();
+        assert!(optimal.data_bits() <= auto.data_bits());
+        assert!(optimal.width() <= auto.width());
+    }
+
+    #[test]
+    fn test_optimal_segment_splits() {
+        let symbol = QRCode::from_string("ABC12345678".to_string())
+            .with_mode(SegmentMode::Optimal)
+            .to_qrcode();
+        let spans = symbol.segments();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(
+            (spans[0].mode, spans[0].range.clone()),
+            (SegmentMode::Alphanumeric, 0..3)
+        );
+        assert_eq!(
+            (spans[1].mode, spans[1].range.clone()),
+            (SegmentMode::Numeric, 3..11)
+        );
+        // 4 + 9 + 17 bits of letters, then 4 + 10 + 27 bits of digits.
+        assert_eq!(symbol.data_bits(), 30 + 41);
+
+        // Two digits between letters are not worth a segment of their own.
+        let symbol = QRCode::from_string("AB12CD".to_string())
+            .with_mode(SegmentMode::Optimal)
+            .to_qrcode();
+        assert_eq!(symbol.segments().len(), 1);
+        assert_eq!(symbol.data_bits(), 4 + 9 + 33);
+
+        // Shift JIS pairs go in Kanji mode unless another set is announced.
+        let mut sjis = b"\x93\xfa\x96\x7b".to_vec();
+        sjis.extend_from_slice(b"12345");
+        let symbol = QRCode::new(sjis)
+            .with_mode(SegmentMode::Optimal)
+            .to_qrcode();
+        let modes: Vec<SegmentMode> = symbol.segments().iter().map(|span| span.mode).collect();
+        assert_eq!(modes, [SegmentMode::Kanji, SegmentMode::Numeric]);
+        let utf8 = QRCode::from_string("日本12345".to_string())
+            .set_encoding_format("utf-8")
+            .unwrap()
+            .with_mode(SegmentMode::Optimal)
+            .to_qrcode();
+        assert!(utf8
+            .segments()
+            .iter()
+            .all(|span| span.mode != SegmentMode::Kanji));
+    }
+
+    #[test]
+    fn test_optimal_segments_in_micro_qr() {
+        let qr = QRCode::from_string("ABC1234".to_string())
+            .with_symbol_type(SymbolType::Micro)
+            .with_ec_level(EcLevel::L)
+            .with_mode(SegmentMode::Optimal);
+        let symbol = qr.to_qrcode();
+        assert_eq!(symbol.version(), Version::Micro(2));
+        assert_eq!(symbol.segments().len(), 2);
+        assert!(symbol.data_bits() <= symbol.capacity_bits());
+
+        // M1 holds digits only, so letters move the data to a larger size.
+        let digits = QRCode::from_string("12345".to_string())
+            .with_symbol_type(SymbolType::Micro)
+            .with_ec_level(EcLevel::L)
+            .with_mode(SegmentMode::Optimal)
+            .to_qrcode();
+        assert_eq!(digits.version(), Version::Micro(1));
+        assert_eq!(digits.data_bits(), 3 + 17);
+    }
+
+    #[test]
+    fn test_symbol_reports_bits_for_every_path() {
+        let plain = QRCode::from_string("HELLO".to_string()).to_qrcode();
+        assert_eq!(plain.data_bits(), 4 + 9 + 28);
+        assert_eq!(plain.capacity_bits(), 16 * 8);
+
+        let sequence = Sequence::new(0, 1, b"HELLO");
+        let linked = QRCode::from_string("HELLO".to_string())
+            .with_structured_append(sequence)
+            .to_qrcode();
+        assert_eq!(linked.data_bits(), 20 + 41);
+
+        let rmqr = QRCode::from_string("HELLO".to_string())
+            .with_symbol_type(SymbolType::Rmqr)
+            .with_mode(SegmentMode::Optimal)
+            .to_qrcode();
+        assert_eq!(rmqr.segments().len(), 1);
+        assert_eq!(rmqr.segments()[0].mode, SegmentMode::Alphanumeric);
+        assert!(rmqr.data_bits() <= rmqr.capacity_bits());
+    }
+
     // ── Shape tests ─────────────────────────────────────────────────────
 
     #[test]
@@ -274,8 +2229,8 @@ mod tests {
     #[test]
     fn test_shape_svg_rounded() {
         let qr = QRCode::from_string("shapes".to_string()).with_shape(ModuleShape::RoundedSquare);
-        let svg = qr.to_svg(256);
-        assert!(svg.contains("rx="));
-        assert!(svg.contains("ry="));
+        let svg = qr.to_svg(290);
+        assert_eq!(svg.matches("<path").count(), 1);
+        assert!(svg.contains("a3 3 0 0 1"));
     }
 }